@@ -0,0 +1,65 @@
+//! Lists the meetings of an installed App module whose stake is still held after they ended and,
+//! with `--submit`, returns those stakes in batches.
+//!
+//! Without `--submit` this is a dry run that only queries the chain. Point it at a local node
+//! (see `just juno-local`) to try it out.
+//!
+//! ## Example
+//!
+//! ```bash
+//! $ cargo run --example resolve-pending --features daemon -- --network-id juno-1 --account-id 1 --before 2024-03-01
+//! ```
+
+use abstract_core::objects::AccountId;
+use app::interface::daemon::CalendarDeployment;
+use clap::Parser;
+use cw_orch::{anyhow, prelude::networks::parse_network, tokio::runtime::Runtime};
+
+#[derive(Parser, Default, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Arguments {
+    /// Network Id the app is installed on
+    #[arg(short, long)]
+    network_id: String,
+    /// Local sequence of the account the app is installed on
+    #[arg(short, long)]
+    account_id: u32,
+    /// Only meetings that ended before this date (`%Y-%m-%d`) are resolved
+    #[arg(short, long)]
+    before: String,
+    /// Return the stakes instead of only listing them
+    #[arg(long)]
+    submit: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    dotenv::dotenv().ok();
+    env_logger::init();
+    let args = Arguments::parse();
+
+    let rt = Runtime::new()?;
+    let deployment = CalendarDeployment::connect(
+        rt.handle(),
+        parse_network(&args.network_id),
+        AccountId::local(args.account_id),
+    )?;
+
+    let pending = deployment.pending_resolutions(&args.before)?;
+    for resolution in &pending {
+        println!(
+            "day {} meeting {}: {} staked by {}",
+            resolution.day_datetime,
+            resolution.meeting_index,
+            resolution.meeting.amount_staked,
+            resolution.meeting.requester
+        );
+    }
+
+    if args.submit {
+        let resolved = deployment.resolve_all_pending(&args.before)?;
+        println!("returned {resolved} stakes");
+    } else {
+        println!("dry run: {} stakes pending", pending.len());
+    }
+    Ok(())
+}
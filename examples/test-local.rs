@@ -70,6 +70,11 @@ fn main() -> anyhow::Result<()> {
                 hour: 17,
                 minute: 0,
             },
+            self_gap_minutes: 0,
+            accepted_denoms: vec![],
+            name: None,
+            description: None,
+            contact_uri: None,
         },
         None,
     )?;
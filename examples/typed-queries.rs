@@ -0,0 +1,79 @@
+//! Installs the App module on a mock Abstract deployment and reads its state through the typed
+//! query functions generated for `AppQueryMsg`.
+//!
+//! # Run
+//!
+//! `cargo run --example typed-queries`
+
+use abstract_core::objects::{gov_type::GovernanceDetails, AccountId, AssetEntry};
+use abstract_interface::{Abstract, AppDeployer, VCExecFns};
+use app::{
+    contract::{APP_ID, APP_VERSION},
+    msg::{AppInstantiateMsg, ConfigResponse, InfoResponse, MeetingsResponse, Time},
+    AppInterface, AppQueryMsgFns,
+};
+use cosmwasm_std::Uint128;
+use cw_asset::AssetInfo;
+use cw_orch::{anyhow, deploy::Deploy, prelude::*};
+
+const ADMIN: &str = "admin";
+const DENOM: &str = "juno>stake";
+
+fn main() -> anyhow::Result<()> {
+    let sender = Addr::unchecked(ADMIN);
+    let mock = Mock::new(&sender);
+
+    let app = AppInterface::new(APP_ID, mock.clone());
+    let abstr_deployment = Abstract::deploy_on(mock.clone(), sender.to_string())?;
+
+    abstr_deployment.ans_host.execute(
+        &abstract_core::ans_host::ExecuteMsg::UpdateAssetAddresses {
+            to_add: vec![(DENOM.to_owned(), AssetInfo::native(DENOM).into())],
+            to_remove: vec![],
+        },
+        None,
+    )?;
+
+    let account =
+        abstr_deployment
+            .account_factory
+            .create_default_account(GovernanceDetails::Monarchy {
+                monarch: ADMIN.to_string(),
+            })?;
+    abstr_deployment
+        .version_control
+        .claim_namespace(AccountId::local(1), "my-namespace".to_string())?;
+
+    app.deploy(APP_VERSION.parse()?)?;
+    account.install_app(
+        app.clone(),
+        &AppInstantiateMsg {
+            price_per_minute: Uint128::from(1u128),
+            denom: AssetEntry::from(DENOM),
+            utc_offset: 0,
+            start_time: Time { hour: 9, minute: 0 },
+            end_time: Time {
+                hour: 17,
+                minute: 0,
+            },
+            self_gap_minutes: 0,
+            accepted_denoms: vec![],
+            name: None,
+            description: None,
+            contact_uri: None,
+        },
+        None,
+    )?;
+
+    let config: ConfigResponse = app.config()?;
+    println!("config: {config:?}");
+
+    let info: InfoResponse = app.info()?;
+    println!("info: {info:?}");
+
+    let day_datetime = mock.block_info()?.time.seconds() as i64 / 86400 * 86400;
+    let meetings: MeetingsResponse = app.meetings(day_datetime)?;
+    println!("meetings on {day_datetime}: {:?}", meetings.meetings);
+
+    Ok(())
+}
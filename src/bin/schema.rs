@@ -0,0 +1,14 @@
+//! Writes the JSON schemas of the Abstract-wrapped messages to `schema/` for use with
+//! `@cosmwasm/ts-codegen`.
+
+use app::msg::{AppInstantiateMsg, ExecuteMsg, MigrateMsg, QueryMsg};
+use cosmwasm_schema::write_api;
+
+fn main() {
+    write_api! {
+        instantiate: AppInstantiateMsg,
+        execute: ExecuteMsg,
+        query: QueryMsg,
+        migrate: MigrateMsg,
+    }
+}
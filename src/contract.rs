@@ -11,6 +11,28 @@ use cosmwasm_std::Response;
 pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// The id of the app
 pub const APP_ID: &str = "abstract:calendar";
+/// The maximum number of meetings handled by a single batch message
+pub const MAX_BATCH: usize = 50;
+/// The number of entries of a page of a list query without a limit
+pub const DEFAULT_PAGE_LIMIT: u32 = 10;
+/// The maximum number of entries of a page of a list query
+pub const MAX_PAGE_LIMIT: u32 = MAX_BATCH as u32;
+/// The number of seconds a slot held by `HoldSlot` can be confirmed for
+pub const HOLD_TTL_SECONDS: u64 = 15 * 60;
+/// The number of seconds before the block time `NeedsAttention` looks back for meetings
+pub const ATTENTION_HORIZON_SECONDS: i64 = 90 * 86_400;
+/// The maximum number of days covered by `AvailabilityHeatmap`
+pub const MAX_HEATMAP_DAYS: u32 = 62;
+/// The maximum number of days listed by `MeetingsForDays`
+pub const MAX_LISTED_DAYS: u32 = 31;
+/// The maximum number of characters of the name of the calendar
+pub const MAX_NAME_LENGTH: usize = 64;
+/// The maximum number of characters of the description of the calendar
+pub const MAX_DESCRIPTION_LENGTH: usize = 512;
+/// The maximum number of characters of the contact URI of the calendar
+pub const MAX_CONTACT_URI_LENGTH: usize = 256;
+/// The maximum number of bytes of the encrypted payload of a meeting
+pub const MAX_ENCRYPTED_PAYLOAD_BYTES: usize = 1024;
 
 /// The type of the result returned by your app's entry points.
 pub type AppResult<T = Response> = Result<T, AppError>;
@@ -18,7 +40,7 @@ pub type AppResult<T = Response> = Result<T, AppError>;
 /// The type of the app that is used to build your app and access the Abstract SDK features.
 pub type App = AppContract<AppError, AppInstantiateMsg, AppExecuteMsg, AppQueryMsg, AppMigrateMsg>;
 
-const APP: App = App::new(APP_ID, APP_VERSION, None)
+pub(crate) const APP: App = App::new(APP_ID, APP_VERSION, None)
     .with_instantiate(handlers::instantiate_handler)
     .with_execute(handlers::execute_handler)
     .with_query(handlers::query_handler)
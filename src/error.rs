@@ -1,77 +1,576 @@
+//! Errors returned by the calendar.
+//!
+//! Every variant has a stable code, returned by [`AppError::code`] and prefixed to its message,
+//! so that clients can match on the code instead of the wording. Codes are assigned in order and
+//! never reused: a new variant takes the next free code and a removed variant retires its code.
+//!
+//! | Code | Variant |
+//! |------|---------|
+//! | `CAL-001` | [`AppError::Std`] |
+//! | `CAL-002` | [`AppError::Abstract`] |
+//! | `CAL-003` | [`AppError::AbstractSdk`] |
+//! | `CAL-004` | [`AppError::Asset`] |
+//! | `CAL-005` | [`AppError::Admin`] |
+//! | `CAL-006` | [`AppError::DappError`] |
+//! | `CAL-007` | [`AppError::PaymentError`] |
+//! | `CAL-008` | [`AppError::StartTimeMustBeInFuture`] |
+//! | `CAL-009` | [`AppError::StartTimeDoesNotFallWithinCalendarBounds`] |
+//! | `CAL-010` | [`AppError::StartTimeAtCalendarClose`] |
+//! | `CAL-011` | [`AppError::EndTimeDoesNotFallWithinCalendarBounds`] |
+//! | `CAL-012` | [`AppError::EndTimeMustBeAfterStartTime`] |
+//! | `CAL-013` | [`AppError::MeetingConflictExists`] |
+//! | `CAL-014` | [`AppError::InvalidUtcOffset`] |
+//! | `CAL-015` | [`AppError::WeekdayNotAllowed`] |
+//! | `CAL-016` | [`AppError::InvalidTimeValue`] |
+//! | `CAL-017` | [`AppError::CalendarStartNotBeforeEnd`] |
+//! | `CAL-018` | [`AppError::LocalTimeOutOfRange`] |
+//! | `CAL-019` | [`AppError::AmbiguousLocalTime`] |
+//! | `CAL-020` | [`AppError::InvalidMeetingStart`] |
+//! | `CAL-021` | [`AppError::InvalidMeetingEnd`] |
+//! | `CAL-022` | [`AppError::TimestampOutOfRange`] |
+//! | `CAL-023` | [`AppError::TimestampNotWholeSeconds`] |
+//! | `CAL-024` | [`AppError::InvalidIsoTime`] |
+//! | `CAL-025` | [`AppError::IsoTimeOffsetMismatch`] |
+//! | `CAL-026` | [`AppError::Overflow`] |
+//! | `CAL-027` | [`AppError::ConversionOverflow`] |
+//! | `CAL-028` | [`AppError::StartAndEndTimeNotOnSameDay`] |
+//! | `CAL-029` | [`AppError::StartTimeNotRoundedToNearestMinute`] |
+//! | `CAL-030` | [`AppError::EndTimeNotRoundedToNearestMinute`] |
+//! | `CAL-031` | [`AppError::InvalidStakeAmountSent`] |
+//! | `CAL-032` | [`AppError::PaymentNotRequired`] |
+//! | `CAL-033` | [`AppError::PaymentRequired`] |
+//! | `CAL-034` | [`AppError::NoMeetingsAtGivenDayDateTime`] |
+//! | `CAL-035` | [`AppError::MeetingDoesNotExist`] |
+//! | `CAL-036` | [`AppError::MeetingNotFoundByKey`] |
+//! | `CAL-037` | [`AppError::DayKeyMismatch`] |
+//! | `CAL-038` | [`AppError::MeetingAlreadyStarted`] |
+//! | `CAL-039` | [`AppError::PastCancellationDeadline`] |
+//! | `CAL-040` | [`AppError::MeetingAlreadyEnded`] |
+//! | `CAL-041` | [`AppError::ExtensionMustEndLater`] |
+//! | `CAL-042` | [`AppError::NotMeetingRequester`] |
+//! | `CAL-043` | [`AppError::RefundNotFound`] |
+//! | `CAL-044` | [`AppError::NotRefundRecipient`] |
+//! | `CAL-045` | [`AppError::RefundClaimExpired`] |
+//! | `CAL-046` | [`AppError::AdminBookingNotAllowed`] |
+//! | `CAL-047` | [`AppError::PayoutsFrozen`] |
+//! | `CAL-048` | [`AppError::RefundClaimNotExpired`] |
+//! | `CAL-049` | [`AppError::HoldNotFound`] |
+//! | `CAL-050` | [`AppError::HoldExpired`] |
+//! | `CAL-051` | [`AppError::SlotHeld`] |
+//! | `CAL-052` | [`AppError::SlotAvailable`] |
+//! | `CAL-053` | [`AppError::WaitlistEntryNotFound`] |
+//! | `CAL-054` | [`AppError::NotAttestor`] |
+//! | `CAL-055` | [`AppError::MeetingNotAttested`] |
+//! | `CAL-056` | [`AppError::MeetingDisputed`] |
+//! | `CAL-057` | [`AppError::MeetingNotInProgress`] |
+//! | `CAL-058` | [`AppError::MeetingNotFinishedYet`] |
+//! | `CAL-059` | [`AppError::StakeAlreadyHandled`] |
+//! | `CAL-060` | [`AppError::MeetingTooLong`] |
+//! | `CAL-061` | [`AppError::MinutesLateCannotExceedDurationOfMeeting`] |
+//! | `CAL-062` | [`AppError::InvalidSlashCurve`] |
+//! | `CAL-063` | [`AppError::SelfGapViolated`] |
+//! | `CAL-064` | [`AppError::InvalidRefundSplits`] |
+//! | `CAL-065` | [`AppError::BatchTooLarge`] |
+//! | `CAL-066` | [`AppError::MeetingsHoldStake`] |
+//! | `CAL-067` | [`AppError::DayNotEmpty`] |
+//! | `CAL-068` | [`AppError::ImportedMeetingsOverlap`] |
+//! | `CAL-069` | [`AppError::DenomNotAccepted`] |
+//! | `CAL-070` | [`AppError::StakeAboveMax`] |
+//! | `CAL-071` | [`AppError::UnresolvableAcceptedDenom`] |
+//! | `CAL-072` | [`AppError::NonNativeAcceptedDenom`] |
+//! | `CAL-073` | [`AppError::InsufficientRecoverableFunds`] |
+//! | `CAL-074` | [`AppError::InsufficientCredit`] |
+//! | `CAL-075` | [`AppError::InvalidGranularity`] |
+//! | `CAL-076` | [`AppError::TimeNotAlignedToGranularity`] |
+//! | `CAL-077` | [`AppError::MeetingsNotAligned`] |
+//! | `CAL-078` | [`AppError::InsufficientContractBalance`] |
+//! | `CAL-079` | [`AppError::PriceSourceNotSet`] |
+//! | `CAL-080` | [`AppError::PriceSourceUnavailable`] |
+//! | `CAL-081` | [`AppError::RangeTooLarge`] |
+//! | `CAL-082` | [`AppError::ProfileFieldTooLong`] |
+//! | `CAL-083` | [`AppError::ProfileFieldHasControlCharacters`] |
+//! | `CAL-084` | [`AppError::InvalidSlashDistribution`] |
+//! | `CAL-085` | [`AppError::EncryptedPayloadTooLarge`] |
+//! | `CAL-086` | [`AppError::ZeroMeetingDuration`] |
+//! | `CAL-087` | [`AppError::MeetingNotWholeMinutes`] |
+//! | `CAL-088` | [`AppError::InvalidDenom`] |
+//! | `CAL-089` | [`AppError::DuplicateImportedDay`] |
+//! | `CAL-090` | [`AppError::ZeroLimit`] |
+//! | `CAL-091` | [`AppError::NoAdminSet`] |
+//! | `CAL-092` | [`AppError::ImportedMeetingResolved`] |
+
 use abstract_app::AppError as AbstractAppError;
 use abstract_core::AbstractError;
 use abstract_sdk::AbstractSdkError;
-use cosmwasm_std::{StdError, Uint128};
+use cosmwasm_std::{StdError, Timestamp, Uint128};
 use cw_asset::AssetError;
 use cw_controllers::AdminError;
 use cw_utils::PaymentError;
 use thiserror::Error;
 
+use crate::msg::Time;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum AppError {
-    #[error("{0}")]
+    #[error("CAL-001: {0}")]
     Std(#[from] StdError),
 
-    #[error("{0}")]
+    #[error("CAL-002: {0}")]
     Abstract(#[from] AbstractError),
 
-    #[error("{0}")]
+    #[error("CAL-003: {0}")]
     AbstractSdk(#[from] AbstractSdkError),
 
-    #[error("{0}")]
+    #[error("CAL-004: {0}")]
     Asset(#[from] AssetError),
 
-    #[error("{0}")]
+    #[error("CAL-005: {0}")]
     Admin(#[from] AdminError),
 
-    #[error("{0}")]
+    #[error("CAL-006: {0}")]
     DappError(#[from] AbstractAppError),
 
-    #[error("{0}")]
+    #[error("CAL-007: {0}")]
     PaymentError(#[from] PaymentError),
 
-    #[error("Start time must be in future")]
+    #[error("CAL-008: Start time must be in future")]
     StartTimeMustBeInFuture {},
 
-    #[error("Start time does not fall within calendar bounds")]
-    StartTimeDoesNotFallWithinCalendarBounds {},
+    #[error(
+        "CAL-009: Start time {time} does not fall within calendar bounds {calendar_start}-{calendar_end}"
+    )]
+    StartTimeDoesNotFallWithinCalendarBounds {
+        time: Time,
+        calendar_start: Time,
+        calendar_end: Time,
+    },
+
+    #[error("CAL-010: Meetings cannot start at {time}, the close of the calendar hours")]
+    StartTimeAtCalendarClose { time: Time },
 
-    #[error("End time does not fall within calendar bounds")]
-    EndTimeDoesNotFallWithinCalendarBounds {},
+    #[error(
+        "CAL-011: End time {time} does not fall within calendar bounds {calendar_start}-{calendar_end}"
+    )]
+    EndTimeDoesNotFallWithinCalendarBounds {
+        time: Time,
+        calendar_start: Time,
+        calendar_end: Time,
+    },
 
-    #[error("End time must be after start time")]
+    #[error("CAL-012: End time must be after start time")]
     EndTimeMustBeAfterStartTime {},
 
-    #[error("Meeting conflict exists")]
-    MeetingConflictExists {},
+    #[error(
+        "CAL-013: Meeting conflicts with the meeting from {existing_start_local} \
+         ({existing_start}) to {existing_end_local} ({existing_end})"
+    )]
+    MeetingConflictExists {
+        existing_start: i64,
+        existing_end: i64,
+        existing_start_local: String,
+        existing_end_local: String,
+    },
+
+    #[error("CAL-014: Invalid utc offset {offset}, must be within 14 hours")]
+    InvalidUtcOffset { offset: i32 },
+
+    #[error("CAL-015: Meetings cannot be booked on this weekday")]
+    WeekdayNotAllowed {},
+
+    #[error("CAL-016: {hour:02}:{minute:02} is not a time of the day")]
+    InvalidTimeValue { hour: u32, minute: u32 },
+
+    #[error("CAL-017: Calendar start time must be before its end time")]
+    CalendarStartNotBeforeEnd {},
+
+    #[error("CAL-018: Timestamp {timestamp} is out of range at UTC offset {utc_offset}s")]
+    LocalTimeOutOfRange { timestamp: i64, utc_offset: i32 },
+
+    #[error("CAL-019: Timestamp {timestamp} is ambiguous at UTC offset {utc_offset}s")]
+    AmbiguousLocalTime { timestamp: i64, utc_offset: i32 },
+
+    #[error("CAL-020: Invalid meeting start: {reason}")]
+    InvalidMeetingStart { reason: Box<AppError> },
 
-    #[error("Invalid time")]
-    InvalidTime {},
+    #[error("CAL-021: Invalid meeting end: {reason}")]
+    InvalidMeetingEnd { reason: Box<AppError> },
 
-    #[error("Start and end time not on same day")]
+    #[error("CAL-022: Timestamp {value} is out of range")]
+    TimestampOutOfRange { value: i64 },
+
+    #[error("CAL-023: Timestamp {timestamp} is not a whole number of seconds")]
+    TimestampNotWholeSeconds { timestamp: Timestamp },
+
+    #[error("CAL-024: Cannot parse \"{input}\" as an ISO 8601 time: {reason}")]
+    InvalidIsoTime { input: String, reason: String },
+
+    #[error("CAL-025: \"{input}\" is not in the calendar's UTC offset of {utc_offset} seconds")]
+    IsoTimeOffsetMismatch { input: String, utc_offset: i32 },
+
+    #[error("CAL-026: Overflow computing with {lhs} and {rhs}")]
+    Overflow { lhs: Uint128, rhs: Uint128 },
+
+    #[error("CAL-027: Cannot convert {value} to {target}")]
+    ConversionOverflow { value: String, target: String },
+
+    #[error("CAL-028: Start and end time not on same day")]
     StartAndEndTimeNotOnSameDay {},
 
-    #[error("Start time not rounded to nearest minute")]
+    #[error("CAL-029: Start time not rounded to nearest minute")]
     StartTimeNotRoundedToNearestMinute {},
 
-    #[error("End time not rounded to nearest minute")]
+    #[error("CAL-030: End time not rounded to nearest minute")]
     EndTimeNotRoundedToNearestMinute {},
 
-    #[error("Invalid stack amount sent. Expected_amount: {expected_amount}")]
-    InvalidStakeAmountSent { expected_amount: Uint128 },
+    #[error(
+        "CAL-031: Invalid stake amount sent. Expected: {expected_amount}, sent: {amount_sent}"
+    )]
+    InvalidStakeAmountSent {
+        expected_amount: Uint128,
+        amount_sent: Uint128,
+    },
+
+    #[error("CAL-032: Meeting is free, no payment should be sent")]
+    PaymentNotRequired {},
+
+    #[error("CAL-033: Payment in {denom} required: {reason}")]
+    PaymentRequired { denom: String, reason: String },
+
+    #[error("CAL-034: No meetings on day {day_datetime}")]
+    NoMeetingsAtGivenDayDateTime { day_datetime: i64 },
+
+    #[error("CAL-035: Meeting {meeting_index} does not exist on day {day_datetime}")]
+    MeetingDoesNotExist {
+        day_datetime: i64,
+        meeting_index: u32,
+    },
+
+    #[error("CAL-036: No meeting of {requester} starts at {start_time} on day {day_datetime}")]
+    MeetingNotFoundByKey {
+        day_datetime: i64,
+        requester: String,
+        start_time: i64,
+    },
+
+    #[error("CAL-037: {day_datetime} is not the day key of the meeting")]
+    DayKeyMismatch { day_datetime: i64 },
+
+    #[error("CAL-038: Meeting already started")]
+    MeetingAlreadyStarted {},
+
+    #[error("CAL-039: The cancellation deadline of the meeting has passed")]
+    PastCancellationDeadline {},
+
+    #[error("CAL-040: Meeting already ended")]
+    MeetingAlreadyEnded {},
+
+    #[error("CAL-041: Extension must end after the current end time")]
+    ExtensionMustEndLater {},
+
+    #[error("CAL-042: Only the requester of the meeting can do this")]
+    NotMeetingRequester {},
+
+    #[error("CAL-043: Queued refund {refund_id} not found")]
+    RefundNotFound { refund_id: u64 },
+
+    #[error("CAL-044: Only the recipient of the refund can claim it")]
+    NotRefundRecipient {},
+
+    #[error("CAL-045: Claim deadline of the refund has passed")]
+    RefundClaimExpired {},
+
+    #[error(
+        "CAL-046: The admin cannot book meetings in their own calendar, use BlockSlot instead"
+    )]
+    AdminBookingNotAllowed {},
+
+    #[error("CAL-047: Payouts are frozen")]
+    PayoutsFrozen {},
+
+    #[error("CAL-048: Refund can still be claimed")]
+    RefundClaimNotExpired {},
+
+    #[error("CAL-049: Hold {hold_id} not found")]
+    HoldNotFound { hold_id: u64 },
+
+    #[error("CAL-050: Hold has expired")]
+    HoldExpired {},
+
+    #[error("CAL-051: Slot is held for another booking")]
+    SlotHeld {},
+
+    #[error("CAL-052: Slot is free, book it instead of joining the waitlist")]
+    SlotAvailable {},
 
-    #[error("No meetings at given day datetime")]
-    NoMeetingsAtGivenDayDateTime {},
+    #[error("CAL-053: Waitlist entry {entry_id} not found")]
+    WaitlistEntryNotFound { entry_id: u64 },
 
-    #[error("Meeting does not exist")]
-    MeetingDoesNotExist {},
+    #[error("CAL-054: Only the admin or attestor can do this")]
+    NotAttestor {},
 
-    #[error("Meeting not finished yet")]
-    MeetingNotFinishedYet {},
+    #[error(
+        "CAL-055: Attendance of meeting {meeting_index} on day {day_datetime} has not been attested"
+    )]
+    MeetingNotAttested {
+        day_datetime: i64,
+        meeting_index: u32,
+    },
 
-    #[error("Stake already handled")]
-    StakeAlreadyHandled {},
+    #[error("CAL-056: Attendance of meeting {meeting_index} on day {day_datetime} is disputed")]
+    MeetingDisputed {
+        day_datetime: i64,
+        meeting_index: u32,
+    },
 
-    #[error("Minutes late cannot exceed duration of meeting")]
+    #[error("CAL-057: Meeting {meeting_index} on day {day_datetime} is not in progress")]
+    MeetingNotInProgress {
+        day_datetime: i64,
+        meeting_index: u32,
+    },
+
+    #[error("CAL-058: Meeting {meeting_index} on day {day_datetime} not finished yet")]
+    MeetingNotFinishedYet {
+        day_datetime: i64,
+        meeting_index: u32,
+    },
+
+    #[error("CAL-059: Stake of meeting {meeting_index} on day {day_datetime} already handled")]
+    StakeAlreadyHandled {
+        day_datetime: i64,
+        meeting_index: u32,
+    },
+
+    #[error("CAL-060: Meeting from {start_time} to {end_time} lasts longer than a day")]
+    MeetingTooLong { start_time: i64, end_time: i64 },
+
+    #[error("CAL-061: Minutes late cannot exceed duration of meeting")]
     MinutesLateCannotExceedDurationOfMeeting {},
+
+    #[error(
+        "CAL-062: Grace of {grace_minutes} minutes must be shorter than {full_after_minutes} \
+         minutes"
+    )]
+    InvalidSlashCurve {
+        grace_minutes: u32,
+        full_after_minutes: u32,
+    },
+
+    #[error(
+        "CAL-063: Meeting is within {self_gap_minutes} minutes of another meeting of the requester"
+    )]
+    SelfGapViolated { self_gap_minutes: u32 },
+
+    #[error("CAL-064: Refund splits must add up to 10000 basis points, got {total_bps}")]
+    InvalidRefundSplits { total_bps: u32 },
+
+    #[error("CAL-065: Cannot handle more than {max} meetings in a single batch")]
+    BatchTooLarge { max: u32 },
+
+    #[error(
+        "CAL-066: {count} meetings hold stake in the current denom, set force to change it anyway"
+    )]
+    MeetingsHoldStake { count: u32 },
+
+    #[error("CAL-067: Day {day_datetime} already has meetings, set force to overwrite it")]
+    DayNotEmpty { day_datetime: i64 },
+
+    #[error("CAL-068: Imported meetings on day {day_datetime} overlap at {start_time}")]
+    ImportedMeetingsOverlap { day_datetime: i64, start_time: i64 },
+
+    #[error("CAL-069: Stakes cannot be paid in {denom}")]
+    DenomNotAccepted { denom: String },
+
+    #[error(
+        "CAL-070: Stake of {expected_amount} including fees is above the maximum of \
+         {max_total_stake}"
+    )]
+    StakeAboveMax {
+        expected_amount: Uint128,
+        max_total_stake: Uint128,
+    },
+
+    #[error("CAL-071: Accepted denom {entry} is not registered in the name service")]
+    UnresolvableAcceptedDenom { entry: String },
+
+    #[error("CAL-072: Accepted denom {entry} is not a native denom")]
+    NonNativeAcceptedDenom { entry: String },
+
+    #[error("CAL-073: Only {available} of the balance is not owed to requesters")]
+    InsufficientRecoverableFunds { available: Uint128 },
+
+    #[error("CAL-074: Insufficient credit. Available: {available}")]
+    InsufficientCredit { available: Uint128 },
+
+    #[error(
+        "CAL-075: Granularity of {granularity_seconds} seconds must be whole minutes dividing a day"
+    )]
+    InvalidGranularity { granularity_seconds: u32 },
+
+    #[error("CAL-076: {time} is not aligned to the granularity of {granularity_seconds} seconds")]
+    TimeNotAlignedToGranularity {
+        time: Time,
+        granularity_seconds: u32,
+    },
+
+    #[error(
+        "CAL-077: {count} upcoming meetings are not aligned to the granularity of {granularity_seconds} \
+         seconds, set force to change it anyway"
+    )]
+    MeetingsNotAligned {
+        count: u32,
+        granularity_seconds: u32,
+    },
+
+    #[error("CAL-078: Contract balance of {available} cannot cover the {needed} it owes")]
+    InsufficientContractBalance { needed: Uint128, available: Uint128 },
+
+    #[error("CAL-079: Stakes are pegged to a price source, but none is set")]
+    PriceSourceNotSet {},
+
+    #[error("CAL-080: Price source has no usable price for {denom}: {reason}")]
+    PriceSourceUnavailable { denom: String, reason: String },
+
+    #[error("CAL-081: Cannot cover more than {max_days} days in a single query")]
+    RangeTooLarge { max_days: u32 },
+
+    #[error("CAL-082: {field} has {length} characters, at most {max} are allowed")]
+    ProfileFieldTooLong {
+        field: String,
+        length: usize,
+        max: usize,
+    },
+
+    #[error("CAL-083: {field} cannot contain control characters")]
+    ProfileFieldHasControlCharacters { field: String },
+
+    #[error(
+        "CAL-084: Slash distribution shares must add up to 10000 basis points, got {total_bps}"
+    )]
+    InvalidSlashDistribution { total_bps: u32 },
+
+    #[error("CAL-085: Encrypted payload of {size} bytes is larger than {max} bytes")]
+    EncryptedPayloadTooLarge { size: usize, max: usize },
+
+    #[error("CAL-086: Cannot slash the stake of a meeting lasting zero minutes")]
+    ZeroMeetingDuration {},
+
+    #[error(
+        "CAL-087: Meeting from {start_time} to {end_time} does not last a whole number of minutes"
+    )]
+    MeetingNotWholeMinutes { start_time: i64, end_time: i64 },
+
+    #[error("CAL-088: \"{denom}\" is not a valid denom")]
+    InvalidDenom { denom: String },
+
+    #[error("CAL-089: Day {day_datetime} is imported more than once")]
+    DuplicateImportedDay { day_datetime: i64 },
+
+    #[error("CAL-090: The limit must be at least one")]
+    ZeroLimit {},
+
+    #[error("CAL-091: No admin is set, so only the attestor can do this")]
+    NoAdminSet {},
+
+    #[error("CAL-092: Imported meeting on day {day_datetime} at {start_time} is already resolved")]
+    ImportedMeetingResolved { day_datetime: i64, start_time: i64 },
+}
+
+impl AppError {
+    /// Stable code of the error, see the [module documentation](self) for the full table.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Std { .. } => "CAL-001",
+            Self::Abstract { .. } => "CAL-002",
+            Self::AbstractSdk { .. } => "CAL-003",
+            Self::Asset { .. } => "CAL-004",
+            Self::Admin { .. } => "CAL-005",
+            Self::DappError { .. } => "CAL-006",
+            Self::PaymentError { .. } => "CAL-007",
+            Self::StartTimeMustBeInFuture { .. } => "CAL-008",
+            Self::StartTimeDoesNotFallWithinCalendarBounds { .. } => "CAL-009",
+            Self::StartTimeAtCalendarClose { .. } => "CAL-010",
+            Self::EndTimeDoesNotFallWithinCalendarBounds { .. } => "CAL-011",
+            Self::EndTimeMustBeAfterStartTime { .. } => "CAL-012",
+            Self::MeetingConflictExists { .. } => "CAL-013",
+            Self::InvalidUtcOffset { .. } => "CAL-014",
+            Self::WeekdayNotAllowed { .. } => "CAL-015",
+            Self::InvalidTimeValue { .. } => "CAL-016",
+            Self::CalendarStartNotBeforeEnd { .. } => "CAL-017",
+            Self::LocalTimeOutOfRange { .. } => "CAL-018",
+            Self::AmbiguousLocalTime { .. } => "CAL-019",
+            Self::InvalidMeetingStart { .. } => "CAL-020",
+            Self::InvalidMeetingEnd { .. } => "CAL-021",
+            Self::TimestampOutOfRange { .. } => "CAL-022",
+            Self::TimestampNotWholeSeconds { .. } => "CAL-023",
+            Self::InvalidIsoTime { .. } => "CAL-024",
+            Self::IsoTimeOffsetMismatch { .. } => "CAL-025",
+            Self::Overflow { .. } => "CAL-026",
+            Self::ConversionOverflow { .. } => "CAL-027",
+            Self::StartAndEndTimeNotOnSameDay { .. } => "CAL-028",
+            Self::StartTimeNotRoundedToNearestMinute { .. } => "CAL-029",
+            Self::EndTimeNotRoundedToNearestMinute { .. } => "CAL-030",
+            Self::InvalidStakeAmountSent { .. } => "CAL-031",
+            Self::PaymentNotRequired { .. } => "CAL-032",
+            Self::PaymentRequired { .. } => "CAL-033",
+            Self::NoMeetingsAtGivenDayDateTime { .. } => "CAL-034",
+            Self::MeetingDoesNotExist { .. } => "CAL-035",
+            Self::MeetingNotFoundByKey { .. } => "CAL-036",
+            Self::DayKeyMismatch { .. } => "CAL-037",
+            Self::MeetingAlreadyStarted { .. } => "CAL-038",
+            Self::PastCancellationDeadline { .. } => "CAL-039",
+            Self::MeetingAlreadyEnded { .. } => "CAL-040",
+            Self::ExtensionMustEndLater { .. } => "CAL-041",
+            Self::NotMeetingRequester { .. } => "CAL-042",
+            Self::RefundNotFound { .. } => "CAL-043",
+            Self::NotRefundRecipient { .. } => "CAL-044",
+            Self::RefundClaimExpired { .. } => "CAL-045",
+            Self::AdminBookingNotAllowed { .. } => "CAL-046",
+            Self::PayoutsFrozen { .. } => "CAL-047",
+            Self::RefundClaimNotExpired { .. } => "CAL-048",
+            Self::HoldNotFound { .. } => "CAL-049",
+            Self::HoldExpired { .. } => "CAL-050",
+            Self::SlotHeld { .. } => "CAL-051",
+            Self::SlotAvailable { .. } => "CAL-052",
+            Self::WaitlistEntryNotFound { .. } => "CAL-053",
+            Self::NotAttestor { .. } => "CAL-054",
+            Self::MeetingNotAttested { .. } => "CAL-055",
+            Self::MeetingDisputed { .. } => "CAL-056",
+            Self::MeetingNotInProgress { .. } => "CAL-057",
+            Self::MeetingNotFinishedYet { .. } => "CAL-058",
+            Self::StakeAlreadyHandled { .. } => "CAL-059",
+            Self::MeetingTooLong { .. } => "CAL-060",
+            Self::MinutesLateCannotExceedDurationOfMeeting { .. } => "CAL-061",
+            Self::InvalidSlashCurve { .. } => "CAL-062",
+            Self::SelfGapViolated { .. } => "CAL-063",
+            Self::InvalidRefundSplits { .. } => "CAL-064",
+            Self::BatchTooLarge { .. } => "CAL-065",
+            Self::MeetingsHoldStake { .. } => "CAL-066",
+            Self::DayNotEmpty { .. } => "CAL-067",
+            Self::ImportedMeetingsOverlap { .. } => "CAL-068",
+            Self::DenomNotAccepted { .. } => "CAL-069",
+            Self::StakeAboveMax { .. } => "CAL-070",
+            Self::UnresolvableAcceptedDenom { .. } => "CAL-071",
+            Self::NonNativeAcceptedDenom { .. } => "CAL-072",
+            Self::InsufficientRecoverableFunds { .. } => "CAL-073",
+            Self::InsufficientCredit { .. } => "CAL-074",
+            Self::InvalidGranularity { .. } => "CAL-075",
+            Self::TimeNotAlignedToGranularity { .. } => "CAL-076",
+            Self::MeetingsNotAligned { .. } => "CAL-077",
+            Self::InsufficientContractBalance { .. } => "CAL-078",
+            Self::PriceSourceNotSet { .. } => "CAL-079",
+            Self::PriceSourceUnavailable { .. } => "CAL-080",
+            Self::RangeTooLarge { .. } => "CAL-081",
+            Self::ProfileFieldTooLong { .. } => "CAL-082",
+            Self::ProfileFieldHasControlCharacters { .. } => "CAL-083",
+            Self::InvalidSlashDistribution { .. } => "CAL-084",
+            Self::EncryptedPayloadTooLarge { .. } => "CAL-085",
+            Self::ZeroMeetingDuration { .. } => "CAL-086",
+            Self::MeetingNotWholeMinutes { .. } => "CAL-087",
+            Self::InvalidDenom { .. } => "CAL-088",
+            Self::DuplicateImportedDay { .. } => "CAL-089",
+            Self::ZeroLimit { .. } => "CAL-090",
+            Self::NoAdminSet { .. } => "CAL-091",
+            Self::ImportedMeetingResolved { .. } => "CAL-092",
+        }
+    }
 }
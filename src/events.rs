@@ -0,0 +1,142 @@
+//! Typed events with a fixed set of keys, emitted next to the tagged `wasm-abstract` event so
+//! that indexers do not depend on the attributes of each handler. Every event carries
+//! [`KEY_SCHEMA_VERSION`], which is bumped whenever a key is renamed or removed.
+
+use cosmwasm_std::{Addr, Event};
+
+use crate::state::{Config, Meeting, Resolution, ResolutionAction};
+
+/// Version of the keys of the events below.
+pub const SCHEMA_VERSION: &str = "1";
+
+/// Type of the event emitted for every meeting added to the calendar.
+pub const MEETING_BOOKED: &str = "calendar_meeting_booked";
+/// Type of the event emitted for every meeting whose stake is resolved.
+pub const STAKE_RESOLVED: &str = "calendar_stake_resolved";
+/// Type of the event emitted when the config is set on instantiation or updated.
+pub const CONFIG_UPDATED: &str = "calendar_config_updated";
+
+pub const KEY_SCHEMA_VERSION: &str = "schema_version";
+pub const KEY_DAY_DATETIME: &str = "day_datetime";
+pub const KEY_MEETING_INDEX: &str = "meeting_index";
+pub const KEY_START_TIME: &str = "start_time";
+pub const KEY_END_TIME: &str = "end_time";
+pub const KEY_REQUESTER: &str = "requester";
+pub const KEY_AMOUNT_STAKED: &str = "amount_staked";
+pub const KEY_DENOM: &str = "denom";
+pub const KEY_ACTION: &str = "action";
+/// Only set for partial slashes.
+pub const KEY_MINUTES_LATE: &str = "minutes_late";
+pub const KEY_RESOLVER: &str = "resolver";
+pub const KEY_AMOUNT_TO_REQUESTER: &str = "amount_to_requester";
+pub const KEY_AMOUNT_TO_ADMIN: &str = "amount_to_admin";
+pub const KEY_UPDATED_BY: &str = "updated_by";
+pub const KEY_CHANGED: &str = "changed";
+pub const KEY_PRICE_PER_MINUTE: &str = "price_per_minute";
+pub const KEY_UTC_OFFSET: &str = "utc_offset";
+
+/// A meeting added to the calendar at `meeting_index` of its day.
+pub struct MeetingBookedEvent<'a> {
+    pub day_datetime: i64,
+    pub meeting_index: u32,
+    pub meeting: &'a Meeting,
+    pub config: &'a Config,
+}
+
+impl From<MeetingBookedEvent<'_>> for Event {
+    fn from(event: MeetingBookedEvent) -> Self {
+        let MeetingBookedEvent {
+            day_datetime,
+            meeting_index,
+            meeting,
+            config,
+        } = event;
+        Event::new(MEETING_BOOKED).add_attributes([
+            (KEY_SCHEMA_VERSION, SCHEMA_VERSION.to_string()),
+            (KEY_DAY_DATETIME, day_datetime.to_string()),
+            (KEY_MEETING_INDEX, meeting_index.to_string()),
+            (KEY_START_TIME, meeting.start_time.to_string()),
+            (KEY_END_TIME, meeting.end_time.to_string()),
+            (KEY_REQUESTER, meeting.requester.to_string()),
+            (KEY_AMOUNT_STAKED, meeting.amount_staked.to_string()),
+            (KEY_DENOM, meeting.stake_denom(config).to_string()),
+        ])
+    }
+}
+
+/// The stake of the meeting that was at `meeting_index` of its day resolved as `resolution`.
+/// Both legs of the payout are listed even when one of them is zero.
+pub struct StakeResolvedEvent<'a> {
+    pub day_datetime: i64,
+    pub meeting_index: u32,
+    pub meeting: &'a Meeting,
+    pub resolution: &'a Resolution,
+    pub config: &'a Config,
+}
+
+impl From<StakeResolvedEvent<'_>> for Event {
+    fn from(event: StakeResolvedEvent) -> Self {
+        let StakeResolvedEvent {
+            day_datetime,
+            meeting_index,
+            meeting,
+            resolution,
+            config,
+        } = event;
+        let (action, minutes_late) = match resolution.action {
+            ResolutionAction::Return => ("return", None),
+            ResolutionAction::FullSlash => ("full_slash", None),
+            ResolutionAction::PartialSlash { minutes_late } => {
+                ("partial_slash", Some(minutes_late))
+            }
+            ResolutionAction::Free => ("free", None),
+        };
+        let mut attributes = vec![
+            (KEY_SCHEMA_VERSION, SCHEMA_VERSION.to_string()),
+            (KEY_DAY_DATETIME, day_datetime.to_string()),
+            (KEY_MEETING_INDEX, meeting_index.to_string()),
+            (KEY_REQUESTER, meeting.requester.to_string()),
+            (KEY_ACTION, action.to_string()),
+        ];
+        if let Some(minutes_late) = minutes_late {
+            attributes.push((KEY_MINUTES_LATE, minutes_late.to_string()));
+        }
+        attributes.extend([
+            (KEY_RESOLVER, resolution.resolver.to_string()),
+            (
+                KEY_AMOUNT_TO_REQUESTER,
+                resolution.amount_to_requester.to_string(),
+            ),
+            (KEY_AMOUNT_TO_ADMIN, resolution.amount_to_admin.to_string()),
+            (KEY_DENOM, meeting.stake_denom(config).to_string()),
+        ]);
+        Event::new(STAKE_RESOLVED).add_attributes(attributes)
+    }
+}
+
+/// The config after `updated_by` set the fields named in `changed`.
+pub struct ConfigUpdatedEvent<'a> {
+    pub updated_by: &'a Addr,
+    pub changed: Vec<&'a str>,
+    pub config: &'a Config,
+}
+
+impl From<ConfigUpdatedEvent<'_>> for Event {
+    fn from(event: ConfigUpdatedEvent) -> Self {
+        let ConfigUpdatedEvent {
+            updated_by,
+            changed,
+            config,
+        } = event;
+        Event::new(CONFIG_UPDATED).add_attributes([
+            (KEY_SCHEMA_VERSION, SCHEMA_VERSION.to_string()),
+            (KEY_UPDATED_BY, updated_by.to_string()),
+            (KEY_CHANGED, changed.join(",")),
+            (KEY_DENOM, config.denom.clone()),
+            (KEY_PRICE_PER_MINUTE, config.price_per_minute.to_string()),
+            (KEY_UTC_OFFSET, config.utc_offset.to_string()),
+            (KEY_START_TIME, config.start_time.to_string()),
+            (KEY_END_TIME, config.end_time.to_string()),
+        ])
+    }
+}
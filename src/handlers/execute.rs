@@ -1,24 +1,57 @@
 use abstract_core::objects::AssetEntry;
 use abstract_sdk::features::AbstractResponse;
-use chrono::{DateTime, FixedOffset, LocalResult, NaiveTime, TimeZone, Timelike};
+use chrono::FixedOffset;
 use cosmwasm_std::{
-    BankMsg, Coin, Deps, DepsMut, Env, Int64, MessageInfo, Response, StdError, Uint128,
+    to_binary, Addr, Api, BankMsg, Binary, Coin, ConversionOverflowError, Decimal, Deps, DepsMut,
+    Env, Event, Int64, MessageInfo, Order, OverflowError, OverflowOperation, QuerierWrapper,
+    Response, StdError, StdResult, Storage, Uint128,
 };
 use cw_asset::AssetInfoBase;
-use cw_utils::must_pay;
+use cw_storage_plus::{Bound, Map};
+use cw_utils::{nonpayable, PaymentError};
+use std::collections::{BTreeMap, BTreeSet};
 
-use crate::contract::{App, AppResult};
+use crate::contract::{
+    App, AppResult, HOLD_TTL_SECONDS, MAX_BATCH, MAX_CONTACT_URI_LENGTH, MAX_DESCRIPTION_LENGTH,
+    MAX_ENCRYPTED_PAYLOAD_BYTES, MAX_NAME_LENGTH,
+};
 
 use crate::error::AppError;
-use crate::msg::AppExecuteMsg;
-use crate::state::{Meeting, CALENDAR, CONFIG};
+use crate::events::{ConfigUpdatedEvent, MeetingBookedEvent, StakeResolvedEvent};
+use crate::msg::{
+    AppExecuteMsg, MeetingResponse, OraclePriceResponse, OracleQueryMsg, RequestMeetingResponse,
+    ResolutionResponse, Weekday,
+};
+use crate::state::{
+    Config, Hold, Meeting, QueuedRefund, Resolution, ResolutionAction, SlashCurve, StakeEvent,
+    StakeMovement, WaitlistEntry, AUDIT_LOG, AUDIT_LOG_SEQ, CALENDAR, CONFIG, CREDITS,
+    CREDITS_TOTAL, HOLDS, HOLDS_SEQ, OUTSTANDING, PAYOUTS_FROZEN, QUEUED_REFUNDS,
+    QUEUED_REFUNDS_SEQ, QUEUED_REFUNDS_TOTAL, RESOLVED_MEETINGS, RESOLVED_MEETINGS_SEQ,
+    STAKED_MEETINGS, WAITLIST, WAITLIST_SEQ, WAITLIST_TOTAL,
+};
+use crate::time::{
+    day_key, duration_in_minutes, format_local, get_date_time, parse_local, start_of_day_timestamp,
+    validate_utc_offset, validate_window,
+};
+use crate::validation::{
+    block_time, curve_slash_payout, find_conflict, meeting_index_at, meeting_position, must_pay_in,
+    payment_required, priced_end_time, split_amount, timestamp_seconds, validate_calendar_hours,
+    validate_day_key, validate_denom, validate_extension, validate_granularity,
+    validate_meeting_day, validate_meeting_times, validate_profile_field, validate_refund_splits,
+    validate_slash_curve, validate_slash_distribution, validate_timestamp, violates_self_gap,
+    MeetingDuration,
+};
 use abstract_sdk::features::AbstractNameService;
 use abstract_sdk::Resolve;
 
 enum StakeAction {
     Return,
     FullSlash,
-    PartialSlash { minutes_late: u32 },
+    PartialSlash {
+        minutes_late: u32,
+    },
+    /// Returns or fully slashes the stake depending on the attested attendance.
+    Settle,
 }
 
 pub fn execute_handler(
@@ -28,11 +61,77 @@ pub fn execute_handler(
     app: App,
     msg: AppExecuteMsg,
 ) -> AppResult {
+    // Funds attached to any other message would be stranded in the contract.
+    if !matches!(
+        msg,
+        AppExecuteMsg::RequestMeeting { .. }
+            | AppExecuteMsg::RequestMeetingAt { .. }
+            | AppExecuteMsg::RequestMeetingIso { .. }
+            | AppExecuteMsg::AmendMeeting { .. }
+            | AppExecuteMsg::ExtendMeeting { .. }
+            | AppExecuteMsg::Deposit {}
+            | AppExecuteMsg::ConfirmHold { .. }
+            | AppExecuteMsg::JoinWaitlist { .. }
+    ) {
+        nonpayable(&info)?;
+    }
+
     match msg {
         AppExecuteMsg::RequestMeeting {
             start_time,
             end_time,
-        } => request_meeting(deps, info, app, env, start_time, end_time),
+            refund_splits,
+            reminder_minutes,
+            max_total_stake,
+        } => request_meeting(
+            deps,
+            info,
+            app,
+            env,
+            start_time,
+            end_time,
+            refund_splits,
+            reminder_minutes,
+            max_total_stake,
+        ),
+        AppExecuteMsg::RequestMeetingAt {
+            start_time,
+            end_time,
+            refund_splits,
+            reminder_minutes,
+            max_total_stake,
+        } => request_meeting(
+            deps,
+            info,
+            app,
+            env,
+            timestamp_seconds(start_time)?,
+            timestamp_seconds(end_time)?,
+            refund_splits,
+            reminder_minutes,
+            max_total_stake,
+        ),
+        AppExecuteMsg::RequestMeetingIso {
+            start_time,
+            end_time,
+            refund_splits,
+            reminder_minutes,
+            max_total_stake,
+        } => {
+            let start_time = parse_calendar_time(deps.storage, &start_time)?;
+            let end_time = parse_calendar_time(deps.storage, &end_time)?;
+            request_meeting(
+                deps,
+                info,
+                app,
+                env,
+                start_time,
+                end_time,
+                refund_splits,
+                reminder_minutes,
+                max_total_stake,
+            )
+        }
         AppExecuteMsg::SlashFullStake {
             day_datetime,
             meeting_index,
@@ -70,13 +169,284 @@ pub fn execute_handler(
             meeting_index,
             StakeAction::Return,
         ),
+        AppExecuteMsg::SlashFullStakeAt {
+            day_datetime,
+            meeting_index,
+        } => handle_stake(
+            deps,
+            info,
+            app,
+            env,
+            timestamp_seconds(day_datetime)?,
+            meeting_index,
+            StakeAction::FullSlash,
+        ),
+        AppExecuteMsg::SlashPartialStakeAt {
+            day_datetime,
+            meeting_index,
+            minutes_late,
+        } => handle_stake(
+            deps,
+            info,
+            app,
+            env,
+            timestamp_seconds(day_datetime)?,
+            meeting_index,
+            StakeAction::PartialSlash { minutes_late },
+        ),
+        AppExecuteMsg::ReturnStakeAt {
+            day_datetime,
+            meeting_index,
+        } => handle_stake(
+            deps,
+            info,
+            app,
+            env,
+            timestamp_seconds(day_datetime)?,
+            meeting_index,
+            StakeAction::Return,
+        ),
+        AppExecuteMsg::SlashFullStakeIso {
+            day_datetime,
+            meeting_index,
+        } => {
+            let day_datetime = parse_calendar_time(deps.storage, &day_datetime)?;
+            handle_stake(
+                deps,
+                info,
+                app,
+                env,
+                day_datetime,
+                meeting_index,
+                StakeAction::FullSlash,
+            )
+        }
+        AppExecuteMsg::SlashPartialStakeIso {
+            day_datetime,
+            meeting_index,
+            minutes_late,
+        } => {
+            let day_datetime = parse_calendar_time(deps.storage, &day_datetime)?;
+            handle_stake(
+                deps,
+                info,
+                app,
+                env,
+                day_datetime,
+                meeting_index,
+                StakeAction::PartialSlash { minutes_late },
+            )
+        }
+        AppExecuteMsg::ReturnStakeIso {
+            day_datetime,
+            meeting_index,
+        } => {
+            let day_datetime = parse_calendar_time(deps.storage, &day_datetime)?;
+            handle_stake(
+                deps,
+                info,
+                app,
+                env,
+                day_datetime,
+                meeting_index,
+                StakeAction::Return,
+            )
+        }
+        AppExecuteMsg::ReturnStakeByKey {
+            day_datetime,
+            requester,
+            start_time,
+        } => {
+            let requester = deps.api.addr_validate(&requester)?;
+            let meeting_index =
+                meeting_index_by_key(deps.storage, deps.api, day_datetime, &requester, start_time)?;
+            handle_stake(
+                deps,
+                info,
+                app,
+                env,
+                day_datetime,
+                meeting_index,
+                StakeAction::Return,
+            )
+        }
         AppExecuteMsg::UpdateConfig {
             price_per_minute,
             denom,
-        } => update_config(deps, info, app, price_per_minute, denom),
+            self_gap_minutes,
+            accepted_denoms,
+            denom_decimals,
+            allowed_weekdays,
+            booking_fee,
+            return_claim_deadline,
+            attestor,
+            allow_partial_outside,
+            price_outside_hours,
+            price_source,
+            check_outstanding_balance,
+            time_granularity_seconds,
+            allow_admin_booking,
+            overbooking_allowed,
+            pegged_price_per_minute,
+            slash_curve,
+            cancel_cutoff_secs,
+            name,
+            description,
+            contact_uri,
+            slash_distribution,
+            force,
+        } => update_config(
+            deps,
+            info,
+            app,
+            env,
+            price_per_minute,
+            denom,
+            self_gap_minutes,
+            accepted_denoms,
+            denom_decimals,
+            allowed_weekdays,
+            booking_fee,
+            return_claim_deadline,
+            attestor,
+            allow_partial_outside,
+            price_outside_hours,
+            price_source,
+            check_outstanding_balance,
+            time_granularity_seconds,
+            allow_admin_booking,
+            overbooking_allowed,
+            pegged_price_per_minute,
+            slash_curve,
+            cancel_cutoff_secs,
+            name,
+            description,
+            contact_uri,
+            slash_distribution,
+            force,
+        ),
+        AppExecuteMsg::ReturnAllForRequester {
+            requester,
+            start_day,
+            end_day,
+        } => return_all_for_requester(deps, info, app, env, requester, start_day, end_day),
+        AppExecuteMsg::Deposit {} => deposit(deps, info, app),
+        AppExecuteMsg::Withdraw { amount } => withdraw(deps, info, app, amount),
+        AppExecuteMsg::ReturnStakes { meetings } => return_stakes(deps, info, app, env, meetings),
+        AppExecuteMsg::ImportState {
+            config,
+            days,
+            force,
+        } => import_state(deps, info, app, config, days, force),
+        AppExecuteMsg::Import { days, overwrite } => {
+            import_state(deps, info, app, None, days, overwrite)
+        }
+        AppExecuteMsg::AmendMeeting {
+            day_datetime,
+            meeting_index,
+            new_start,
+            new_end,
+        } => amend_meeting(
+            deps,
+            info,
+            app,
+            env,
+            day_datetime,
+            meeting_index,
+            new_start,
+            new_end,
+        ),
+        AppExecuteMsg::ExtendMeeting {
+            day_datetime,
+            meeting_index,
+            new_end_time,
+        } => extend_meeting(
+            deps,
+            info,
+            app,
+            env,
+            day_datetime,
+            meeting_index,
+            new_end_time,
+        ),
+        AppExecuteMsg::ClaimRefund { refund_id } => claim_refund(deps, info, app, env, refund_id),
+        AppExecuteMsg::SweepUnclaimed { refund_id } => {
+            sweep_unclaimed(deps, info, app, env, refund_id)
+        }
+        AppExecuteMsg::Attest {
+            day_datetime,
+            meeting_index,
+            attended,
+        } => attest(deps, info, app, day_datetime, meeting_index, attended),
+        AppExecuteMsg::SettleExpired {
+            day_datetime,
+            meeting_index,
+        } => handle_stake(
+            deps,
+            info,
+            app,
+            env,
+            day_datetime,
+            meeting_index,
+            StakeAction::Settle,
+        ),
+        AppExecuteMsg::HoldSlot {
+            start_time,
+            end_time,
+        } => hold_slot(deps, info, app, env, start_time, end_time),
+        AppExecuteMsg::ConfirmHold { hold_id } => confirm_hold(deps, info, app, env, hold_id),
+        AppExecuteMsg::DisputeAttendance {
+            day_datetime,
+            meeting_index,
+        } => dispute_attendance(deps, info, app, day_datetime, meeting_index),
+        AppExecuteMsg::CheckIn {
+            day_datetime,
+            meeting_index,
+        } => check_in(deps, info, app, env, day_datetime, meeting_index),
+        AppExecuteMsg::SetEncryptedPayload {
+            day_datetime,
+            meeting_index,
+            payload,
+        } => set_encrypted_payload(deps, info, app, day_datetime, meeting_index, payload),
+        AppExecuteMsg::EndMeeting {
+            day_datetime,
+            meeting_index,
+        } => end_meeting(deps, info, app, env, day_datetime, meeting_index),
+        AppExecuteMsg::JoinWaitlist {
+            start_time,
+            end_time,
+        } => join_waitlist(deps, info, app, env, start_time, end_time),
+        AppExecuteMsg::LeaveWaitlist {
+            day_datetime,
+            entry_id,
+        } => leave_waitlist(deps, info, app, env, day_datetime, entry_id),
+        AppExecuteMsg::CancelMeeting {
+            day_datetime,
+            meeting_index,
+        } => cancel_meeting(deps, info, app, env, day_datetime, meeting_index),
+        AppExecuteMsg::TransferAllMeetings { to, start_after } => {
+            transfer_all_meetings(deps, info, app, env, to, start_after)
+        }
+        AppExecuteMsg::BlockSlot {
+            start_time,
+            end_time,
+        } => block_slot(deps, info, app, env, start_time, end_time),
+        AppExecuteMsg::SetPayoutFreeze { frozen } => set_payout_freeze(deps, info, app, frozen),
+        AppExecuteMsg::RecoverFunds {
+            denom,
+            amount,
+            recipient,
+        } => recover_funds(deps, info, app, env, denom, amount, recipient),
     }
 }
 
+/// Parses an ISO 8601 time from a message in the calendar's UTC offset.
+fn parse_calendar_time(storage: &dyn Storage, input: &str) -> AppResult<Int64> {
+    let config = CONFIG.load(storage)?;
+    parse_local(validate_utc_offset(config.utc_offset)?, input)
+}
+
+#[allow(clippy::too_many_arguments)]
 fn request_meeting(
     deps: DepsMut,
     info: MessageInfo,
@@ -84,102 +454,291 @@ fn request_meeting(
     env: Env,
     meeting_start_time: Int64,
     meeting_end_time: Int64,
+    refund_splits: Option<Vec<(String, u16)>>,
+    reminder_minutes: Option<u32>,
+    max_total_stake: Option<Uint128>,
 ) -> AppResult {
     let config = CONFIG.load(deps.storage)?;
-    let amount_sent = must_pay(&info, &config.denom)?;
-
-    let timezone: FixedOffset = FixedOffset::east_opt(config.utc_offset).unwrap();
-    let meeting_start_datetime = get_date_time(timezone, meeting_start_time)?;
-    let meeting_start_time: NaiveTime = meeting_start_datetime.time();
+    let (denom, amount_sent) = match info.funds.as_slice() {
+        [] => (config.denom.clone(), Uint128::zero()),
+        [coin] => (coin.denom.clone(), coin.amount),
+        funds => {
+            return Err(payment_required(
+                funds,
+                &config.denom,
+                PaymentError::MultipleDenoms {},
+            ))
+        }
+    };
+    let ValidatedBooking {
+        timezone,
+        start_time: meeting_start_timestamp,
+        end_time: meeting_end_timestamp,
+        day_datetime: start_of_day_timestamp,
+        meetings: mut existing_meetings,
+        stake,
+        booking_fee,
+        expected_amount,
+        admin,
+        self_booked,
+    } = validate_booking(
+        deps.as_ref(),
+        &app,
+        &env,
+        &config,
+        &info.sender,
+        &denom,
+        meeting_start_time,
+        meeting_end_time,
+        max_total_stake,
+    )?;
+    prune_expired_holds(deps.storage, &env)?;
 
-    let meeting_end_datetime = get_date_time(timezone, meeting_end_time)?;
-    let meeting_end_time: NaiveTime = meeting_end_datetime.time();
+    let refund_splits = refund_splits
+        .map(|refund_splits| validate_refund_splits(deps.api, refund_splits))
+        .transpose()?;
 
-    // Check that date falls between the given range.
-    let calendar_start_time: NaiveTime = config.start_time.into();
-    let calendar_end_time: NaiveTime = config.end_time.into();
+    if expected_amount.is_zero() && !info.funds.is_empty() {
+        return Err(AppError::PaymentNotRequired {});
+    }
+    let mut credit_used = Uint128::zero();
+    if amount_sent != expected_amount {
+        // Credit is only held in the config denom.
+        if denom != config.denom {
+            return Err(AppError::InvalidStakeAmountSent {
+                expected_amount,
+                amount_sent,
+            });
+        }
+        // Draw the shortfall from the requester's deposited credit.
+        let credit = CREDITS
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default();
+        if amount_sent > expected_amount || credit < expected_amount - amount_sent {
+            return Err(AppError::InvalidStakeAmountSent {
+                expected_amount,
+                amount_sent,
+            });
+        }
+        credit_used = expected_amount - amount_sent;
+        CREDITS.save(deps.storage, &info.sender, &(credit - credit_used))?;
+        let credits_total = CREDITS_TOTAL.may_load(deps.storage)?.unwrap_or_default();
+        let credits_total = credits_total
+            .checked_sub(credit_used)
+            .map_err(StdError::from)?;
+        CREDITS_TOTAL.save(deps.storage, &credits_total)?;
+    }
 
-    let meeting_start_timestamp = meeting_start_datetime.timestamp();
-    let meeting_end_timestamp = meeting_end_datetime.timestamp();
+    existing_meetings.push(Meeting {
+        start_time: meeting_start_timestamp,
+        end_time: meeting_end_timestamp,
+        requester: info.sender,
+        amount_staked: stake,
+        refund_splits,
+        denom: Some(denom.clone()),
+        reminder_minutes,
+        free: stake.is_zero(),
+        attended: None,
+        disputed: false,
+        checked_in: false,
+        ended_early_at: None,
+        self_booked,
+        resolution: None,
+        cancel_deadline: Some(config.cancel_deadline(meeting_start_timestamp)),
+        encrypted_payload: None,
+    });
+    let meeting_index = meeting_index_at(existing_meetings.len() - 1)?;
+    let booked_meeting = &existing_meetings[existing_meetings.len() - 1];
+    let booked = Event::from(MeetingBookedEvent {
+        day_datetime: start_of_day_timestamp,
+        meeting_index,
+        meeting: booked_meeting,
+        config: &config,
+    });
+    let data = to_binary(&RequestMeetingResponse {
+        day_datetime: start_of_day_timestamp,
+        meeting_index,
+        amount_staked: stake,
+        meeting: MeetingResponse::try_from((booked_meeting, &config, &env))?,
+    })?;
 
-    if meeting_start_datetime.date_naive() != meeting_end_datetime.date_naive() {
-        return Err(AppError::StartAndEndTimeNotOnSameDay {});
-    }
+    save_day(
+        deps.storage,
+        deps.api,
+        &config,
+        start_of_day_timestamp,
+        &existing_meetings[..existing_meetings.len() - 1],
+        &existing_meetings,
+    )?;
 
-    if meeting_start_time.second() != 0 || meeting_start_time.nanosecond() != 0 {
-        return Err(AppError::StartTimeNotRoundedToNearestMinute {});
-    }
+    let fee_messages = match admin {
+        Some(admin) if !booking_fee.is_zero() => vec![BankMsg::Send {
+            to_address: admin.to_string(),
+            amount: vec![Coin::new(booking_fee.into(), denom)],
+        }],
+        _ => vec![],
+    };
 
-    if meeting_end_time.second() != 0 || meeting_end_time.nanosecond() != 0 {
-        return Err(AppError::EndTimeNotRoundedToNearestMinute {});
-    }
+    Ok(app.tag_response(
+        Response::default()
+            .add_messages(fee_messages)
+            .add_event(booked)
+            .add_attribute("meeting_start_time", meeting_start_timestamp.to_string())
+            .add_attribute("meeting_end_time", meeting_end_timestamp.to_string())
+            .add_attribute(
+                "meeting_start_local",
+                format_local(timezone, meeting_start_timestamp)?,
+            )
+            .add_attribute(
+                "meeting_end_local",
+                format_local(timezone, meeting_end_timestamp)?,
+            )
+            .add_attribute("credit_used", credit_used.to_string())
+            .add_attribute("booking_fee", booking_fee.to_string())
+            .add_attribute("day_datetime", start_of_day_timestamp.to_string())
+            .set_data(data),
+        "request_meeting",
+    ))
+}
 
-    // Not 100% sure about this typecasting but the same is done in the cosmwasm doc example using
-    // chrono so it should be fine.
-    if (env.block.time.seconds() as i64) > meeting_start_timestamp {
-        return Err(AppError::StartTimeMustBeInFuture {});
-    }
+/// A booking that passed every check of `request_meeting` but the payment.
+pub struct ValidatedBooking {
+    pub timezone: FixedOffset,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub day_datetime: i64,
+    /// The meetings already booked on the day of the booking.
+    pub meetings: Vec<Meeting>,
+    pub stake: Uint128,
+    pub booking_fee: Uint128,
+    /// The stake plus the booking fee.
+    pub expected_amount: Uint128,
+    pub admin: Option<Addr>,
+    pub self_booked: bool,
+}
 
-    if meeting_start_time >= meeting_end_time {
-        return Err(AppError::EndTimeMustBeAfterStartTime {});
-    }
+/// Runs the checks of `request_meeting` for `requester` booking between `meeting_start_time`
+/// and `meeting_end_time` in `denom`, in the order the execute handler runs them, without
+/// writing to storage. Shared with the queries that dry-run a booking so that they cannot
+/// drift from it.
+#[allow(clippy::too_many_arguments)]
+pub fn validate_booking(
+    deps: Deps,
+    app: &App,
+    env: &Env,
+    config: &Config,
+    requester: &Addr,
+    denom: &str,
+    meeting_start_time: Int64,
+    meeting_end_time: Int64,
+    max_total_stake: Option<Uint128>,
+) -> AppResult<ValidatedBooking> {
+    let price_per_minute =
+        config
+            .price_per_minute_in(denom)
+            .ok_or_else(|| AppError::DenomNotAccepted {
+                denom: denom.to_string(),
+            })?;
 
-    if meeting_start_time < calendar_start_time || meeting_start_time > calendar_end_time {
-        return Err(AppError::StartTimeDoesNotFallWithinCalendarBounds {});
-    }
+    let timezone: FixedOffset = validate_utc_offset(config.utc_offset)?;
+    validate_timestamp(meeting_start_time)?;
+    validate_timestamp(meeting_end_time)?;
+    let meeting_start_datetime = get_date_time(timezone, meeting_start_time).map_err(|error| {
+        AppError::InvalidMeetingStart {
+            reason: Box::new(error),
+        }
+    })?;
+    let meeting_end_datetime =
+        get_date_time(timezone, meeting_end_time).map_err(|error| AppError::InvalidMeetingEnd {
+            reason: Box::new(error),
+        })?;
 
-    if meeting_end_time < calendar_start_time || meeting_end_time > calendar_end_time {
-        return Err(AppError::EndTimeDoesNotFallWithinCalendarBounds {});
-    }
+    validate_meeting_times(
+        config,
+        block_time(env)?,
+        &meeting_start_datetime,
+        &meeting_end_datetime,
+    )?;
 
-    // This number will be positive enforced by previous checks.
-    let duration_in_minutes: Uint128 =
-        Uint128::new((meeting_end_time - meeting_start_time).num_minutes() as u128);
+    let meeting_start_timestamp = meeting_start_datetime.timestamp();
+    let meeting_end_timestamp = meeting_end_datetime.timestamp();
 
-    let expected_amount = duration_in_minutes * config.price_per_minute;
-    if amount_sent != expected_amount {
-        return Err(AppError::InvalidStakeAmountSent { expected_amount });
+    let stake = required_stake(
+        &deps.querier,
+        config,
+        denom,
+        price_per_minute,
+        meeting_start_timestamp,
+        priced_end_time(config, meeting_start_timestamp, meeting_end_timestamp)?,
+    )?;
+    // The booking fee is paid out to the admin right away, so it is waived once the admin is
+    // renounced.
+    let admin = app.admin.get(deps)?;
+    let self_booked = is_self_booking(config, admin.as_ref(), requester)?;
+    let booking_fee = match admin {
+        Some(_) => config.booking_fee,
+        None => Uint128::zero(),
+    };
+    let expected_amount = checked_add(stake, booking_fee)?;
+    if let Some(max_total_stake) = max_total_stake {
+        if expected_amount > max_total_stake {
+            return Err(AppError::StakeAboveMax {
+                expected_amount,
+                max_total_stake,
+            });
+        }
     }
 
     // Get unix start date of the current day
-    let start_of_day_timestamp: i64 = meeting_start_datetime
-        .date_naive()
-        .and_time(NaiveTime::default())
-        .timestamp();
+    let start_of_day_timestamp: i64 = start_of_day_timestamp(&meeting_start_datetime);
 
-    let mut existing_meetings: Vec<Meeting> = CALENDAR
-        .may_load(deps.storage, start_of_day_timestamp)?
+    let existing_meetings: Vec<Meeting> = CALENDAR
+        .may_load(deps.storage, deps.api, start_of_day_timestamp)?
         .unwrap_or_default();
 
-    if !existing_meetings.is_empty() {
-        //Validate that there are no colisions.
-        for meeting in existing_meetings.iter() {
-            let start_time_conflicts = meeting.start_time <= meeting_start_timestamp
-                && meeting_start_timestamp < meeting.end_time;
-
-            let end_time_conflicts = meeting.start_time < meeting_end_timestamp
-                && meeting_end_timestamp <= meeting.end_time;
-
-            if start_time_conflicts || end_time_conflicts {
-                return Err(AppError::MeetingConflictExists {});
-            }
+    //Validate that there are no colisions.
+    let conflicting_meeting = find_conflict(
+        &existing_meetings,
+        meeting_start_timestamp,
+        meeting_end_timestamp,
+    );
+    match conflicting_meeting {
+        Some(conflicting_meeting) if !config.overbooking_allowed => {
+            return Err(conflict_error(config, conflicting_meeting));
         }
+        _ => {}
     }
-    existing_meetings.push(Meeting {
-        start_time: meeting_start_timestamp,
-        end_time: meeting_end_timestamp,
-        requester: info.sender,
-        amount_staked: amount_sent,
-    });
+    check_not_held(
+        deps.storage,
+        env,
+        meeting_start_timestamp,
+        meeting_end_timestamp,
+    )?;
 
-    CALENDAR.save(deps.storage, start_of_day_timestamp, &existing_meetings)?;
+    if violates_self_gap(
+        &existing_meetings,
+        requester,
+        meeting_start_timestamp,
+        meeting_end_timestamp,
+        config.self_gap_minutes,
+    ) {
+        return Err(AppError::SelfGapViolated {
+            self_gap_minutes: config.self_gap_minutes,
+        });
+    }
 
-    Ok(app.tag_response(
-        Response::default()
-            .add_attribute("meeting_start_time", meeting_start_timestamp.to_string())
-            .add_attribute("meeting_end_time", meeting_end_timestamp.to_string()),
-        "request_meeting",
-    ))
+    Ok(ValidatedBooking {
+        timezone,
+        start_time: meeting_start_timestamp,
+        end_time: meeting_end_timestamp,
+        day_datetime: start_of_day_timestamp,
+        meetings: existing_meetings,
+        stake,
+        booking_fee,
+        expected_amount,
+        admin,
+        self_booked,
+    })
 }
 
 fn handle_stake(
@@ -191,117 +750,2623 @@ fn handle_stake(
     meeting_index: u32,
     stake_action: StakeAction,
 ) -> AppResult {
-    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
-
+    assert_payouts_open(deps.storage)?;
     let config = CONFIG.load(deps.storage)?;
 
-    let meetings = CALENDAR.may_load(deps.storage, day_datetime.i64())?;
-    if meetings.is_none() {
-        return Err(AppError::NoMeetingsAtGivenDayDateTime {});
+    // Only the admin or attestor can resolve stakes other than by settling. Once the admin is
+    // renounced only the attestor can, and stakes are then always returned so that they are
+    // not stranded in the contract. The admin loaded here is compared directly rather than
+    // loaded again through `assert_attestor`.
+    let admin = app.admin.get(deps.as_ref())?;
+    if !matches!(stake_action, StakeAction::Settle)
+        && config.attestor.as_ref() != Some(&info.sender)
+    {
+        match &admin {
+            Some(admin) if *admin == info.sender => {}
+            Some(_) => return Err(AppError::NotAttestor {}),
+            None => return Err(AppError::NoAdminSet {}),
+        }
     }
-    let mut meetings = meetings.unwrap();
-    if meeting_index as usize >= meetings.len() {
-        return Err(AppError::MeetingDoesNotExist {});
+
+    let timezone = validate_utc_offset(config.utc_offset)?;
+    let (mut meetings, position) =
+        load_day_meeting(deps.storage, deps.api, &config, day_datetime, meeting_index)?;
+    let meeting: &Meeting = &meetings[position];
+
+    let now = block_time(&env)?;
+    if !meeting.has_ended(now) {
+        return Err(AppError::MeetingNotFinishedYet {
+            day_datetime: day_datetime.i64(),
+            meeting_index,
+        });
     }
-    let meeting: &mut Meeting = meetings.get_mut(meeting_index as usize).unwrap();
 
-    if (env.block.time.seconds() as i64) <= meeting.end_time {
-        return Err(AppError::MeetingNotFinishedYet {});
+    if meeting.resolution.is_some() {
+        return Err(AppError::StakeAlreadyHandled {
+            day_datetime: day_datetime.i64(),
+            meeting_index,
+        });
     }
 
-    let amount_staked = meeting.amount_staked;
-    let requester = meeting.requester.to_string();
-    if amount_staked.is_zero() {
-        return Err(AppError::StakeAlreadyHandled {});
+    if meeting.free {
+        let mut meeting = meetings.remove(position);
+        save_day_without(
+            deps.storage,
+            deps.api,
+            &config,
+            day_datetime.i64(),
+            &meetings,
+            &meeting,
+        )?;
+        let resolution = Resolution {
+            action: ResolutionAction::Free,
+            resolver: info.sender,
+            resolved_at: now,
+            amount_to_requester: Uint128::zero(),
+            amount_to_admin: Uint128::zero(),
+        };
+        let resolved = Event::from(StakeResolvedEvent {
+            day_datetime: day_datetime.i64(),
+            meeting_index,
+            meeting: &meeting,
+            resolution: &resolution,
+            config: &config,
+        });
+        meeting.resolution = Some(resolution);
+        archive_resolved(deps.storage, day_datetime.i64(), &meeting)?;
+        return Ok(app.tag_response(
+            Response::default()
+                .add_event(resolved)
+                .set_data(to_binary(&ResolutionResponse {
+                    day_datetime: day_datetime.i64(),
+                    meeting_index,
+                    action: ResolutionAction::Free,
+                    amount_to_requester: Uint128::zero(),
+                    amount_to_admin: Uint128::zero(),
+                    meeting: MeetingResponse::try_from((&meeting, &config, &env))?,
+                })?)
+                .add_attribute("stake", "none")
+                .add_attribute("meetings_left", meetings.len().to_string()),
+            "nothing_to_resolve",
+        ));
     }
+    let amount_staked = meeting.amount_staked;
+    let action = match stake_action {
+        StakeAction::Settle if meeting.disputed => {
+            return Err(AppError::MeetingDisputed {
+                day_datetime: day_datetime.i64(),
+                meeting_index,
+            })
+        }
+        // An attestation decides the settlement. Without one, a requester who checked in
+        // attended even if the meeting was ended early, while a meeting ended early without a
+        // check-in was not attended.
+        StakeAction::Settle => match (meeting.attended, meeting.checked_in, meeting.ended_early_at)
+        {
+            (Some(true), _, _) => ResolutionAction::Return,
+            (Some(false), _, _) => ResolutionAction::FullSlash,
+            (None, true, _) => ResolutionAction::Return,
+            (None, false, Some(_)) => ResolutionAction::FullSlash,
+            (None, false, None) => {
+                return Err(AppError::MeetingNotAttested {
+                    day_datetime: day_datetime.i64(),
+                    meeting_index,
+                })
+            }
+        },
+        StakeAction::Return => ResolutionAction::Return,
+        StakeAction::FullSlash => ResolutionAction::FullSlash,
+        StakeAction::PartialSlash { minutes_late } => {
+            ResolutionAction::PartialSlash { minutes_late }
+        }
+    };
 
-    meeting.amount_staked = Uint128::zero();
+    // Resolved meetings are removed from their day, which shifts the index of every later
+    // meeting of that day down by one.
+    let mut meeting = meetings.remove(position);
+    save_day_without(
+        deps.storage,
+        deps.api,
+        &config,
+        day_datetime.i64(),
+        &meetings,
+        &meeting,
+    )?;
+    let denom = meeting.stake_denom(&config).to_string();
 
-    let response = match stake_action {
-        StakeAction::Return => app.tag_response(
-            Response::default().add_message(BankMsg::Send {
-                to_address: requester,
-                amount: vec![Coin::new(amount_staked.into(), config.denom)],
-            }),
-            "return_stake",
-        ),
-        StakeAction::FullSlash => app.tag_response(
-            Response::default().add_message(BankMsg::Send {
-                to_address: app.admin.get(deps.as_ref())?.unwrap().to_string(),
-                amount: vec![Coin::new(amount_staked.into(), config.denom)],
-            }),
-            "full_slash",
-        ),
-        StakeAction::PartialSlash { minutes_late } => {
-            // Cast should be safe given we cannot have a meeting longer than 24 hours.
-            let meeting_duration_in_minutes: u32 =
-                ((meeting.end_time - meeting.start_time) / 60) as u32;
-            if minutes_late > meeting_duration_in_minutes {
-                return Err(AppError::MinutesLateCannotExceedDurationOfMeeting {});
-            }
-            let amount_to_slash =
-                amount_staked.multiply_ratio(minutes_late, meeting_duration_in_minutes as u128);
-
-            app.tag_response(
-                Response::default()
-                    .add_message(BankMsg::Send {
-                        to_address: requester,
-                        amount: vec![Coin::new(
-                            (amount_staked - amount_to_slash).into(),
-                            config.denom.clone(),
-                        )],
-                    })
-                    .add_message(BankMsg::Send {
-                        to_address: app.admin.get(deps.as_ref())?.unwrap().to_string(),
-                        amount: vec![Coin::new(amount_to_slash.into(), config.denom)],
-                    }),
-                "partial_slash",
-            )
+    let (amount_to_return, amount_to_slash, topic) = match action {
+        ResolutionAction::Return => (amount_staked, Uint128::zero(), "return_stake"),
+        ResolutionAction::FullSlash => (Uint128::zero(), amount_staked, "slash_full_stake"),
+        ResolutionAction::PartialSlash { minutes_late } => {
+            let meeting_duration = MeetingDuration::of(&meeting)?;
+            let (amount_to_return, amount_to_slash) = curve_slash_payout(
+                &config.slash_curve,
+                amount_staked,
+                minutes_late,
+                meeting_duration.minutes(),
+            )?;
+            (amount_to_return, amount_to_slash, "slash_partial_stake")
         }
+        // Free meetings were resolved above, so this only returns whatever was staked.
+        ResolutionAction::Free => (amount_staked, Uint128::zero(), "return_stake"),
+    };
+
+    // Without an admin there is nowhere to send slashed stake.
+    let (amount_to_return, amount_to_slash, action) = match admin {
+        Some(_) => (amount_to_return, amount_to_slash, action),
+        None => (amount_staked, Uint128::zero(), ResolutionAction::Return),
+    };
+    // A bank send the contract cannot cover would fail the whole transaction without saying why.
+    // The stake was already taken out of the outstanding total when the meeting was removed.
+    let needed = if config.check_outstanding_balance {
+        checked_add(
+            OUTSTANDING
+                .may_load(deps.storage, &denom)?
+                .unwrap_or_default(),
+            amount_staked,
+        )?
+    } else {
+        amount_staked
+    };
+    let available = deps
+        .querier
+        .query_balance(&env.contract.address, &denom)?
+        .amount;
+    if available < needed {
+        return Err(AppError::InsufficientContractBalance { needed, available });
+    }
+    let returned = refund_messages(&meeting, amount_to_return, &denom);
+    let slashed = match admin {
+        Some(admin) => slash_messages(&config, &admin, amount_to_slash, &denom),
+        None => vec![],
     };
 
-    CALENDAR.save(deps.storage, day_datetime.i64(), &meetings)?;
+    log_stake_movements(
+        deps.storage,
+        &env,
+        &info.sender,
+        StakeMovement::Return,
+        &returned,
+    )?;
+    log_stake_movements(
+        deps.storage,
+        &env,
+        &info.sender,
+        StakeMovement::Slash,
+        &slashed,
+    )?;
+    let returned = queue_refunds(deps.storage, &env, &config, returned)?;
+    let resolution = Resolution {
+        action: action.clone(),
+        resolver: info.sender,
+        resolved_at: now,
+        amount_to_requester: amount_to_return,
+        amount_to_admin: amount_to_slash,
+    };
+    let resolved = Event::from(StakeResolvedEvent {
+        day_datetime: day_datetime.i64(),
+        meeting_index,
+        meeting: &meeting,
+        resolution: &resolution,
+        config: &config,
+    });
+    meeting.resolution = Some(resolution);
+    archive_resolved(deps.storage, day_datetime.i64(), &meeting)?;
+    let data = to_binary(&ResolutionResponse {
+        day_datetime: day_datetime.i64(),
+        meeting_index,
+        action,
+        amount_to_requester: amount_to_return,
+        amount_to_admin: amount_to_slash,
+        meeting: MeetingResponse::try_from((&meeting, &config, &env))?,
+    })?;
 
-    Ok(response)
+    Ok(app.custom_tag_response(
+        Response::default()
+            .add_messages(returned)
+            .add_messages(slashed)
+            .add_event(resolved)
+            .set_data(data),
+        topic,
+        vec![
+            ("amount_returned", amount_to_return.to_string()),
+            ("amount_slashed", amount_to_slash.to_string()),
+            ("meetings_left", meetings.len().to_string()),
+            ("day_datetime", day_datetime.to_string()),
+            (
+                "meeting_start_local",
+                format_local(timezone, meeting.start_time)?,
+            ),
+            (
+                "meeting_end_local",
+                format_local(timezone, meeting.end_time)?,
+            ),
+        ],
+    ))
 }
 
-fn update_config(
+fn return_all_for_requester(
     deps: DepsMut,
     info: MessageInfo,
     app: App,
-    price_per_minute: Option<Uint128>,
-    denom: Option<AssetEntry>,
+    env: Env,
+    requester: String,
+    start_day: Int64,
+    end_day: Int64,
 ) -> AppResult {
     app.admin.assert_admin(deps.as_ref(), &info.sender)?;
-    let mut config = CONFIG.load(deps.storage)?;
-    let mut attrs = vec![];
-    if let Some(price_per_minute) = price_per_minute {
-        config.price_per_minute = price_per_minute;
-        attrs.push(("price_per_minute", price_per_minute.to_string()));
-    }
-    if let Some(unresolved) = denom {
-        let denom = resolve_native_ans_denom(deps.as_ref(), &app, unresolved.clone())?;
-        config.denom = denom;
-        attrs.push(("denom", unresolved.to_string()));
+    assert_payouts_open(deps.storage)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let requester = deps.api.addr_validate(&requester)?;
+
+    let mut meetings_returned: usize = 0;
+    let mut amount_returned = Uint128::zero();
+    let mut refunds: Vec<BankMsg> = vec![];
+    let mut events: Vec<Event> = vec![];
+    let mut min = Bound::inclusive(start_day.i64());
+    while meetings_returned < MAX_BATCH {
+        // Days are read one at a time, since returning their stakes rewrites them, and no
+        // further than the day the batch fills up on.
+        let next_day = CALENDAR
+            .range(
+                deps.storage,
+                deps.api,
+                Some(min),
+                Some(Bound::inclusive(end_day.i64())),
+                Order::Ascending,
+            )
+            .next()
+            .transpose()?;
+        let Some((day_datetime, meetings)) = next_day else {
+            break;
+        };
+        min = Bound::exclusive(day_datetime);
+        // Returned meetings are removed from their day.
+        let mut kept = Vec::with_capacity(meetings.len());
+        for (position, meeting) in meetings.iter().cloned().enumerate() {
+            if meetings_returned >= MAX_BATCH
+                || meeting.requester != requester
+                || meeting.amount_staked.is_zero()
+            {
+                kept.push(meeting);
+                continue;
+            }
+            amount_returned = checked_add(amount_returned, meeting.amount_staked)?;
+            refunds.extend(refund_messages(
+                &meeting,
+                meeting.amount_staked,
+                meeting.stake_denom(&config),
+            ));
+            let resolution = Resolution {
+                action: ResolutionAction::Return,
+                resolver: info.sender.clone(),
+                resolved_at: block_time(&env)?,
+                amount_to_requester: meeting.amount_staked,
+                amount_to_admin: Uint128::zero(),
+            };
+            events.push(
+                StakeResolvedEvent {
+                    day_datetime,
+                    meeting_index: meeting_index_at(position)?,
+                    meeting: &meeting,
+                    resolution: &resolution,
+                    config: &config,
+                }
+                .into(),
+            );
+            archive_resolved(
+                deps.storage,
+                day_datetime,
+                &Meeting {
+                    resolution: Some(resolution),
+                    ..meeting
+                },
+            )?;
+            meetings_returned += 1;
+        }
+        if kept.len() < meetings.len() {
+            save_day(
+                deps.storage,
+                deps.api,
+                &config,
+                day_datetime,
+                &meetings,
+                &kept,
+            )?;
+        }
     }
-    CONFIG.save(deps.storage, &config)?;
-    Ok(app.custom_tag_response(Response::new(), "update_config", attrs))
-}
 
-pub fn resolve_native_ans_denom(deps: Deps, app: &App, denom: AssetEntry) -> AppResult<String> {
-    let ans_host = app.ans_host(deps)?;
-    let resolved_denom = denom.resolve(&deps.querier, &ans_host)?;
-    let denom = match resolved_denom {
-        AssetInfoBase::Native(denom) => Ok(denom),
-        _ => Err(StdError::generic_err("Non-native denom not supported")),
-    }?;
-    Ok(denom)
+    log_stake_movements(
+        deps.storage,
+        &env,
+        &info.sender,
+        StakeMovement::Return,
+        &refunds,
+    )?;
+    let refunds = queue_refunds(deps.storage, &env, &config, refunds)?;
+
+    Ok(app.custom_tag_response(
+        Response::default().add_messages(refunds).add_events(events),
+        "return_all_for_requester",
+        vec![
+            ("requester", requester.to_string()),
+            ("meetings_returned", meetings_returned.to_string()),
+            ("amount_returned", amount_returned.to_string()),
+        ],
+    ))
 }
 
-fn get_date_time(timezone: FixedOffset, timestamp: Int64) -> AppResult<DateTime<FixedOffset>> {
-    if let LocalResult::Single(value) = timezone.timestamp_opt(timestamp.i64(), 0) {
-        Ok(value)
+fn return_stakes(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    meetings: Vec<(Int64, u32)>,
+) -> AppResult {
+    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
+    assert_payouts_open(deps.storage)?;
+
+    if meetings.len() > MAX_BATCH {
+        return Err(AppError::BatchTooLarge {
+            max: MAX_BATCH as u32,
+        });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let timezone = validate_utc_offset(config.utc_offset)?;
+
+    let mut refunds: Vec<BankMsg> = vec![];
+    let mut amount_returned = Uint128::zero();
+    let mut events: Vec<Event> = vec![];
+    // Positions of the resolved meetings of each day, removed once the whole batch is handled
+    // so that the indices in the batch all refer to the same state.
+    let mut resolved: BTreeMap<i64, BTreeSet<usize>> = BTreeMap::new();
+    for (day_datetime, meeting_index) in &meetings {
+        validate_day_key(timezone, day_datetime.i64())?;
+        let mut day_meetings = CALENDAR
+            .may_load(deps.storage, deps.api, day_datetime.i64())?
+            .ok_or(AppError::NoMeetingsAtGivenDayDateTime {
+                day_datetime: day_datetime.i64(),
+            })?;
+        let previous = day_meetings.clone();
+        let position = meeting_position(*meeting_index)?;
+        let meeting = day_meetings
+            .get_mut(position)
+            .ok_or(AppError::MeetingDoesNotExist {
+                day_datetime: day_datetime.i64(),
+                meeting_index: *meeting_index,
+            })?;
+        validate_meeting_day(timezone, day_datetime.i64(), meeting)?;
+
+        if !meeting.has_ended(block_time(&env)?) {
+            return Err(AppError::MeetingNotFinishedYet {
+                day_datetime: day_datetime.i64(),
+                meeting_index: *meeting_index,
+            });
+        }
+        if meeting.resolution.is_some() {
+            return Err(AppError::StakeAlreadyHandled {
+                day_datetime: day_datetime.i64(),
+                meeting_index: *meeting_index,
+            });
+        }
+        resolved
+            .entry(day_datetime.i64())
+            .or_default()
+            .insert(position);
+
+        let action = if meeting.free {
+            ResolutionAction::Free
+        } else {
+            amount_returned = checked_add(amount_returned, meeting.amount_staked)?;
+            refunds.extend(refund_messages(
+                meeting,
+                meeting.amount_staked,
+                meeting.stake_denom(&config),
+            ));
+            ResolutionAction::Return
+        };
+        let resolution = Resolution {
+            action,
+            resolver: info.sender.clone(),
+            resolved_at: block_time(&env)?,
+            amount_to_requester: meeting.amount_staked,
+            amount_to_admin: Uint128::zero(),
+        };
+        events.push(
+            StakeResolvedEvent {
+                day_datetime: day_datetime.i64(),
+                meeting_index: *meeting_index,
+                meeting,
+                resolution: &resolution,
+                config: &config,
+            }
+            .into(),
+        );
+        meeting.resolution = Some(resolution);
+
+        // Saved per meeting so that a meeting listed twice is rejected as already handled.
+        save_day(
+            deps.storage,
+            deps.api,
+            &config,
+            day_datetime.i64(),
+            &previous,
+            &day_meetings,
+        )?;
+    }
+    for (day_datetime, positions) in resolved {
+        let mut day_meetings = CALENDAR.load(deps.storage, deps.api, day_datetime)?;
+        let previous = day_meetings.clone();
+        let mut removed = vec![];
+        for position in positions.into_iter().rev() {
+            removed.push(day_meetings.remove(position));
+        }
+        save_day(
+            deps.storage,
+            deps.api,
+            &config,
+            day_datetime,
+            &previous,
+            &day_meetings,
+        )?;
+        for meeting in removed.iter().rev() {
+            archive_resolved(deps.storage, day_datetime, meeting)?;
+        }
+    }
+
+    log_stake_movements(
+        deps.storage,
+        &env,
+        &info.sender,
+        StakeMovement::Return,
+        &refunds,
+    )?;
+    let refunds = queue_refunds(deps.storage, &env, &config, refunds)?;
+
+    Ok(app.custom_tag_response(
+        Response::default().add_messages(refunds).add_events(events),
+        "return_stakes",
+        vec![
+            ("meetings_returned", meetings.len().to_string()),
+            ("amount_returned", amount_returned.to_string()),
+        ],
+    ))
+}
+
+fn import_state(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    config: Option<Config>,
+    days: Vec<(i64, Vec<Meeting>)>,
+    force: bool,
+) -> AppResult {
+    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let config = config
+        .map(|config| validate_imported_config(deps.api, config))
+        .transpose()?;
+    let (utc_offset, overbooking_allowed) = match &config {
+        Some(config) => (config.utc_offset, config.overbooking_allowed),
+        None => {
+            let config = CONFIG.load(deps.storage)?;
+            (config.utc_offset, config.overbooking_allowed)
+        }
+    };
+    let timezone = validate_utc_offset(utc_offset)?;
+    let mut imported = BTreeSet::new();
+    for (day_datetime, meetings) in &days {
+        // Days are written one after the other, so a repeated key would overwrite the first.
+        if !imported.insert(*day_datetime) {
+            return Err(AppError::DuplicateImportedDay {
+                day_datetime: *day_datetime,
+            });
+        }
+        if !force && CALENDAR.has(deps.storage, *day_datetime) {
+            return Err(AppError::DayNotEmpty {
+                day_datetime: *day_datetime,
+            });
+        }
+        validate_imported_day(deps.api, timezone, *day_datetime, meetings)?;
+        if overbooking_allowed {
+            continue;
+        }
+        // Days are kept in booking order rather than by start time, so every meeting is
+        // checked against the ones before it.
+        for (position, meeting) in meetings.iter().enumerate() {
+            if find_conflict(&meetings[..position], meeting.start_time, meeting.end_time).is_some()
+            {
+                return Err(AppError::ImportedMeetingsOverlap {
+                    day_datetime: *day_datetime,
+                    start_time: meeting.start_time,
+                });
+            }
+        }
+    }
+
+    if let Some(config) = config {
+        CONFIG.save(deps.storage, &config)?;
+    }
+    let config = CONFIG.load(deps.storage)?;
+    for (day_datetime, meetings) in &days {
+        // Forced imports overwrite the stored day, whose stakes are then no longer held.
+        let previous = CALENDAR
+            .may_load(deps.storage, deps.api, *day_datetime)?
+            .unwrap_or_default();
+        save_day(
+            deps.storage,
+            deps.api,
+            &config,
+            *day_datetime,
+            &previous,
+            meetings,
+        )?;
+    }
+
+    Ok(app.custom_tag_response(
+        Response::default(),
+        "import_state",
+        vec![("days_imported", days.len().to_string())],
+    ))
+}
+
+/// Validates an imported config with the checks of instantiation and `UpdateConfig`, returning
+/// it with its profile fields normalized like they are when set.
+fn validate_imported_config(api: &dyn Api, mut config: Config) -> AppResult<Config> {
+    validate_utc_offset(config.utc_offset)?;
+    validate_calendar_hours(&config.start_time, &config.end_time)?;
+    validate_denom(&config.denom)?;
+    for (denom, _) in &config.accepted_denoms {
+        validate_denom(denom)?;
+    }
+    // Zero is the granularity of a calendar that never set one.
+    if config.time_granularity_seconds != 0 {
+        validate_granularity(config.time_granularity_seconds)?;
+    }
+    validate_slash_curve(&config.slash_curve)?;
+    for address in config.attestor.iter().chain(&config.price_source) {
+        api.addr_validate(address.as_str())?;
+    }
+    config.slash_distribution =
+        validate_slash_distribution(api, unchecked_splits(&config.slash_distribution))?;
+    let profile_field = |field, value: Option<String>, max_length| -> AppResult<Option<String>> {
+        Ok(value
+            .map(|value| validate_profile_field(field, value, max_length))
+            .transpose()?
+            .flatten())
+    };
+    config.name = profile_field("name", config.name, MAX_NAME_LENGTH)?;
+    config.description = profile_field("description", config.description, MAX_DESCRIPTION_LENGTH)?;
+    config.contact_uri = profile_field("contact_uri", config.contact_uri, MAX_CONTACT_URI_LENGTH)?;
+    Ok(config)
+}
+
+/// Validates the key and meetings of an imported day with the checks a booking gets that do not
+/// depend on when it is made, so meetings booked under earlier calendar hours still import.
+fn validate_imported_day(
+    api: &dyn Api,
+    timezone: FixedOffset,
+    day_datetime: i64,
+    meetings: &[Meeting],
+) -> AppResult<()> {
+    validate_day_key(timezone, day_datetime)?;
+    for meeting in meetings {
+        MeetingDuration::of(meeting)?;
+        validate_meeting_day(timezone, day_datetime, meeting)?;
+        api.addr_validate(meeting.requester.as_str())?;
+        if let Some(denom) = &meeting.denom {
+            validate_denom(denom)?;
+        }
+        if let Some(refund_splits) = &meeting.refund_splits {
+            validate_refund_splits(api, unchecked_splits(refund_splits))?;
+        }
+        // Resolved meetings are moved out of the calendar, so their stake is no longer held.
+        if meeting.resolution.is_some() {
+            return Err(AppError::ImportedMeetingResolved {
+                day_datetime,
+                start_time: meeting.start_time,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns stored splits in the form they are given in messages, to validate them again.
+fn unchecked_splits(splits: &[(Addr, u16)]) -> Vec<(String, u16)> {
+    splits
+        .iter()
+        .map(|(recipient, bps)| (recipient.to_string(), *bps))
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn amend_meeting(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    day_datetime: Int64,
+    meeting_index: u32,
+    new_start: Int64,
+    new_end: Int64,
+) -> AppResult {
+    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    validate_timestamp(new_start)?;
+    validate_timestamp(new_end)?;
+
+    let (mut meetings, position) =
+        load_day_meeting(deps.storage, deps.api, &config, day_datetime, meeting_index)?;
+    let previous = meetings.clone();
+    let mut meeting = meetings.remove(position);
+
+    let now = block_time(&env)?;
+    if now >= meeting.start_time {
+        return Err(AppError::MeetingAlreadyStarted {});
+    }
+
+    let timezone: FixedOffset = validate_utc_offset(config.utc_offset)?;
+    validate_window(&config, new_start.i64(), new_end.i64(), now)?;
+
+    // The meeting moves to another day when its date was amended.
+    let new_day_datetime = day_key(timezone, new_start.i64())?;
+    let same_day = new_day_datetime == day_datetime.i64();
+    let mut new_day_meetings = if same_day {
+        std::mem::take(&mut meetings)
+    } else {
+        CALENDAR
+            .may_load(deps.storage, deps.api, new_day_datetime)?
+            .unwrap_or_default()
+    };
+
+    if let Some(conflicting_meeting) =
+        find_conflict(&new_day_meetings, new_start.i64(), new_end.i64())
+    {
+        return Err(conflict_error(&config, conflicting_meeting));
+    }
+    assert_not_held(deps.storage, &env, new_start.i64(), new_end.i64())?;
+    if violates_self_gap(
+        &new_day_meetings,
+        &meeting.requester,
+        new_start.i64(),
+        new_end.i64(),
+        config.self_gap_minutes,
+    ) {
+        return Err(AppError::SelfGapViolated {
+            self_gap_minutes: config.self_gap_minutes,
+        });
+    }
+
+    let denom = meeting.stake_denom(&config).to_string();
+    let price_per_minute =
+        config
+            .price_per_minute_in(&denom)
+            .ok_or_else(|| AppError::DenomNotAccepted {
+                denom: denom.clone(),
+            })?;
+    let new_stake = required_stake(
+        &deps.querier,
+        &config,
+        &denom,
+        price_per_minute,
+        new_start.i64(),
+        priced_end_time(&config, new_start.i64(), new_end.i64())?,
+    )?;
+    let amount_sent = if info.funds.is_empty() {
+        Uint128::zero()
+    } else {
+        must_pay_in(&info, &denom)?
+    };
+    let expected_amount = new_stake.saturating_sub(meeting.amount_staked);
+    if amount_sent != expected_amount {
+        return Err(AppError::InvalidStakeAmountSent {
+            expected_amount,
+            amount_sent,
+        });
+    }
+    let refunds = if meeting.amount_staked > new_stake {
+        refund_messages(&meeting, meeting.amount_staked - new_stake, &denom)
+    } else {
+        vec![]
+    };
+    if !refunds.is_empty() {
+        assert_payouts_open(deps.storage)?;
+    }
+
+    // A moved meeting is rebooked, so its deadline follows the new start.
+    meeting.cancel_deadline = Some(config.cancel_deadline(new_start.i64()));
+    meeting.start_time = new_start.i64();
+    meeting.end_time = new_end.i64();
+    meeting.amount_staked = new_stake;
+    meeting.free = new_stake.is_zero();
+
+    if same_day {
+        new_day_meetings.insert(position, meeting);
+        save_day(
+            deps.storage,
+            deps.api,
+            &config,
+            new_day_datetime,
+            &previous,
+            &new_day_meetings,
+        )?;
+    } else {
+        new_day_meetings.push(meeting);
+        save_day(
+            deps.storage,
+            deps.api,
+            &config,
+            day_datetime.i64(),
+            &previous,
+            &meetings,
+        )?;
+        save_day(
+            deps.storage,
+            deps.api,
+            &config,
+            new_day_datetime,
+            &new_day_meetings[..new_day_meetings.len() - 1],
+            &new_day_meetings,
+        )?;
+    }
+
+    log_stake_movements(
+        deps.storage,
+        &env,
+        &info.sender,
+        StakeMovement::Return,
+        &refunds,
+    )?;
+    let refunds = queue_refunds(deps.storage, &env, &config, refunds)?;
+
+    Ok(app.custom_tag_response(
+        Response::default().add_messages(refunds),
+        "amend_meeting",
+        vec![
+            ("amended_by", "admin".to_string()),
+            ("meeting_start_time", new_start.to_string()),
+            ("meeting_end_time", new_end.to_string()),
+            ("stake", new_stake.to_string()),
+        ],
+    ))
+}
+
+fn extend_meeting(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    day_datetime: Int64,
+    meeting_index: u32,
+    new_end_time: Int64,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let (mut meetings, position) =
+        load_day_meeting(deps.storage, deps.api, &config, day_datetime, meeting_index)?;
+
+    let (additional_stake, conflicts) = extension_quote(
+        &deps.querier,
+        &config,
+        block_time(&env)?,
+        day_datetime.i64(),
+        &meetings,
+        meeting_index,
+        new_end_time,
+    )?;
+    if info.sender != meetings[position].requester {
+        return Err(AppError::NotMeetingRequester {});
+    }
+    if conflicts {
+        let end_time = meetings[position].end_time;
+        if let Some(conflicting_meeting) = find_conflict(&meetings, end_time, new_end_time.i64()) {
+            return Err(conflict_error(&config, conflicting_meeting));
+        }
+    }
+    let previous = meetings.clone();
+    let meeting = &mut meetings[position];
+    assert_not_held(deps.storage, &env, meeting.end_time, new_end_time.i64())?;
+
+    let amount_sent = if info.funds.is_empty() {
+        Uint128::zero()
+    } else {
+        must_pay_in(&info, &additional_stake.denom)?
+    };
+    if amount_sent != additional_stake.amount {
+        return Err(AppError::InvalidStakeAmountSent {
+            expected_amount: additional_stake.amount,
+            amount_sent,
+        });
+    }
+
+    meeting.end_time = new_end_time.i64();
+    meeting.amount_staked = checked_add(meeting.amount_staked, additional_stake.amount)?;
+    meeting.free = meeting.amount_staked.is_zero();
+    save_day(
+        deps.storage,
+        deps.api,
+        &config,
+        day_datetime.i64(),
+        &previous,
+        &meetings,
+    )?;
+
+    Ok(app.custom_tag_response(
+        Response::default(),
+        "extend_meeting",
+        vec![
+            ("meeting_end_time", new_end_time.to_string()),
+            ("additional_stake", additional_stake.amount.to_string()),
+        ],
+    ))
+}
+
+/// Returns the additional stake needed to extend the meeting at `meeting_index` to end at
+/// `new_end_time`, and whether the extension runs into another meeting.
+pub fn extension_quote(
+    querier: &QuerierWrapper,
+    config: &Config,
+    now: i64,
+    day_datetime: i64,
+    meetings: &[Meeting],
+    meeting_index: u32,
+    new_end_time: Int64,
+) -> AppResult<(Coin, bool)> {
+    let meeting =
+        meetings
+            .get(meeting_position(meeting_index)?)
+            .ok_or(AppError::MeetingDoesNotExist {
+                day_datetime,
+                meeting_index,
+            })?;
+
+    let timezone: FixedOffset = validate_utc_offset(config.utc_offset)?;
+    let new_end_datetime = get_date_time(timezone, new_end_time)?;
+    validate_extension(config, now, meeting, &new_end_datetime)?;
+
+    let denom = meeting.stake_denom(config);
+    let price_per_minute =
+        config
+            .price_per_minute_in(denom)
+            .ok_or_else(|| AppError::DenomNotAccepted {
+                denom: denom.to_string(),
+            })?;
+    let new_stake = required_stake(
+        querier,
+        config,
+        denom,
+        price_per_minute,
+        meeting.start_time,
+        priced_end_time(config, meeting.start_time, new_end_time.i64())?,
+    )?;
+    let additional_stake = new_stake.saturating_sub(meeting.amount_staked);
+
+    // The meeting itself ends where the extension starts, so it never conflicts with it.
+    let conflicts = find_conflict(meetings, meeting.end_time, new_end_time.i64()).is_some();
+
+    Ok((Coin::new(additional_stake.into(), denom), conflicts))
+}
+
+fn claim_refund(deps: DepsMut, info: MessageInfo, app: App, env: Env, refund_id: u64) -> AppResult {
+    assert_payouts_open(deps.storage)?;
+    let refund = QUEUED_REFUNDS
+        .may_load(deps.storage, refund_id)?
+        .ok_or(AppError::RefundNotFound { refund_id })?;
+    if info.sender != refund.recipient {
+        return Err(AppError::NotRefundRecipient {});
+    }
+    if env.block.time > refund.claim_deadline {
+        return Err(AppError::RefundClaimExpired {});
+    }
+    QUEUED_REFUNDS.remove(deps.storage, refund_id);
+    decrease_total(
+        deps.storage,
+        &QUEUED_REFUNDS_TOTAL,
+        &refund.amount.denom,
+        refund.amount.amount,
+    )?;
+
+    let claim = vec![BankMsg::Send {
+        to_address: refund.recipient.to_string(),
+        amount: vec![refund.amount],
+    }];
+    log_stake_movements(
+        deps.storage,
+        &env,
+        &info.sender,
+        StakeMovement::Claim,
+        &claim,
+    )?;
+
+    Ok(app.custom_tag_response(
+        Response::default().add_messages(claim),
+        "claim_refund",
+        vec![("refund_id", refund_id.to_string())],
+    ))
+}
+
+fn sweep_unclaimed(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    refund_id: u64,
+) -> AppResult {
+    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
+    assert_payouts_open(deps.storage)?;
+
+    let refund = QUEUED_REFUNDS
+        .may_load(deps.storage, refund_id)?
+        .ok_or(AppError::RefundNotFound { refund_id })?;
+    if env.block.time <= refund.claim_deadline {
+        return Err(AppError::RefundClaimNotExpired {});
+    }
+    QUEUED_REFUNDS.remove(deps.storage, refund_id);
+    decrease_total(
+        deps.storage,
+        &QUEUED_REFUNDS_TOTAL,
+        &refund.amount.denom,
+        refund.amount.amount,
+    )?;
+
+    let sweep = vec![BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: vec![refund.amount],
+    }];
+    log_stake_movements(
+        deps.storage,
+        &env,
+        &info.sender,
+        StakeMovement::Sweep,
+        &sweep,
+    )?;
+
+    Ok(app.custom_tag_response(
+        Response::default().add_messages(sweep),
+        "sweep_unclaimed",
+        vec![("refund_id", refund_id.to_string())],
+    ))
+}
+
+fn attest(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    day_datetime: Int64,
+    meeting_index: u32,
+    attended: bool,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    assert_attestor(deps.as_ref(), &app, &config, &info.sender)?;
+
+    let (mut meetings, position) =
+        load_day_meeting(deps.storage, deps.api, &config, day_datetime, meeting_index)?;
+    let meeting = &mut meetings[position];
+    meeting.attended = Some(attended);
+    meeting.disputed = false;
+    CALENDAR.save(deps.storage, deps.api, day_datetime.i64(), &meetings)?;
+
+    Ok(app.custom_tag_response(
+        Response::default(),
+        "attest",
+        vec![("attended", attended.to_string())],
+    ))
+}
+
+fn dispute_attendance(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    day_datetime: Int64,
+    meeting_index: u32,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let (mut meetings, position) =
+        load_day_meeting(deps.storage, deps.api, &config, day_datetime, meeting_index)?;
+    let meeting = &mut meetings[position];
+    if meeting.requester != info.sender {
+        return Err(AppError::NotMeetingRequester {});
+    }
+    if meeting.attended.is_none() {
+        return Err(AppError::MeetingNotAttested {
+            day_datetime: day_datetime.i64(),
+            meeting_index,
+        });
+    }
+    meeting.disputed = true;
+    CALENDAR.save(deps.storage, deps.api, day_datetime.i64(), &meetings)?;
+
+    Ok(app.custom_tag_response(
+        Response::default(),
+        "dispute_attendance",
+        vec![("meeting_index", meeting_index.to_string())],
+    ))
+}
+
+fn check_in(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    day_datetime: Int64,
+    meeting_index: u32,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let now = block_time(&env)?;
+    let (mut meetings, position) =
+        load_day_meeting(deps.storage, deps.api, &config, day_datetime, meeting_index)?;
+    let meeting = &mut meetings[position];
+    if meeting.requester != info.sender {
+        return Err(AppError::NotMeetingRequester {});
+    }
+    if now < meeting.start_time || now >= meeting.ended_at() {
+        return Err(AppError::MeetingNotInProgress {
+            day_datetime: day_datetime.i64(),
+            meeting_index,
+        });
+    }
+    meeting.checked_in = true;
+    CALENDAR.save(deps.storage, deps.api, day_datetime.i64(), &meetings)?;
+
+    Ok(app.custom_tag_response(
+        Response::default(),
+        "check_in",
+        vec![("meeting_index", meeting_index.to_string())],
+    ))
+}
+
+fn set_encrypted_payload(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    day_datetime: Int64,
+    meeting_index: u32,
+    payload: Option<Binary>,
+) -> AppResult {
+    if let Some(payload) = &payload {
+        if payload.len() > MAX_ENCRYPTED_PAYLOAD_BYTES {
+            return Err(AppError::EncryptedPayloadTooLarge {
+                size: payload.len(),
+                max: MAX_ENCRYPTED_PAYLOAD_BYTES,
+            });
+        }
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let (mut meetings, position) =
+        load_day_meeting(deps.storage, deps.api, &config, day_datetime, meeting_index)?;
+    let meeting = &mut meetings[position];
+    if meeting.requester != info.sender {
+        return Err(AppError::NotMeetingRequester {});
+    }
+    let size = payload.as_ref().map_or(0, |payload| payload.len());
+    meeting.encrypted_payload = payload;
+    CALENDAR.save(deps.storage, deps.api, day_datetime.i64(), &meetings)?;
+
+    Ok(app.custom_tag_response(
+        Response::default(),
+        "set_encrypted_payload",
+        vec![
+            ("meeting_index", meeting_index.to_string()),
+            ("payload_bytes", size.to_string()),
+        ],
+    ))
+}
+
+fn end_meeting(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    day_datetime: Int64,
+    meeting_index: u32,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    assert_attestor(deps.as_ref(), &app, &config, &info.sender)?;
+
+    let now = block_time(&env)?;
+    let (mut meetings, position) =
+        load_day_meeting(deps.storage, deps.api, &config, day_datetime, meeting_index)?;
+    let meeting = &mut meetings[position];
+    if now < meeting.start_time || now >= meeting.ended_at() {
+        return Err(AppError::MeetingNotInProgress {
+            day_datetime: day_datetime.i64(),
+            meeting_index,
+        });
+    }
+    meeting.ended_early_at = Some(now);
+    CALENDAR.save(deps.storage, deps.api, day_datetime.i64(), &meetings)?;
+
+    Ok(app.custom_tag_response(
+        Response::default(),
+        "end_meeting",
+        vec![("ended_at", now.to_string())],
+    ))
+}
+
+fn hold_slot(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    start_time: Int64,
+    end_time: Int64,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+
+    let timezone: FixedOffset = validate_utc_offset(config.utc_offset)?;
+    validate_timestamp(start_time)?;
+    validate_timestamp(end_time)?;
+    validate_window(&config, start_time.i64(), end_time.i64(), block_time(&env)?)?;
+
+    let meetings = CALENDAR
+        .may_load(deps.storage, deps.api, day_key(timezone, start_time.i64())?)?
+        .unwrap_or_default();
+    if let Some(conflicting_meeting) = find_conflict(&meetings, start_time.i64(), end_time.i64()) {
+        return Err(conflict_error(&config, conflicting_meeting));
+    }
+    if violates_self_gap(
+        &meetings,
+        &info.sender,
+        start_time.i64(),
+        end_time.i64(),
+        config.self_gap_minutes,
+    ) {
+        return Err(AppError::SelfGapViolated {
+            self_gap_minutes: config.self_gap_minutes,
+        });
+    }
+    assert_not_held(deps.storage, &env, start_time.i64(), end_time.i64())?;
+
+    let stake = required_stake(
+        &deps.querier,
+        &config,
+        &config.denom,
+        config.price_per_minute,
+        start_time.i64(),
+        priced_end_time(&config, start_time.i64(), end_time.i64())?,
+    )?;
+    let admin = app.admin.get(deps.as_ref())?;
+    is_self_booking(&config, admin.as_ref(), &info.sender)?;
+    let booking_fee = match admin {
+        Some(_) => config.booking_fee,
+        None => Uint128::zero(),
+    };
+    let hold = Hold {
+        requester: info.sender,
+        start_time: start_time.i64(),
+        end_time: end_time.i64(),
+        stake: Coin::new(stake.into(), config.denom),
+        booking_fee,
+        expires_at: env.block.time.plus_seconds(HOLD_TTL_SECONDS),
+    };
+    let hold_id = HOLDS_SEQ.may_load(deps.storage)?.unwrap_or_default();
+    HOLDS.save(deps.storage, hold_id, &hold)?;
+    HOLDS_SEQ.save(deps.storage, &(hold_id + 1))?;
+
+    Ok(app.custom_tag_response(
+        Response::default(),
+        "hold_slot",
+        vec![
+            ("hold_id", hold_id.to_string()),
+            ("stake", hold.stake.to_string()),
+            ("booking_fee", booking_fee.to_string()),
+            ("expires_at", hold.expires_at.seconds().to_string()),
+        ],
+    ))
+}
+
+fn confirm_hold(deps: DepsMut, info: MessageInfo, app: App, env: Env, hold_id: u64) -> AppResult {
+    let hold = HOLDS
+        .may_load(deps.storage, hold_id)?
+        .ok_or(AppError::HoldNotFound { hold_id })?;
+    if info.sender != hold.requester {
+        return Err(AppError::NotMeetingRequester {});
+    }
+    if env.block.time > hold.expires_at {
+        return Err(AppError::HoldExpired {});
+    }
+    if block_time(&env)? > hold.start_time {
+        return Err(AppError::StartTimeMustBeInFuture {});
+    }
+
+    let expected_amount = checked_add(hold.stake.amount, hold.booking_fee)?;
+    let amount_sent = if expected_amount.is_zero() {
+        nonpayable(&info)?;
+        Uint128::zero()
+    } else {
+        must_pay_in(&info, &hold.stake.denom)?
+    };
+    if amount_sent != expected_amount {
+        return Err(AppError::InvalidStakeAmountSent {
+            expected_amount,
+            amount_sent,
+        });
+    }
+    HOLDS.remove(deps.storage, hold_id);
+
+    let config = CONFIG.load(deps.storage)?;
+    let timezone: FixedOffset = validate_utc_offset(config.utc_offset)?;
+    let day_datetime = day_key(timezone, hold.start_time)?;
+    let mut meetings = CALENDAR
+        .may_load(deps.storage, deps.api, day_datetime)?
+        .unwrap_or_default();
+    // Held slots cannot be booked by others, but meetings may have been imported since.
+    if let Some(conflicting_meeting) = find_conflict(&meetings, hold.start_time, hold.end_time) {
+        return Err(conflict_error(&config, conflicting_meeting));
+    }
+
+    // The fee is paid out to the admin right away, or stays with the stake once the admin is
+    // renounced.
+    let admin = app.admin.get(deps.as_ref())?;
+    let self_booked = is_self_booking(&config, admin.as_ref(), &hold.requester)?;
+    let (amount_staked, fee_messages) = match admin {
+        Some(admin) if !hold.booking_fee.is_zero() => (
+            hold.stake.amount,
+            vec![BankMsg::Send {
+                to_address: admin.to_string(),
+                amount: vec![Coin::new(hold.booking_fee.into(), &hold.stake.denom)],
+            }],
+        ),
+        _ => (expected_amount, vec![]),
+    };
+    meetings.push(Meeting {
+        start_time: hold.start_time,
+        end_time: hold.end_time,
+        requester: hold.requester,
+        amount_staked,
+        refund_splits: None,
+        denom: Some(hold.stake.denom),
+        reminder_minutes: None,
+        free: amount_staked.is_zero(),
+        attended: None,
+        disputed: false,
+        checked_in: false,
+        ended_early_at: None,
+        self_booked,
+        resolution: None,
+        cancel_deadline: Some(config.cancel_deadline(hold.start_time)),
+        encrypted_payload: None,
+    });
+    let booked = Event::from(MeetingBookedEvent {
+        day_datetime,
+        meeting_index: meeting_index_at(meetings.len() - 1)?,
+        meeting: &meetings[meetings.len() - 1],
+        config: &config,
+    });
+    save_day(
+        deps.storage,
+        deps.api,
+        &config,
+        day_datetime,
+        &meetings[..meetings.len() - 1],
+        &meetings,
+    )?;
+
+    Ok(app.custom_tag_response(
+        Response::default()
+            .add_messages(fee_messages)
+            .add_event(booked),
+        "confirm_hold",
+        vec![
+            ("hold_id", hold_id.to_string()),
+            ("meeting_start_time", hold.start_time.to_string()),
+            ("meeting_end_time", hold.end_time.to_string()),
+            ("day_datetime", day_datetime.to_string()),
+        ],
+    ))
+}
+
+fn join_waitlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    start_time: Int64,
+    end_time: Int64,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+
+    let timezone: FixedOffset = validate_utc_offset(config.utc_offset)?;
+    validate_timestamp(start_time)?;
+    validate_timestamp(end_time)?;
+    validate_window(&config, start_time.i64(), end_time.i64(), block_time(&env)?)?;
+    let admin = app.admin.get(deps.as_ref())?;
+    is_self_booking(&config, admin.as_ref(), &info.sender)?;
+
+    let day_datetime = day_key(timezone, start_time.i64())?;
+    let meetings = CALENDAR
+        .may_load(deps.storage, deps.api, day_datetime)?
+        .unwrap_or_default();
+    if config.overbooking_allowed
+        || find_conflict(&meetings, start_time.i64(), end_time.i64()).is_none()
+    {
+        return Err(AppError::SlotAvailable {});
+    }
+
+    let stake = required_stake(
+        &deps.querier,
+        &config,
+        &config.denom,
+        config.price_per_minute,
+        start_time.i64(),
+        priced_end_time(&config, start_time.i64(), end_time.i64())?,
+    )?;
+    let booking_fee = match admin {
+        Some(_) => config.booking_fee,
+        None => Uint128::zero(),
+    };
+    let expected_amount = checked_add(stake, booking_fee)?;
+    let amount_sent = if expected_amount.is_zero() {
+        nonpayable(&info)?;
+        Uint128::zero()
+    } else {
+        must_pay_in(&info, &config.denom)?
+    };
+    if amount_sent != expected_amount {
+        return Err(AppError::InvalidStakeAmountSent {
+            expected_amount,
+            amount_sent,
+        });
+    }
+
+    let entry_id = WAITLIST_SEQ.may_load(deps.storage)?.unwrap_or_default();
+    increase_total(
+        deps.storage,
+        &WAITLIST_TOTAL,
+        &config.denom,
+        expected_amount,
+    )?;
+    WAITLIST.save(
+        deps.storage,
+        (day_datetime, entry_id),
+        &WaitlistEntry {
+            requester: info.sender,
+            day_datetime,
+            start_time: start_time.i64(),
+            end_time: end_time.i64(),
+            stake: Coin::new(stake.into(), config.denom),
+            booking_fee,
+        },
+    )?;
+    WAITLIST_SEQ.save(deps.storage, &(entry_id + 1))?;
+
+    Ok(app.custom_tag_response(
+        Response::default(),
+        "join_waitlist",
+        vec![
+            ("day_datetime", day_datetime.to_string()),
+            ("entry_id", entry_id.to_string()),
+        ],
+    ))
+}
+
+fn leave_waitlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    day_datetime: Int64,
+    entry_id: u64,
+) -> AppResult {
+    assert_payouts_open(deps.storage)?;
+    let key = (day_datetime.i64(), entry_id);
+    let entry = WAITLIST
+        .may_load(deps.storage, key)?
+        .ok_or(AppError::WaitlistEntryNotFound { entry_id })?;
+    if entry.requester != info.sender {
+        return Err(AppError::NotMeetingRequester {});
+    }
+    WAITLIST.remove(deps.storage, key);
+
+    // Some bank modules reject sends of zero.
+    let amount = checked_add(entry.stake.amount, entry.booking_fee)?;
+    decrease_total(deps.storage, &WAITLIST_TOTAL, &entry.stake.denom, amount)?;
+    let refund = if amount.is_zero() {
+        vec![]
+    } else {
+        vec![BankMsg::Send {
+            to_address: entry.requester.to_string(),
+            amount: vec![Coin::new(amount.into(), entry.stake.denom)],
+        }]
+    };
+    log_stake_movements(
+        deps.storage,
+        &env,
+        &info.sender,
+        StakeMovement::Return,
+        &refund,
+    )?;
+
+    Ok(app.custom_tag_response(
+        Response::default().add_messages(refund),
+        "leave_waitlist",
+        vec![
+            ("entry_id", entry_id.to_string()),
+            ("amount", amount.to_string()),
+        ],
+    ))
+}
+
+fn cancel_meeting(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    day_datetime: Int64,
+    meeting_index: u32,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let (mut meetings, position) =
+        load_day_meeting(deps.storage, deps.api, &config, day_datetime, meeting_index)?;
+    let meeting = &meetings[position];
+    if meeting.requester != info.sender {
+        return Err(AppError::NotMeetingRequester {});
+    }
+    let now = block_time(&env)?;
+    if now >= meeting.start_time {
+        return Err(AppError::MeetingAlreadyStarted {});
+    }
+    if matches!(meeting.cancel_deadline, Some(cancel_deadline) if now > cancel_deadline) {
+        return Err(AppError::PastCancellationDeadline {});
+    }
+
+    let previous = meetings.clone();
+    let meeting = meetings.remove(position);
+    let refunds = refund_messages(
+        &meeting,
+        meeting.amount_staked,
+        meeting.stake_denom(&config),
+    );
+    if !refunds.is_empty() {
+        assert_payouts_open(deps.storage)?;
+    }
+    let admin = app.admin.get(deps.as_ref())?;
+    let (promoted, fee_messages) = promote_waitlist(
+        deps.storage,
+        &env,
+        &config,
+        admin.as_ref(),
+        day_datetime.i64(),
+        &mut meetings,
+    )?;
+    // Promoted entries are appended to the day.
+    let booked: Vec<Event> = meetings
+        .iter()
+        .enumerate()
+        .skip(meetings.len() - meeting_position(promoted)?)
+        .map(|(position, meeting)| -> AppResult<Event> {
+            Ok(MeetingBookedEvent {
+                day_datetime: day_datetime.i64(),
+                meeting_index: meeting_index_at(position)?,
+                meeting,
+                config: &config,
+            }
+            .into())
+        })
+        .collect::<AppResult<_>>()?;
+    save_day(
+        deps.storage,
+        deps.api,
+        &config,
+        day_datetime.i64(),
+        &previous,
+        &meetings,
+    )?;
+
+    log_stake_movements(
+        deps.storage,
+        &env,
+        &info.sender,
+        StakeMovement::Return,
+        &refunds,
+    )?;
+    let refunds = queue_refunds(deps.storage, &env, &config, refunds)?;
+
+    Ok(app.custom_tag_response(
+        Response::default()
+            .add_messages(refunds)
+            .add_messages(fee_messages)
+            .add_events(booked),
+        "cancel_meeting",
+        vec![
+            ("amount_returned", meeting.amount_staked.to_string()),
+            ("promoted_entries", promoted.to_string()),
+        ],
+    ))
+}
+
+fn transfer_all_meetings(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    to: String,
+    start_after: Option<Int64>,
+) -> AppResult {
+    let to = deps.api.addr_validate(&to)?;
+    let config = CONFIG.load(deps.storage)?;
+    let admin = app.admin.get(deps.as_ref())?;
+    // The meetings are booked by `to` from now on, which may be the admin.
+    let self_booked = is_self_booking(&config, admin.as_ref(), &to)?;
+    let now = block_time(&env)?;
+    // Meetings starting after `now` are on days whose local midnight is less than a day before.
+    let mut last_read = match start_after {
+        Some(start_after) => start_after.i64().max(now - 86_400),
+        None => now - 86_400,
+    };
+
+    let mut meetings_transferred: usize = 0;
+    let mut has_more = false;
+    let mut changed_days = vec![];
+    // Days are read one at a time, up to the first meeting beyond the batch or the first day
+    // beyond the days read per call.
+    let min = Bound::exclusive(last_read);
+    let days = CALENDAR.range(deps.storage, deps.api, Some(min), None, Order::Ascending);
+    for (days_read, day) in days.enumerate() {
+        if days_read == MAX_BATCH {
+            has_more = true;
+            break;
+        }
+        let (day_datetime, mut meetings) = day?;
+        let mut changed = false;
+        for meeting in meetings.iter_mut() {
+            if meeting.requester != info.sender || meeting.start_time <= now {
+                continue;
+            }
+            if meetings_transferred >= MAX_BATCH {
+                has_more = true;
+                break;
+            }
+            meeting.requester = to.clone();
+            // The splits were chosen by the old requester, so the stake returns to `to`.
+            meeting.refund_splits = None;
+            meeting.self_booked = self_booked;
+            meetings_transferred += 1;
+            changed = true;
+        }
+        if changed {
+            changed_days.push((day_datetime, meetings));
+        }
+        if has_more {
+            break;
+        }
+        // A day left behind with meetings still to transfer is read again by the next call.
+        last_read = day_datetime;
+    }
+    // Only requesters change, so the stakes held stay the same.
+    for (day_datetime, meetings) in changed_days {
+        CALENDAR.save(deps.storage, deps.api, day_datetime, &meetings)?;
+    }
+
+    let mut attributes = vec![
+        ("to", to.to_string()),
+        ("meetings_transferred", meetings_transferred.to_string()),
+        ("has_more", has_more.to_string()),
+    ];
+    if has_more {
+        attributes.push(("next_key", last_read.to_string()));
+    }
+    Ok(app.custom_tag_response(Response::default(), "transfer_all_meetings", attributes))
+}
+
+fn block_slot(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    start_time: Int64,
+    end_time: Int64,
+) -> AppResult {
+    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let timezone: FixedOffset = validate_utc_offset(config.utc_offset)?;
+    validate_timestamp(start_time)?;
+    validate_timestamp(end_time)?;
+    validate_window(&config, start_time.i64(), end_time.i64(), block_time(&env)?)?;
+
+    let day_datetime = day_key(timezone, start_time.i64())?;
+    let mut meetings = CALENDAR
+        .may_load(deps.storage, deps.api, day_datetime)?
+        .unwrap_or_default();
+    if let Some(conflicting_meeting) = find_conflict(&meetings, start_time.i64(), end_time.i64()) {
+        return Err(conflict_error(&config, conflicting_meeting));
+    }
+    assert_not_held(deps.storage, &env, start_time.i64(), end_time.i64())?;
+
+    meetings.push(Meeting {
+        start_time: start_time.i64(),
+        end_time: end_time.i64(),
+        requester: info.sender,
+        amount_staked: Uint128::zero(),
+        refund_splits: None,
+        denom: None,
+        reminder_minutes: None,
+        free: true,
+        attended: None,
+        disputed: false,
+        checked_in: false,
+        ended_early_at: None,
+        self_booked: true,
+        resolution: None,
+        cancel_deadline: Some(config.cancel_deadline(start_time.i64())),
+        encrypted_payload: None,
+    });
+    let booked = Event::from(MeetingBookedEvent {
+        day_datetime,
+        meeting_index: meeting_index_at(meetings.len() - 1)?,
+        meeting: &meetings[meetings.len() - 1],
+        config: &config,
+    });
+    save_day(
+        deps.storage,
+        deps.api,
+        &config,
+        day_datetime,
+        &meetings[..meetings.len() - 1],
+        &meetings,
+    )?;
+
+    Ok(app.custom_tag_response(
+        Response::default().add_event(booked),
+        "block_slot",
+        vec![("day_datetime", day_datetime.to_string())],
+    ))
+}
+
+fn set_payout_freeze(deps: DepsMut, info: MessageInfo, app: App, frozen: bool) -> AppResult {
+    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
+    PAYOUTS_FROZEN.save(deps.storage, &frozen)?;
+
+    Ok(app.custom_tag_response(
+        Response::default(),
+        "set_payout_freeze",
+        vec![("payouts_frozen", frozen.to_string())],
+    ))
+}
+
+fn recover_funds(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    denom: String,
+    amount: Uint128,
+    recipient: String,
+) -> AppResult {
+    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
+    assert_payouts_open(deps.storage)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    // Stakes, queued refunds, waitlist payments and credit are owed to others and cannot be
+    // recovered. Their running totals are read so that the cost does not grow with the calendar.
+    let mut owed = OUTSTANDING
+        .may_load(deps.storage, &denom)?
+        .unwrap_or_default();
+    let queued_refunds = QUEUED_REFUNDS_TOTAL
+        .may_load(deps.storage, &denom)?
+        .unwrap_or_default();
+    let waitlist = WAITLIST_TOTAL
+        .may_load(deps.storage, &denom)?
+        .unwrap_or_default();
+    owed = checked_add(checked_add(owed, queued_refunds)?, waitlist)?;
+    if denom == config.denom {
+        owed = checked_add(
+            owed,
+            CREDITS_TOTAL.may_load(deps.storage)?.unwrap_or_default(),
+        )?;
+    }
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &denom)?
+        .amount;
+    let available = balance.saturating_sub(owed);
+    if amount > available {
+        return Err(AppError::InsufficientRecoverableFunds { available });
+    }
+
+    // Some bank modules reject sends of zero.
+    let recovery = if amount.is_zero() {
+        vec![]
+    } else {
+        vec![BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin::new(amount.into(), denom)],
+        }]
+    };
+
+    Ok(app.custom_tag_response(
+        Response::default().add_messages(recovery),
+        "recover_funds",
+        vec![
+            ("amount", amount.to_string()),
+            ("recipient", recipient.to_string()),
+        ],
+    ))
+}
+
+fn deposit(deps: DepsMut, info: MessageInfo, app: App) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = must_pay_in(&info, &config.denom)?;
+
+    let credit = CREDITS.update(deps.storage, &info.sender, |credit| -> AppResult<_> {
+        checked_add(credit.unwrap_or_default(), amount)
+    })?;
+    let credits_total = CREDITS_TOTAL.may_load(deps.storage)?.unwrap_or_default();
+    CREDITS_TOTAL.save(deps.storage, &checked_add(credits_total, amount)?)?;
+
+    Ok(app.custom_tag_response(
+        Response::default(),
+        "deposit",
+        vec![
+            ("amount", amount.to_string()),
+            ("credit", credit.to_string()),
+        ],
+    ))
+}
+
+fn withdraw(deps: DepsMut, info: MessageInfo, app: App, amount: Uint128) -> AppResult {
+    assert_payouts_open(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let available = CREDITS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if amount > available {
+        return Err(AppError::InsufficientCredit { available });
+    }
+    let credit = available - amount;
+    if credit.is_zero() {
+        CREDITS.remove(deps.storage, &info.sender);
+    } else {
+        CREDITS.save(deps.storage, &info.sender, &credit)?;
+    }
+    let credits_total = CREDITS_TOTAL.may_load(deps.storage)?.unwrap_or_default();
+    let credits_total = credits_total.checked_sub(amount).map_err(StdError::from)?;
+    CREDITS_TOTAL.save(deps.storage, &credits_total)?;
+
+    // Some bank modules reject sends of zero.
+    let withdrawal = if amount.is_zero() {
+        vec![]
+    } else {
+        vec![BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![Coin::new(amount.into(), config.denom)],
+        }]
+    };
+
+    Ok(app.custom_tag_response(
+        Response::default().add_messages(withdrawal),
+        "withdraw",
+        vec![
+            ("amount", amount.to_string()),
+            ("credit", credit.to_string()),
+        ],
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: App,
+    env: Env,
+    price_per_minute: Option<Uint128>,
+    denom: Option<AssetEntry>,
+    self_gap_minutes: Option<u32>,
+    accepted_denoms: Option<Vec<(AssetEntry, Uint128)>>,
+    denom_decimals: Option<u8>,
+    allowed_weekdays: Option<Vec<Weekday>>,
+    booking_fee: Option<Uint128>,
+    return_claim_deadline: Option<u64>,
+    attestor: Option<String>,
+    allow_partial_outside: Option<bool>,
+    price_outside_hours: Option<bool>,
+    price_source: Option<String>,
+    check_outstanding_balance: Option<bool>,
+    time_granularity_seconds: Option<u32>,
+    allow_admin_booking: Option<bool>,
+    overbooking_allowed: Option<bool>,
+    pegged_price_per_minute: Option<Decimal>,
+    slash_curve: Option<SlashCurve>,
+    cancel_cutoff_secs: Option<u64>,
+    name: Option<String>,
+    description: Option<String>,
+    contact_uri: Option<String>,
+    slash_distribution: Option<Vec<(String, u16)>>,
+    force: bool,
+) -> AppResult {
+    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    let staked_meetings = STAKED_MEETINGS.may_load(deps.storage)?.unwrap_or_default();
+    let pricing_changed = price_per_minute.is_some() || denom.is_some();
+    let mut attrs = vec![];
+    if let Some(price_per_minute) = price_per_minute {
+        config.price_per_minute = price_per_minute;
+        attrs.push(("price_per_minute", price_per_minute.to_string()));
+    }
+    if let Some(unresolved) = denom {
+        let denom = resolve_native_ans_denom(deps.as_ref(), &app, unresolved.clone())?;
+        // Meetings booked before stakes recorded their denom are returned in the config denom.
+        if denom != config.denom && staked_meetings > 0 && !force {
+            return Err(AppError::MeetingsHoldStake {
+                count: staked_meetings,
+            });
+        }
+        config.denom = denom;
+        attrs.push(("denom", unresolved.to_string()));
+    }
+    if pricing_changed {
+        attrs.push(("affected_meetings", staked_meetings.to_string()));
+    }
+    if let Some(self_gap_minutes) = self_gap_minutes {
+        config.self_gap_minutes = self_gap_minutes;
+        attrs.push(("self_gap_minutes", self_gap_minutes.to_string()));
+    }
+    if let Some(accepted_denoms) = accepted_denoms {
+        attrs.push(("accepted_denoms", accepted_denoms.len().to_string()));
+        config.accepted_denoms = resolve_accepted_denoms(deps.as_ref(), &app, accepted_denoms)?;
+    }
+    if let Some(denom_decimals) = denom_decimals {
+        config.denom_decimals = denom_decimals;
+        attrs.push(("denom_decimals", denom_decimals.to_string()));
+    }
+    if let Some(allowed_weekdays) = allowed_weekdays {
+        attrs.push(("allowed_weekdays", format!("{allowed_weekdays:?}")));
+        config.allowed_weekdays = allowed_weekdays;
+    }
+    if let Some(booking_fee) = booking_fee {
+        config.booking_fee = booking_fee;
+        attrs.push(("booking_fee", booking_fee.to_string()));
+    }
+    if let Some(return_claim_deadline) = return_claim_deadline {
+        config.return_claim_deadline = Some(return_claim_deadline).filter(|seconds| *seconds > 0);
+        attrs.push(("return_claim_deadline", return_claim_deadline.to_string()));
+    }
+    if let Some(attestor) = attestor {
+        config.attestor = validate_optional_addr(deps.api, &attestor)?;
+        attrs.push(("attestor", attestor));
+    }
+    if let Some(allow_partial_outside) = allow_partial_outside {
+        config.allow_partial_outside = allow_partial_outside;
+        attrs.push(("allow_partial_outside", allow_partial_outside.to_string()));
+    }
+    if let Some(price_outside_hours) = price_outside_hours {
+        config.price_outside_hours = price_outside_hours;
+        attrs.push(("price_outside_hours", price_outside_hours.to_string()));
+    }
+    if let Some(price_source) = price_source {
+        config.price_source = validate_optional_addr(deps.api, &price_source)?;
+        attrs.push(("price_source", price_source));
+    }
+    if let Some(check_outstanding_balance) = check_outstanding_balance {
+        config.check_outstanding_balance = check_outstanding_balance;
+        attrs.push((
+            "check_outstanding_balance",
+            check_outstanding_balance.to_string(),
+        ));
+    }
+    if let Some(granularity_seconds) = time_granularity_seconds {
+        validate_granularity(granularity_seconds)?;
+        let misaligned = count_misaligned(
+            deps.storage,
+            deps.api,
+            block_time(&env)?,
+            granularity_seconds,
+        )?;
+        if misaligned > 0 && !force {
+            return Err(AppError::MeetingsNotAligned {
+                count: misaligned,
+                granularity_seconds,
+            });
+        }
+        config.time_granularity_seconds = granularity_seconds;
+        attrs.push(("time_granularity_seconds", granularity_seconds.to_string()));
+        attrs.push(("misaligned_meetings", misaligned.to_string()));
+    }
+    if let Some(allow_admin_booking) = allow_admin_booking {
+        config.allow_admin_booking = allow_admin_booking;
+        attrs.push(("allow_admin_booking", allow_admin_booking.to_string()));
+    }
+    if let Some(overbooking_allowed) = overbooking_allowed {
+        config.overbooking_allowed = overbooking_allowed;
+        attrs.push(("overbooking_allowed", overbooking_allowed.to_string()));
+    }
+    if let Some(pegged_price_per_minute) = pegged_price_per_minute {
+        config.pegged_price_per_minute =
+            Some(pegged_price_per_minute).filter(|price| !price.is_zero());
+        attrs.push((
+            "pegged_price_per_minute",
+            pegged_price_per_minute.to_string(),
+        ));
+    }
+    if let Some(slash_curve) = slash_curve {
+        validate_slash_curve(&slash_curve)?;
+        attrs.push(("slash_curve", format!("{slash_curve:?}")));
+        config.slash_curve = slash_curve;
+    }
+    if let Some(cancel_cutoff_secs) = cancel_cutoff_secs {
+        config.cancel_cutoff_secs = cancel_cutoff_secs;
+        attrs.push(("cancel_cutoff_secs", cancel_cutoff_secs.to_string()));
+    }
+    if let Some(name) = name {
+        config.name = validate_profile_field("name", name, MAX_NAME_LENGTH)?;
+        attrs.push(("name", config.name.clone().unwrap_or_default()));
+    }
+    if let Some(description) = description {
+        config.description =
+            validate_profile_field("description", description, MAX_DESCRIPTION_LENGTH)?;
+        attrs.push((
+            "description",
+            config.description.clone().unwrap_or_default(),
+        ));
+    }
+    if let Some(contact_uri) = contact_uri {
+        config.contact_uri =
+            validate_profile_field("contact_uri", contact_uri, MAX_CONTACT_URI_LENGTH)?;
+        attrs.push((
+            "contact_uri",
+            config.contact_uri.clone().unwrap_or_default(),
+        ));
+    }
+    if let Some(slash_distribution) = slash_distribution {
+        attrs.push(("slash_distribution", slash_distribution.len().to_string()));
+        config.slash_distribution = validate_slash_distribution(deps.api, slash_distribution)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+    let updated = Event::from(ConfigUpdatedEvent {
+        updated_by: &info.sender,
+        // The counts of affected meetings are not fields of the config.
+        changed: attrs
+            .iter()
+            .map(|(key, _)| *key)
+            .filter(|key| !matches!(*key, "affected_meetings" | "misaligned_meetings"))
+            .collect(),
+        config: &config,
+    });
+    Ok(app.custom_tag_response(Response::new().add_event(updated), "update_config", attrs))
+}
+
+/// Validates an address set through `UpdateConfig`, where an empty address clears it.
+fn validate_optional_addr(api: &dyn Api, address: &str) -> StdResult<Option<Addr>> {
+    match address {
+        "" => Ok(None),
+        address => api.addr_validate(address).map(Some),
+    }
+}
+
+/// Returns the number of meetings starting after `now` whose start or end is not aligned to
+/// `granularity_seconds`.
+fn count_misaligned(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    now: i64,
+    granularity_seconds: u32,
+) -> AppResult<u32> {
+    let granularity_seconds = i64::from(granularity_seconds);
+    // Meetings starting after `now` are on days whose local midnight is less than a day before.
+    let min = Bound::exclusive(now - 86_400);
+    let mut misaligned: u32 = 0;
+    for day in CALENDAR.range(storage, api, Some(min), None, Order::Ascending) {
+        let (day_datetime, meetings) = day?;
+        for meeting in meetings {
+            // Day keys are local midnights, so these are seconds since local midnight.
+            let start = meeting.start_time - day_datetime;
+            let end = meeting.end_time - day_datetime;
+            if meeting.start_time > now
+                && (start % granularity_seconds != 0 || end % granularity_seconds != 0)
+            {
+                misaligned = misaligned.saturating_add(1);
+            }
+        }
+    }
+    Ok(misaligned)
+}
+
+/// Checks that `sender` is the attestor or the admin, who can both attest attendance and
+/// resolve stakes.
+fn assert_attestor(deps: Deps, app: &App, config: &Config, sender: &Addr) -> AppResult<()> {
+    if config.attestor.as_ref() == Some(sender) || app.admin.is_admin(deps, sender)? {
+        Ok(())
+    } else {
+        Err(AppError::NotAttestor {})
+    }
+}
+
+/// Books the waitlist entries of `day_datetime` that fit into `meetings`, oldest entry first.
+/// Entries whose slot is still taken or held, that already started, or that a direct booking
+/// would now be refused for, stay queued. Returns the number of booked entries and the booking
+/// fees to pay out to the admin.
+fn promote_waitlist(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    admin: Option<&Addr>,
+    day_datetime: i64,
+    meetings: &mut Vec<Meeting>,
+) -> AppResult<(u32, Vec<BankMsg>)> {
+    let now = block_time(env)?;
+    let entries = WAITLIST
+        .prefix(day_datetime)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut promoted: u32 = 0;
+    let mut fee_messages = vec![];
+    for (entry_id, entry) in entries {
+        // The checks of a direct booking, as the config or the admin may have changed since
+        // the entry joined.
+        if validate_window(config, entry.start_time, entry.end_time, now).is_err()
+            || find_conflict(meetings, entry.start_time, entry.end_time).is_some()
+            || violates_self_gap(
+                meetings,
+                &entry.requester,
+                entry.start_time,
+                entry.end_time,
+                config.self_gap_minutes,
+            )
+        {
+            continue;
+        }
+        let Ok(self_booked) = is_self_booking(config, admin, &entry.requester) else {
+            continue;
+        };
+        match assert_not_held(storage, env, entry.start_time, entry.end_time) {
+            Err(AppError::SlotHeld {}) => continue,
+            result => result?,
+        }
+        WAITLIST.remove(storage, (day_datetime, entry_id));
+        decrease_total(
+            storage,
+            &WAITLIST_TOTAL,
+            &entry.stake.denom,
+            checked_add(entry.stake.amount, entry.booking_fee)?,
+        )?;
+
+        // The fee is paid out to the admin like for a direct booking, or stays with the stake
+        // once the admin is renounced.
+        let amount_staked = match admin {
+            Some(admin) if !entry.booking_fee.is_zero() => {
+                fee_messages.push(BankMsg::Send {
+                    to_address: admin.to_string(),
+                    amount: vec![Coin::new(entry.booking_fee.into(), &entry.stake.denom)],
+                });
+                entry.stake.amount
+            }
+            _ => checked_add(entry.stake.amount, entry.booking_fee)?,
+        };
+        meetings.push(Meeting {
+            start_time: entry.start_time,
+            end_time: entry.end_time,
+            requester: entry.requester,
+            amount_staked,
+            refund_splits: None,
+            denom: Some(entry.stake.denom),
+            reminder_minutes: None,
+            free: amount_staked.is_zero(),
+            attended: None,
+            disputed: false,
+            checked_in: false,
+            ended_early_at: None,
+            self_booked,
+            resolution: None,
+            cancel_deadline: Some(config.cancel_deadline(entry.start_time)),
+            encrypted_payload: None,
+        });
+        promoted += 1;
+    }
+    Ok((promoted, fee_messages))
+}
+
+/// Loads the meetings of the day keyed `day_datetime` and returns them with the position of the
+/// meeting at `meeting_index`, after checking that the key is a local midnight and that the
+/// meeting starts on that day, as a key matching another day must not reach its meetings.
+fn load_day_meeting(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    config: &Config,
+    day_datetime: Int64,
+    meeting_index: u32,
+) -> AppResult<(Vec<Meeting>, usize)> {
+    validate_timestamp(day_datetime)?;
+    let timezone = validate_utc_offset(config.utc_offset)?;
+    validate_day_key(timezone, day_datetime.i64())?;
+    let meetings = CALENDAR.may_load(storage, api, day_datetime.i64())?.ok_or(
+        AppError::NoMeetingsAtGivenDayDateTime {
+            day_datetime: day_datetime.i64(),
+        },
+    )?;
+    let position = meeting_position(meeting_index)?;
+    let meeting = meetings
+        .get(position)
+        .ok_or(AppError::MeetingDoesNotExist {
+            day_datetime: day_datetime.i64(),
+            meeting_index,
+        })?;
+    validate_meeting_day(timezone, day_datetime.i64(), meeting)?;
+    Ok((meetings, position))
+}
+
+/// Returns the index of the meeting of `requester` starting at `start_time` on the given day.
+/// The pair is unique as the meetings of a requester cannot overlap.
+fn meeting_index_by_key(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    day_datetime: Int64,
+    requester: &Addr,
+    start_time: Int64,
+) -> AppResult<u32> {
+    let meetings = CALENDAR.may_load(storage, api, day_datetime.i64())?.ok_or(
+        AppError::NoMeetingsAtGivenDayDateTime {
+            day_datetime: day_datetime.i64(),
+        },
+    )?;
+    let position = meetings
+        .iter()
+        .position(|meeting| {
+            &meeting.requester == requester && meeting.start_time == start_time.i64()
+        })
+        .ok_or(AppError::MeetingNotFoundByKey {
+            day_datetime: day_datetime.i64(),
+            requester: requester.to_string(),
+            start_time: start_time.i64(),
+        })?;
+    meeting_index_at(position)
+}
+
+/// Returns whether `requester` booking a meeting is the admin booking their own calendar, which
+/// fails unless the config allows it.
+fn is_self_booking(config: &Config, admin: Option<&Addr>, requester: &Addr) -> AppResult<bool> {
+    let self_booked = admin == Some(requester);
+    if self_booked && !config.allow_admin_booking {
+        return Err(AppError::AdminBookingNotAllowed {});
+    }
+    Ok(self_booked)
+}
+
+/// Fails while payouts are frozen.
+fn assert_payouts_open(storage: &dyn Storage) -> AppResult<()> {
+    if PAYOUTS_FROZEN.may_load(storage)?.unwrap_or_default() {
+        return Err(AppError::PayoutsFrozen {});
+    }
+    Ok(())
+}
+
+/// Fails when the slot between `start_time` and `end_time` overlaps an unexpired hold. Expired
+/// holds found along the way are removed.
+fn assert_not_held(
+    storage: &mut dyn Storage,
+    env: &Env,
+    start_time: i64,
+    end_time: i64,
+) -> AppResult<()> {
+    prune_expired_holds(storage, env)?;
+    check_not_held(storage, env, start_time, end_time)
+}
+
+/// Fails when the slot between `start_time` and `end_time` overlaps an unexpired hold.
+fn check_not_held(
+    storage: &dyn Storage,
+    env: &Env,
+    start_time: i64,
+    end_time: i64,
+) -> AppResult<()> {
+    for hold in HOLDS.range(storage, None, None, Order::Ascending) {
+        let (_, hold) = hold?;
+        if env.block.time <= hold.expires_at
+            && start_time < hold.end_time
+            && hold.start_time < end_time
+        {
+            return Err(AppError::SlotHeld {});
+        }
+    }
+    Ok(())
+}
+
+/// Removes the holds that expired.
+fn prune_expired_holds(storage: &mut dyn Storage, env: &Env) -> AppResult<()> {
+    let expired = HOLDS
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|hold| match hold {
+            Ok((hold_id, hold)) if env.block.time > hold.expires_at => Some(Ok(hold_id)),
+            Ok(_) => None,
+            Err(error) => Some(Err(error)),
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    for hold_id in expired {
+        HOLDS.remove(storage, hold_id);
+    }
+    Ok(())
+}
+
+/// Computes the stake required to book a meeting between the given unix timestamps at the given
+/// price per minute.
+pub fn expected_stake(
+    price_per_minute: Uint128,
+    meeting_start_time: i64,
+    meeting_end_time: i64,
+) -> AppResult<Uint128> {
+    let duration_in_minutes =
+        Uint128::from(duration_in_minutes(meeting_start_time, meeting_end_time)?);
+
+    duration_in_minutes
+        .checked_mul(price_per_minute)
+        .map_err(|_| AppError::Overflow {
+            lhs: duration_in_minutes,
+            rhs: price_per_minute,
+        })
+}
+
+/// Computes the stake in `denom` required to book a meeting between the given unix timestamps.
+/// Stakes pegged to the quote currency of the price source are converted at its current price,
+/// rounded up, and are otherwise priced at `price_per_minute`.
+pub fn required_stake(
+    querier: &QuerierWrapper,
+    config: &Config,
+    denom: &str,
+    price_per_minute: Uint128,
+    meeting_start_time: i64,
+    meeting_end_time: i64,
+) -> AppResult<Uint128> {
+    let Some(pegged_price_per_minute) = config.pegged_price_per_minute else {
+        return expected_stake(price_per_minute, meeting_start_time, meeting_end_time);
+    };
+    let price_source = config
+        .price_source
+        .as_ref()
+        .ok_or(AppError::PriceSourceNotSet {})?;
+    let unavailable = |reason: String| AppError::PriceSourceUnavailable {
+        denom: denom.to_string(),
+        reason,
+    };
+    let price: OraclePriceResponse = querier
+        .query_wasm_smart(
+            price_source,
+            &OracleQueryMsg::Price {
+                denom: denom.to_string(),
+            },
+        )
+        .map_err(|error| unavailable(error.to_string()))?;
+    if price.price.is_zero() {
+        return Err(unavailable("price is zero".to_string()));
+    }
+
+    let duration_in_minutes =
+        Uint128::from(duration_in_minutes(meeting_start_time, meeting_end_time)?);
+    // Both prices have the same decimal places, so dividing their atomics gives whole units.
+    let value = duration_in_minutes
+        .checked_mul(pegged_price_per_minute.atomics())
+        .map_err(|_| AppError::Overflow {
+            lhs: duration_in_minutes,
+            rhs: pegged_price_per_minute.atomics(),
+        })?;
+    let unit_price = price.price.atomics();
+    let stake = value / unit_price;
+    // Rounded up so that the stake is worth at least its pegged value.
+    if (value % unit_price).is_zero() {
+        Ok(stake)
+    } else {
+        Ok(stake + Uint128::one())
+    }
+}
+
+/// The error for a meeting overlapping `conflicting_meeting`.
+fn conflict_error(config: &Config, conflicting_meeting: &Meeting) -> AppError {
+    // Stored meetings were validated on booking, so their times are in range at the UTC offset
+    // of the config.
+    let local = |timestamp: i64| {
+        validate_utc_offset(config.utc_offset)
+            .and_then(|timezone| format_local(timezone, timestamp))
+            .unwrap_or_default()
+    };
+    AppError::MeetingConflictExists {
+        existing_start: conflicting_meeting.start_time,
+        existing_end: conflicting_meeting.end_time,
+        existing_start_local: local(conflicting_meeting.start_time),
+        existing_end_local: local(conflicting_meeting.end_time),
+    }
+}
+
+/// Adds two amounts, failing instead of panicking on overflow.
+fn checked_add(lhs: Uint128, rhs: Uint128) -> AppResult<Uint128> {
+    lhs.checked_add(rhs)
+        .map_err(|_| AppError::Overflow { lhs, rhs })
+}
+
+/// Bank messages returning `amount` to the requester of `meeting`, split between the recipients
+/// of its refund splits when present.
+fn refund_messages(meeting: &Meeting, amount: Uint128, denom: &str) -> Vec<BankMsg> {
+    let recipients = match &meeting.refund_splits {
+        Some(refund_splits) => split_amount(amount, refund_splits),
+        None => vec![(meeting.requester.clone(), amount)],
+    };
+    recipients
+        .into_iter()
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(recipient, amount)| BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin::new(amount.into(), denom)],
+        })
+        .collect()
+}
+
+/// Bank messages paying out `amount` slashed from a stake, split between the recipients of the
+/// slash distribution when one is set and sent to the admin otherwise.
+fn slash_messages(config: &Config, admin: &Addr, amount: Uint128, denom: &str) -> Vec<BankMsg> {
+    let recipients = if config.slash_distribution.is_empty() {
+        vec![(admin.clone(), amount)]
+    } else {
+        split_amount(amount, &config.slash_distribution)
+    };
+    // Some bank modules reject sends of zero, so an empty leg gets no message.
+    recipients
+        .into_iter()
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(recipient, amount)| BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin::new(amount.into(), denom)],
+        })
+        .collect()
+}
+
+/// Saves the meetings of a day after `removed` was taken out of it. Only the stake of `removed`
+/// leaves the count of meetings holding stake and the outstanding stake, so unlike [`save_day`]
+/// the day and the config are not read again.
+fn save_day_without(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    config: &Config,
+    day_datetime: i64,
+    meetings: &[Meeting],
+    removed: &Meeting,
+) -> StdResult<()> {
+    let removed = std::slice::from_ref(removed);
+    let count = count_staked(removed)?;
+    if count > 0 {
+        let staked_meetings = STAKED_MEETINGS.may_load(storage)?.unwrap_or_default();
+        STAKED_MEETINGS.save(storage, &recount(staked_meetings, count, 0)?)?;
+    }
+    for (denom, stake) in stakes_by_denom(config, removed)? {
+        if !stake.is_zero() {
+            let outstanding = OUTSTANDING.may_load(storage, &denom)?.unwrap_or_default();
+            OUTSTANDING.save(storage, &denom, &outstanding.checked_sub(stake)?)?;
+        }
+    }
+
+    if meetings.is_empty() {
+        CALENDAR.remove(storage, day_datetime);
+        Ok(())
+    } else {
+        CALENDAR.save(storage, api, day_datetime, meetings)
+    }
+}
+
+/// Saves the meetings of a day, removing the day instead when none are left so that empty days
+/// do not take up storage. Every write changing stakes goes through here, or through
+/// [`save_day_without`] when a single meeting is removed, to keep the count of meetings holding
+/// stake up to date. `previous` are the meetings of the day as stored before, which callers
+/// have already loaded.
+fn save_day(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    config: &Config,
+    day_datetime: i64,
+    previous: &[Meeting],
+    meetings: &[Meeting],
+) -> StdResult<()> {
+    let (removed, added) = (count_staked(previous)?, count_staked(meetings)?);
+    if removed != added {
+        let count = STAKED_MEETINGS.may_load(storage)?.unwrap_or_default();
+        STAKED_MEETINGS.save(storage, &recount(count, removed, added)?)?;
+    }
+    let (removed, added) = (
+        stakes_by_denom(config, previous)?,
+        stakes_by_denom(config, meetings)?,
+    );
+    for denom in removed.keys().chain(added.keys()).collect::<BTreeSet<_>>() {
+        let (removed, added) = (
+            removed.get(denom).copied().unwrap_or_default(),
+            added.get(denom).copied().unwrap_or_default(),
+        );
+        if removed != added {
+            let outstanding = OUTSTANDING.may_load(storage, denom)?.unwrap_or_default();
+            OUTSTANDING.save(
+                storage,
+                denom,
+                &outstanding.checked_sub(removed)?.checked_add(added)?,
+            )?;
+        }
+    }
+
+    if meetings.is_empty() {
+        CALENDAR.remove(storage, day_datetime);
+        Ok(())
+    } else {
+        CALENDAR.save(storage, api, day_datetime, meetings)
+    }
+}
+
+/// Returns the count of meetings holding stake once `removed` of them left and `added` joined,
+/// failing instead of going below zero when the count is out of step with the calendar.
+fn recount(count: u32, removed: u32, added: u32) -> StdResult<u32> {
+    let count = count
+        .checked_sub(removed)
+        .ok_or_else(|| OverflowError::new(OverflowOperation::Sub, count, removed))?;
+    count
+        .checked_add(added)
+        .ok_or_else(|| OverflowError::new(OverflowOperation::Add, count, added).into())
+}
+
+/// Returns the total stake of the given unresolved meetings per denom.
+pub fn stakes_by_denom(
+    config: &Config,
+    meetings: &[Meeting],
+) -> StdResult<BTreeMap<String, Uint128>> {
+    let mut stakes: BTreeMap<String, Uint128> = BTreeMap::new();
+    for meeting in meetings
+        .iter()
+        .filter(|meeting| meeting.resolution.is_none())
+    {
+        let stake = stakes
+            .entry(meeting.stake_denom(config).to_string())
+            .or_default();
+        *stake = stake.checked_add(meeting.amount_staked)?;
+    }
+    Ok(stakes)
+}
+
+/// Returns the number of unresolved meetings holding stake.
+pub fn count_staked(meetings: &[Meeting]) -> StdResult<u32> {
+    let count = meetings
+        .iter()
+        .filter(|meeting| meeting.resolution.is_none() && !meeting.amount_staked.is_zero())
+        .count();
+    u32::try_from(count)
+        .map_err(|_| ConversionOverflowError::new("usize", "u32", count.to_string()).into())
+}
+
+/// Keeps `meeting`, removed from the day at `day_datetime` once its stake was resolved, in the
+/// history of resolved meetings.
+fn archive_resolved(
+    storage: &mut dyn Storage,
+    day_datetime: i64,
+    meeting: &Meeting,
+) -> StdResult<()> {
+    let id = RESOLVED_MEETINGS_SEQ.may_load(storage)?.unwrap_or_default();
+    RESOLVED_MEETINGS.save(storage, (day_datetime, id), meeting)?;
+    RESOLVED_MEETINGS_SEQ.save(storage, &(id + 1))
+}
+
+/// Queues the returned stake sent by `refunds` to be claimed by its recipients when the config
+/// has a claim deadline, returning the messages that still have to be sent right away.
+fn queue_refunds(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    refunds: Vec<BankMsg>,
+) -> StdResult<Vec<BankMsg>> {
+    let Some(return_claim_deadline) = config.return_claim_deadline else {
+        return Ok(refunds);
+    };
+    let claim_deadline = env.block.time.plus_seconds(return_claim_deadline);
+    let mut refund_id = QUEUED_REFUNDS_SEQ.may_load(storage)?.unwrap_or_default();
+    for refund in refunds {
+        if let BankMsg::Send { to_address, amount } = refund {
+            for amount in amount {
+                increase_total(storage, &QUEUED_REFUNDS_TOTAL, &amount.denom, amount.amount)?;
+                QUEUED_REFUNDS.save(
+                    storage,
+                    refund_id,
+                    &QueuedRefund {
+                        recipient: Addr::unchecked(&to_address),
+                        amount,
+                        claim_deadline,
+                    },
+                )?;
+                refund_id += 1;
+            }
+        }
+    }
+    QUEUED_REFUNDS_SEQ.save(storage, &refund_id)?;
+    Ok(vec![])
+}
+
+/// Adds `amount` to the running total of `denom` in `totals`.
+fn increase_total<'a>(
+    storage: &mut dyn Storage,
+    totals: &Map<'a, &'a str, Uint128>,
+    denom: &'a str,
+    amount: Uint128,
+) -> StdResult<()> {
+    let total = totals.may_load(storage, denom)?.unwrap_or_default();
+    totals.save(storage, denom, &total.checked_add(amount)?)
+}
+
+/// Takes `amount` off the running total of `denom` in `totals`, removing the total once nothing
+/// is left of it.
+fn decrease_total<'a>(
+    storage: &mut dyn Storage,
+    totals: &Map<'a, &'a str, Uint128>,
+    denom: &'a str,
+    amount: Uint128,
+) -> StdResult<()> {
+    let total = totals
+        .may_load(storage, denom)?
+        .unwrap_or_default()
+        .checked_sub(amount)?;
+    if total.is_zero() {
+        totals.remove(storage, denom);
+        Ok(())
     } else {
-        Err(AppError::InvalidTime {})
+        totals.save(storage, denom, &total)
+    }
+}
+
+/// Appends an entry to the audit log for every coin sent by `transfers`.
+fn log_stake_movements(
+    storage: &mut dyn Storage,
+    env: &Env,
+    actor: &Addr,
+    movement: StakeMovement,
+    transfers: &[BankMsg],
+) -> StdResult<()> {
+    let mut seq = AUDIT_LOG_SEQ.may_load(storage)?.unwrap_or_default();
+    for transfer in transfers {
+        if let BankMsg::Send { to_address, amount } = transfer {
+            for coin in amount {
+                AUDIT_LOG.save(
+                    storage,
+                    seq,
+                    &StakeEvent {
+                        movement: movement.clone(),
+                        actor: actor.clone(),
+                        recipient: Addr::unchecked(to_address),
+                        amount: coin.amount,
+                        denom: coin.denom.clone(),
+                        timestamp: env.block.time,
+                    },
+                )?;
+                seq += 1;
+            }
+        }
     }
+    AUDIT_LOG_SEQ.save(storage, &seq)
+}
+
+/// Resolves every accepted denom to its native denom, failing on the first entry that is not
+/// registered in the name service or is not native.
+pub fn resolve_accepted_denoms(
+    deps: Deps,
+    app: &App,
+    accepted_denoms: Vec<(AssetEntry, Uint128)>,
+) -> AppResult<Vec<(String, Uint128)>> {
+    let ans_host = app.ans_host(deps)?;
+    accepted_denoms
+        .into_iter()
+        .map(|(entry, price_per_minute)| {
+            let resolved = entry.resolve(&deps.querier, &ans_host).map_err(|_| {
+                AppError::UnresolvableAcceptedDenom {
+                    entry: entry.to_string(),
+                }
+            })?;
+            match resolved {
+                AssetInfoBase::Native(denom) => Ok((denom, price_per_minute)),
+                _ => Err(AppError::NonNativeAcceptedDenom {
+                    entry: entry.to_string(),
+                }),
+            }
+        })
+        .collect()
+}
+
+pub fn resolve_native_ans_denom(deps: Deps, app: &App, denom: AssetEntry) -> AppResult<String> {
+    let ans_host = app.ans_host(deps)?;
+    let resolved_denom = denom.resolve(&deps.querier, &ans_host)?;
+    let denom = match resolved_denom {
+        AssetInfoBase::Native(denom) => Ok(denom),
+        _ => Err(StdError::generic_err("Non-native denom not supported")),
+    }?;
+    Ok(denom)
 }
@@ -1,19 +1,52 @@
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{DepsMut, Env, Event, MessageInfo, Response, Uint128};
 
-use crate::contract::{App, AppResult};
+use crate::contract::{
+    App, AppResult, MAX_CONTACT_URI_LENGTH, MAX_DESCRIPTION_LENGTH, MAX_NAME_LENGTH,
+};
+use crate::events::ConfigUpdatedEvent;
 use crate::msg::AppInstantiateMsg;
-use crate::state::{Config, CONFIG};
+use crate::state::{
+    Config, SlashCurve, COMPACT_MEETINGS, CONFIG, CREDITS_TOTAL, LOCAL_DAY_KEYS, STAKED_MEETINGS,
+};
+use crate::time::validate_utc_offset;
+use crate::validation::{validate_calendar_hours, validate_profile_field};
 
-use super::execute::resolve_native_ans_denom;
+use super::execute::{resolve_accepted_denoms, resolve_native_ans_denom};
 
 pub fn instantiate_handler(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     app: App,
     msg: AppInstantiateMsg,
 ) -> AppResult {
+    validate_utc_offset(msg.utc_offset)?;
+    validate_calendar_hours(&msg.start_time, &msg.end_time)?;
     let denom = resolve_native_ans_denom(deps.as_ref(), &app, msg.denom)?;
+    let accepted_denoms = resolve_accepted_denoms(deps.as_ref(), &app, msg.accepted_denoms)?;
+    let mut changed = vec![
+        "price_per_minute",
+        "denom",
+        "utc_offset",
+        "start_time",
+        "end_time",
+        "self_gap_minutes",
+        "accepted_denoms",
+    ];
+    let mut profile_field =
+        |field: &'static str, value: Option<String>, max_length| -> AppResult<Option<String>> {
+            let value = value
+                .map(|value| validate_profile_field(field, value, max_length))
+                .transpose()?
+                .flatten();
+            if value.is_some() {
+                changed.push(field);
+            }
+            Ok(value)
+        };
+    let name = profile_field("name", msg.name, MAX_NAME_LENGTH)?;
+    let description = profile_field("description", msg.description, MAX_DESCRIPTION_LENGTH)?;
+    let contact_uri = profile_field("contact_uri", msg.contact_uri, MAX_CONTACT_URI_LENGTH)?;
 
     let config: Config = Config {
         price_per_minute: msg.price_per_minute,
@@ -21,9 +54,38 @@ pub fn instantiate_handler(
         utc_offset: msg.utc_offset,
         start_time: msg.start_time,
         end_time: msg.end_time,
+        self_gap_minutes: msg.self_gap_minutes,
+        accepted_denoms,
+        denom_decimals: 0,
+        allowed_weekdays: vec![],
+        booking_fee: Uint128::zero(),
+        return_claim_deadline: None,
+        attestor: None,
+        allow_partial_outside: false,
+        price_outside_hours: false,
+        price_source: None,
+        check_outstanding_balance: false,
+        time_granularity_seconds: 0,
+        allow_admin_booking: false,
+        overbooking_allowed: false,
+        pegged_price_per_minute: None,
+        slash_curve: SlashCurve::Linear,
+        cancel_cutoff_secs: 0,
+        name,
+        description,
+        contact_uri,
+        slash_distribution: vec![],
     };
 
     CONFIG.save(deps.storage, &config)?;
+    LOCAL_DAY_KEYS.save(deps.storage, &true)?;
+    COMPACT_MEETINGS.save(deps.storage, &true)?;
+    STAKED_MEETINGS.save(deps.storage, &0)?;
+    CREDITS_TOTAL.save(deps.storage, &Uint128::zero())?;
 
-    Ok(Response::new())
+    Ok(Response::new().add_event(Event::from(ConfigUpdatedEvent {
+        updated_by: &info.sender,
+        changed,
+        config: &config,
+    })))
 }
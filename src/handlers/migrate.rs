@@ -1,10 +1,144 @@
-use crate::contract::{App, AppResult};
+use crate::contract::{App, AppResult, MAX_BATCH};
+use crate::migration::{compact_meetings, rebucket_days, rekey_waitlist};
 use crate::msg::AppMigrateMsg;
+use crate::state::{
+    Meeting, CALENDAR, COMPACT_MEETINGS, CONFIG, CREDITS, CREDITS_TOTAL, LOCAL_DAY_KEYS,
+    OUTSTANDING, QUEUED_REFUNDS, QUEUED_REFUNDS_TOTAL, REBUCKET_CURSOR, STAKED_MEETINGS, WAITLIST,
+    WAITLIST_TOTAL,
+};
+use crate::time::validate_utc_offset;
 use abstract_sdk::AbstractResponse;
-use cosmwasm_std::{DepsMut, Env, Response};
+use cosmwasm_std::{DepsMut, Env, Order, Response, StdError, StdResult, Uint128};
+use std::collections::BTreeMap;
+
+use super::execute::{count_staked, stakes_by_denom};
 
 /// Handle the app migrate msg
 /// The top-level Abstract app does version checking and dispatches to this handler
-pub fn migrate_handler(_deps: DepsMut, _env: Env, app: App, _msg: AppMigrateMsg) -> AppResult {
-    Ok(app.tag_response(Response::default(), "migrate"))
+///
+/// Calendars created before meetings were stored compactly get every day rewritten as
+/// `StoredMeeting`s once, before the other migrations read them.
+///
+/// Calendars created before day keys were local midnights keyed each day by its local date
+/// read as a UTC date, `utc_offset` seconds after the local midnight. Those days are moved to
+/// their local midnight key once.
+///
+/// Calendars created before the meetings holding stake were counted get their count and the
+/// outstanding stake per denom once.
+///
+/// Calendars created before the funds owed to others were totalled get the total credit and
+/// the total of the queued refunds and waitlist payments per denom once.
+///
+/// Waitlists stored before entries were keyed by their day get every entry moved under its day
+/// once.
+///
+/// `RebucketDays` then moves meetings keyed under another day than their start in bounded
+/// steps, see [`rebucket_days`].
+pub fn migrate_handler(deps: DepsMut, _env: Env, app: App, msg: AppMigrateMsg) -> AppResult {
+    let mut days_compacted: u32 = 0;
+    if !COMPACT_MEETINGS.may_load(deps.storage)?.unwrap_or_default() {
+        days_compacted = compact_meetings(deps.storage, deps.api)?;
+        COMPACT_MEETINGS.save(deps.storage, &true)?;
+    }
+    let mut days_rekeyed: usize = 0;
+    if !LOCAL_DAY_KEYS.may_load(deps.storage)?.unwrap_or_default() {
+        let utc_offset = i64::from(CONFIG.load(deps.storage)?.utc_offset);
+        if utc_offset != 0 {
+            let days: Vec<(i64, Vec<Meeting>)> = CALENDAR
+                .range(deps.storage, deps.api, None, None, Order::Ascending)
+                .collect::<StdResult<_>>()?;
+            for (day_datetime, _) in &days {
+                CALENDAR.remove(deps.storage, *day_datetime);
+            }
+            for (day_datetime, meetings) in &days {
+                CALENDAR.save(deps.storage, deps.api, day_datetime - utc_offset, meetings)?;
+            }
+            days_rekeyed = days.len();
+        }
+        LOCAL_DAY_KEYS.save(deps.storage, &true)?;
+    }
+    let waitlist_entries_rekeyed = rekey_waitlist(deps.storage)?;
+    if STAKED_MEETINGS.may_load(deps.storage)?.is_none() {
+        let config = CONFIG.load(deps.storage)?;
+        let mut staked_meetings: u32 = 0;
+        let mut outstanding: BTreeMap<String, Uint128> = BTreeMap::new();
+        for day in CALENDAR.range(deps.storage, deps.api, None, None, Order::Ascending) {
+            let (_, meetings) = day?;
+            staked_meetings = staked_meetings.saturating_add(count_staked(&meetings)?);
+            for (denom, stake) in stakes_by_denom(&config, &meetings)? {
+                let total = outstanding.entry(denom).or_default();
+                *total = total.checked_add(stake).map_err(StdError::from)?;
+            }
+        }
+        STAKED_MEETINGS.save(deps.storage, &staked_meetings)?;
+        for (denom, total) in outstanding {
+            OUTSTANDING.save(deps.storage, &denom, &total)?;
+        }
+    }
+    if CREDITS_TOTAL.may_load(deps.storage)?.is_none() {
+        let mut credits_total = Uint128::zero();
+        for credit in CREDITS.range(deps.storage, None, None, Order::Ascending) {
+            credits_total = credits_total
+                .checked_add(credit?.1)
+                .map_err(StdError::from)?;
+        }
+        CREDITS_TOTAL.save(deps.storage, &credits_total)?;
+        let mut queued_refunds: BTreeMap<String, Uint128> = BTreeMap::new();
+        for refund in QUEUED_REFUNDS.range(deps.storage, None, None, Order::Ascending) {
+            let (_, refund) = refund?;
+            let total = queued_refunds.entry(refund.amount.denom).or_default();
+            *total = total
+                .checked_add(refund.amount.amount)
+                .map_err(StdError::from)?;
+        }
+        for (denom, total) in queued_refunds {
+            QUEUED_REFUNDS_TOTAL.save(deps.storage, &denom, &total)?;
+        }
+        let mut waitlist: BTreeMap<String, Uint128> = BTreeMap::new();
+        for entry in WAITLIST.range(deps.storage, None, None, Order::Ascending) {
+            let (_, entry) = entry?;
+            let total = waitlist.entry(entry.stake.denom).or_default();
+            *total = total
+                .checked_add(entry.stake.amount)
+                .and_then(|total| total.checked_add(entry.booking_fee))
+                .map_err(StdError::from)?;
+        }
+        for (denom, total) in waitlist {
+            WAITLIST_TOTAL.save(deps.storage, &denom, &total)?;
+        }
+    }
+
+    let mut attributes = vec![
+        ("days_compacted", days_compacted.to_string()),
+        ("days_rekeyed", days_rekeyed.to_string()),
+        (
+            "waitlist_entries_rekeyed",
+            waitlist_entries_rekeyed.to_string(),
+        ),
+    ];
+    if let AppMigrateMsg::RebucketDays { limit } = msg {
+        let timezone = validate_utc_offset(CONFIG.load(deps.storage)?.utc_offset)?;
+        let start_after = REBUCKET_CURSOR.may_load(deps.storage)?;
+        let rebucketed = rebucket_days(
+            deps.storage,
+            deps.api,
+            timezone,
+            start_after,
+            limit.min(MAX_BATCH as u32),
+        )?;
+        match rebucketed.resume_after {
+            Some(day_datetime) => REBUCKET_CURSOR.save(deps.storage, &day_datetime)?,
+            None => REBUCKET_CURSOR.remove(deps.storage),
+        }
+        attributes.extend([
+            ("days_checked", rebucketed.days_checked.to_string()),
+            ("meetings_moved", rebucketed.meetings_moved.to_string()),
+            (
+                "rebucket_done",
+                rebucketed.resume_after.is_none().to_string(),
+            ),
+        ]);
+    }
+
+    Ok(app.custom_tag_response(Response::default(), "migrate", attributes))
 }
@@ -1,12 +1,177 @@
-use crate::contract::{App, AppResult};
-use crate::msg::{AppQueryMsg, ConfigResponse, MeetingsResponse};
-use crate::state::{CALENDAR, CONFIG};
-use cosmwasm_std::{to_binary, Binary, Deps, Env, StdResult};
+use crate::contract::{
+    App, AppResult, ATTENTION_HORIZON_SECONDS, DEFAULT_PAGE_LIMIT, MAX_HEATMAP_DAYS,
+    MAX_LISTED_DAYS, MAX_PAGE_LIMIT,
+};
+use crate::error::AppError;
+use crate::msg::{
+    AppQueryMsg, AttentionItem, AttentionReason, AuditLogResponse, AvailabilityHeatmapResponse,
+    BookingFailure, BusyBlocksResponse, ConfigResponse, CreditResponse, DayBoundsResponse,
+    DaySummaryResponse, DiagnoseResponse, DueReminder, DueRemindersResponse, ExportResponse,
+    ExtensionQuoteResponse, FeatureFlags, ICalEvent, ICalFeedResponse, InfoResponse,
+    LongestFreeBlockResponse, MaxNonOverlappingResponse, MeetingResponse, MeetingsForDaysResponse,
+    MeetingsResponse, NeedsAttentionResponse, OraclePriceResponse, OracleQueryMsg,
+    OutstandingResponse, OverlapsResponse, Paged, PendingResolution, PendingResolutionsResponse,
+    ProfileResponse, QuotePriceResponse, ResolvedMeetingsResponse, SelfTestResponse,
+    SimulateRequestMeetingResponse, SlashPreviewResponse, StakeValueResponse, Time,
+    UniqueRequestersResponse, Violation, ViolationReason, WaitlistResponse, Weekday,
+};
+use crate::schedule::{
+    busy_blocks, free_minutes, in_calendar_window, longest_free_slot, max_non_overlapping,
+    overlapping_pairs,
+};
+use crate::state::{
+    Config, AUDIT_LOG, AUDIT_LOG_SEQ, CALENDAR, CONFIG, CREDITS, OUTSTANDING, PAYOUTS_FROZEN,
+    RESOLVED_MEETINGS, WAITLIST,
+};
+use crate::time::{day_key, get_date_time, validate_utc_offset};
+use crate::validation::{
+    block_time, curve_slash_payout, meeting_index_at, meeting_position, priced_end_time,
+    validate_day_key, MeetingDuration,
+};
+use chrono::Datelike;
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Coin, Decimal, Deps, Env, Int64, Order, StdError,
+    StdResult, Uint128,
+};
+use cw_storage_plus::Bound;
+use std::collections::BTreeSet;
 
-pub fn query_handler(deps: Deps, _env: Env, _app: &App, msg: AppQueryMsg) -> AppResult<Binary> {
+use super::execute::{extension_quote, required_stake, validate_booking};
+
+pub fn query_handler(deps: Deps, env: Env, app: &App, msg: AppQueryMsg) -> AppResult<Binary> {
     match msg {
         AppQueryMsg::Config {} => to_binary(&query_config(deps)?),
-        AppQueryMsg::Meetings { datetime } => to_binary(&query_meetings(deps, datetime)?),
+        AppQueryMsg::Profile {} => to_binary(&query_profile(deps)?),
+        AppQueryMsg::Meetings { datetime } => to_binary(&query_meetings(deps, env, datetime)?),
+        AppQueryMsg::MeetingsForDays { days } => {
+            to_binary(&query_meetings_for_days(deps, env, days)?)
+        }
+        AppQueryMsg::QuotePrice {
+            start_time,
+            end_time,
+        } => to_binary(&query_quote_price(deps, start_time, end_time)?),
+        AppQueryMsg::CheapestDenom {
+            start_time,
+            end_time,
+        } => to_binary(&query_cheapest_denom(deps, start_time, end_time)?),
+        AppQueryMsg::Info {} => to_binary(&query_info(deps)?),
+        AppQueryMsg::Credit { address } => to_binary(&query_credit(deps, address)?),
+        AppQueryMsg::MaxNonOverlapping { day_datetime } => {
+            to_binary(&query_max_non_overlapping(deps, day_datetime)?)
+        }
+        AppQueryMsg::DaySummary { day_datetime } => {
+            to_binary(&query_day_summary(deps, day_datetime)?)
+        }
+        AppQueryMsg::PendingResolutions {
+            before,
+            start_after,
+            limit,
+        } => to_binary(&query_pending_resolutions(
+            deps,
+            env,
+            before,
+            start_after,
+            limit,
+        )?),
+        AppQueryMsg::Export { start_after, limit } => {
+            to_binary(&query_export(deps, start_after, limit)?)
+        }
+        AppQueryMsg::DueReminders {
+            now,
+            lookahead,
+            start_after,
+            limit,
+        } => to_binary(&query_due_reminders(
+            deps,
+            env,
+            now,
+            lookahead,
+            start_after,
+            limit,
+        )?),
+        AppQueryMsg::AuditLog { start_after, limit } => {
+            to_binary(&query_audit_log(deps, start_after, limit)?)
+        }
+        AppQueryMsg::ExtensionQuote {
+            day_datetime,
+            meeting_index,
+            new_end_time,
+        } => to_binary(&query_extension_quote(
+            deps,
+            env,
+            day_datetime,
+            meeting_index,
+            new_end_time,
+        )?),
+        AppQueryMsg::UniqueRequesters { start_day, end_day } => {
+            to_binary(&query_unique_requesters(deps, start_day, end_day)?)
+        }
+        AppQueryMsg::AvailabilityHeatmap { start_day, end_day } => {
+            to_binary(&query_availability_heatmap(deps, start_day, end_day)?)
+        }
+        AppQueryMsg::ICalFeed { start_day, end_day } => {
+            to_binary(&query_ical_feed(deps, env, start_day, end_day)?)
+        }
+        AppQueryMsg::LongestFreeBlock { day_datetime } => {
+            to_binary(&query_longest_free_block(deps, day_datetime)?)
+        }
+        AppQueryMsg::DayBounds { day_datetime } => {
+            to_binary(&query_day_bounds(deps, day_datetime)?)
+        }
+        AppQueryMsg::Overlaps { day_datetime } => to_binary(&query_overlaps(deps, day_datetime)?),
+        AppQueryMsg::BusyBlocks { day_datetime } => {
+            to_binary(&query_busy_blocks(deps, day_datetime)?)
+        }
+        AppQueryMsg::NeedsAttention { start_after, limit } => {
+            to_binary(&query_needs_attention(deps, env, start_after, limit)?)
+        }
+        AppQueryMsg::StakeValueInQuote {
+            day_datetime,
+            meeting_index,
+        } => to_binary(&query_stake_value(deps, day_datetime, meeting_index)?),
+        AppQueryMsg::Outstanding {} => to_binary(&query_outstanding(deps, env)?),
+        AppQueryMsg::Waitlist { start_after, limit } => {
+            to_binary(&query_waitlist(deps, start_after, limit)?)
+        }
+        AppQueryMsg::ResolvedMeetings {
+            day_datetime,
+            start_after,
+            limit,
+        } => to_binary(&query_resolved_meetings(
+            deps,
+            env,
+            day_datetime,
+            start_after,
+            limit,
+        )?),
+        AppQueryMsg::SlashPreview {
+            amount_staked,
+            duration_minutes,
+            minutes_late,
+        } => to_binary(&query_slash_preview(
+            deps,
+            amount_staked,
+            duration_minutes,
+            minutes_late,
+        )?),
+        AppQueryMsg::SelfTest { start_after, limit } => {
+            to_binary(&query_self_test(deps, start_after, limit)?)
+        }
+        AppQueryMsg::Diagnose {
+            start_time,
+            end_time,
+            sender,
+        } => to_binary(&query_diagnose(
+            deps, env, app, start_time, end_time, sender,
+        )?),
+        AppQueryMsg::SimulateRequestMeeting {
+            start_time,
+            end_time,
+            requester,
+        } => to_binary(&query_simulate_request_meeting(
+            deps, env, app, start_time, end_time, requester,
+        )?),
     }
     .map_err(Into::into)
 }
@@ -18,12 +183,1012 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         utc_offset: config.utc_offset,
         start_time: config.start_time,
         end_time: config.end_time,
+        self_gap_minutes: config.self_gap_minutes,
+        accepted_denoms: config.accepted_denoms,
+        denom_decimals: config.denom_decimals,
+        allowed_weekdays: config.allowed_weekdays,
+        booking_fee: config.booking_fee,
+        return_claim_deadline: config.return_claim_deadline,
+        attestor: config.attestor,
+        allow_partial_outside: config.allow_partial_outside,
+        price_outside_hours: config.price_outside_hours,
+        price_source: config.price_source,
+        check_outstanding_balance: config.check_outstanding_balance,
+        time_granularity_seconds: config.time_granularity_seconds,
+        allow_admin_booking: config.allow_admin_booking,
+        overbooking_allowed: config.overbooking_allowed,
+        pegged_price_per_minute: config.pegged_price_per_minute,
+        slash_curve: config.slash_curve,
+        cancel_cutoff_secs: config.cancel_cutoff_secs,
+        name: config.name,
+        description: config.description,
+        contact_uri: config.contact_uri,
+        slash_distribution: config.slash_distribution,
+    })
+}
+
+fn query_profile(deps: Deps) -> StdResult<ProfileResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ProfileResponse {
+        name: config.name,
+        description: config.description,
+        contact_uri: config.contact_uri,
     })
 }
 
-fn query_meetings(deps: Deps, datetime: i64) -> StdResult<MeetingsResponse> {
+fn query_meetings(deps: Deps, env: Env, datetime: i64) -> AppResult<MeetingsResponse> {
+    let config = CONFIG.load(deps.storage)?;
     let meetings = CALENDAR
-        .may_load(deps.storage, datetime)?
-        .unwrap_or_default();
+        .may_load(deps.storage, deps.api, datetime)?
+        .unwrap_or_default()
+        .iter()
+        .map(|meeting| MeetingResponse::try_from((meeting, &config, &env)))
+        .collect::<AppResult<_>>()?;
     Ok(MeetingsResponse { meetings })
 }
+
+fn query_meetings_for_days(
+    deps: Deps,
+    env: Env,
+    days: Vec<i64>,
+) -> AppResult<MeetingsForDaysResponse> {
+    if days.len() > MAX_LISTED_DAYS as usize {
+        return Err(AppError::RangeTooLarge {
+            max_days: MAX_LISTED_DAYS,
+        });
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let days = days
+        .into_iter()
+        .map(|day_datetime| -> AppResult<(i64, Vec<MeetingResponse>)> {
+            let meetings = CALENDAR
+                .may_load(deps.storage, deps.api, day_datetime)?
+                .unwrap_or_default()
+                .iter()
+                .map(|meeting| MeetingResponse::try_from((meeting, &config, &env)))
+                .collect::<AppResult<_>>()?;
+            Ok((day_datetime, meetings))
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+    Ok(MeetingsForDaysResponse { days })
+}
+
+fn query_quote_price(
+    deps: Deps,
+    start_time: Int64,
+    end_time: Int64,
+) -> AppResult<QuotePriceResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let end_time = priced_end_time(&config, start_time.i64(), end_time.i64())?;
+    let amount = required_stake(
+        &deps.querier,
+        &config,
+        &config.denom,
+        config.price_per_minute,
+        start_time.i64(),
+        end_time,
+    )?;
+    Ok(QuotePriceResponse {
+        display_amount: format_amount(amount, config.denom_decimals),
+        price: Coin::new(amount.into(), config.denom),
+    })
+}
+
+fn query_cheapest_denom(
+    deps: Deps,
+    start_time: Int64,
+    end_time: Int64,
+) -> AppResult<QuotePriceResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let end_time = priced_end_time(&config, start_time.i64(), end_time.i64())?;
+    let mut cheapest = Coin::new(
+        required_stake(
+            &deps.querier,
+            &config,
+            &config.denom,
+            config.price_per_minute,
+            start_time.i64(),
+            end_time,
+        )?
+        .into(),
+        config.denom.clone(),
+    );
+    for (denom, price_per_minute) in &config.accepted_denoms {
+        let amount = required_stake(
+            &deps.querier,
+            &config,
+            denom,
+            *price_per_minute,
+            start_time.i64(),
+            end_time,
+        )?;
+        if amount < cheapest.amount {
+            cheapest = Coin::new(amount.into(), denom);
+        }
+    }
+    let decimals = if cheapest.denom == config.denom {
+        config.denom_decimals
+    } else {
+        0
+    };
+    Ok(QuotePriceResponse {
+        display_amount: format_amount(cheapest.amount, decimals),
+        price: cheapest,
+    })
+}
+
+/// Formats `amount` as a decimal number with `decimals` fractional digits, without trailing
+/// zeros.
+fn format_amount(amount: Uint128, decimals: u8) -> String {
+    let raw = amount.to_string();
+    if decimals == 0 {
+        return raw;
+    }
+    let decimals = decimals as usize;
+    let padded = format!("{raw:0>width$}", width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+fn query_info(deps: Deps) -> StdResult<InfoResponse> {
+    let version = cw2::get_contract_version(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    Ok(InfoResponse {
+        contract: version.contract,
+        version: version.version,
+        features: FeatureFlags {
+            free_mode: config.price_per_minute.is_zero(),
+            overbooking_allowed: config.overbooking_allowed,
+            allow_partial_outside: config.allow_partial_outside,
+            price_outside_hours: config.price_outside_hours,
+            check_outstanding_balance: config.check_outstanding_balance,
+            allow_admin_booking: config.allow_admin_booking,
+            pegged_pricing: config.pegged_price_per_minute.is_some(),
+            claimable_refunds: config.return_claim_deadline.is_some(),
+            payouts_frozen: PAYOUTS_FROZEN.may_load(deps.storage)?.unwrap_or_default(),
+        },
+    })
+}
+
+fn query_credit(deps: Deps, address: String) -> StdResult<CreditResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let amount = CREDITS
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    Ok(CreditResponse { amount })
+}
+
+fn query_max_non_overlapping(
+    deps: Deps,
+    day_datetime: i64,
+) -> StdResult<MaxNonOverlappingResponse> {
+    let meetings = CALENDAR
+        .may_load(deps.storage, deps.api, day_datetime)?
+        .unwrap_or_default();
+    Ok(MaxNonOverlappingResponse {
+        count: max_non_overlapping(&meetings),
+    })
+}
+
+fn query_day_summary(deps: Deps, day_datetime: i64) -> StdResult<DaySummaryResponse> {
+    let meetings = CALENDAR
+        .may_load(deps.storage, deps.api, day_datetime)?
+        .unwrap_or_default();
+    let (self_booked, booked): (Vec<_>, Vec<_>) =
+        meetings.iter().partition(|meeting| meeting.self_booked);
+    Ok(DaySummaryResponse {
+        count: booked.len() as u32,
+        total_staked: booked.iter().map(|meeting| meeting.amount_staked).sum(),
+        self_booked: self_booked.len() as u32,
+        first_start: meetings.iter().map(|meeting| meeting.start_time).min(),
+        last_end: meetings.iter().map(|meeting| meeting.end_time).max(),
+    })
+}
+
+fn query_longest_free_block(deps: Deps, day_datetime: i64) -> StdResult<LongestFreeBlockResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let meetings = CALENDAR
+        .may_load(deps.storage, deps.api, day_datetime)?
+        .unwrap_or_default();
+    let seconds_into_day = |time: &Time| i64::from(time.hour * 3600 + time.minute * 60);
+    Ok(LongestFreeBlockResponse {
+        slot: longest_free_slot(
+            &meetings,
+            day_datetime + seconds_into_day(&config.start_time),
+            day_datetime + seconds_into_day(&config.end_time),
+        ),
+    })
+}
+
+fn query_day_bounds(deps: Deps, day_datetime: Int64) -> AppResult<DayBoundsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    validate_day_key(validate_utc_offset(config.utc_offset)?, day_datetime.i64())?;
+    let seconds_into_day = |time: &Time| i64::from(time.hour * 3600 + time.minute * 60);
+    Ok(DayBoundsResponse {
+        open_ts: day_datetime.i64() + seconds_into_day(&config.start_time),
+        close_ts: day_datetime.i64() + seconds_into_day(&config.end_time),
+    })
+}
+
+fn query_availability_heatmap(
+    deps: Deps,
+    start_day: Int64,
+    end_day: Int64,
+) -> AppResult<AvailabilityHeatmapResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let timezone = validate_utc_offset(config.utc_offset)?;
+    validate_day_key(timezone, start_day.i64())?;
+    let day_count = day_count(start_day, end_day, MAX_HEATMAP_DAYS)?;
+    let seconds_into_day = |time: &Time| i64::from(time.hour * 3600 + time.minute * 60);
+
+    let mut days = vec![];
+    for day_datetime in (0..day_count).map(|day| start_day.i64() + day * 86_400) {
+        let weekday = Weekday::from(get_date_time(timezone, day_datetime.into())?.weekday());
+        let closed =
+            !config.allowed_weekdays.is_empty() && !config.allowed_weekdays.contains(&weekday);
+        let free_minutes = if closed {
+            0
+        } else {
+            let meetings = CALENDAR
+                .may_load(deps.storage, deps.api, day_datetime)?
+                .unwrap_or_default();
+            free_minutes(
+                &meetings,
+                day_datetime + seconds_into_day(&config.start_time),
+                day_datetime + seconds_into_day(&config.end_time),
+            )?
+        };
+        days.push((day_datetime, free_minutes));
+    }
+    Ok(AvailabilityHeatmapResponse { days })
+}
+
+fn query_overlaps(deps: Deps, day_datetime: i64) -> AppResult<OverlapsResponse> {
+    let meetings = CALENDAR
+        .may_load(deps.storage, deps.api, day_datetime)?
+        .unwrap_or_default();
+    Ok(OverlapsResponse {
+        pairs: overlapping_pairs(&meetings)?,
+    })
+}
+
+fn query_busy_blocks(deps: Deps, day_datetime: i64) -> StdResult<BusyBlocksResponse> {
+    let meetings = CALENDAR
+        .may_load(deps.storage, deps.api, day_datetime)?
+        .unwrap_or_default();
+    Ok(BusyBlocksResponse {
+        blocks: busy_blocks(&meetings),
+    })
+}
+
+fn query_extension_quote(
+    deps: Deps,
+    env: Env,
+    day_datetime: Int64,
+    meeting_index: u32,
+    new_end_time: Int64,
+) -> AppResult<ExtensionQuoteResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let meetings = CALENDAR
+        .may_load(deps.storage, deps.api, day_datetime.i64())?
+        .ok_or(AppError::NoMeetingsAtGivenDayDateTime {
+            day_datetime: day_datetime.i64(),
+        })?;
+    let (additional_stake, conflicts) = extension_quote(
+        &deps.querier,
+        &config,
+        block_time(&env)?,
+        day_datetime.i64(),
+        &meetings,
+        meeting_index,
+        new_end_time,
+    )?;
+    Ok(ExtensionQuoteResponse {
+        additional_stake,
+        conflicts,
+    })
+}
+
+fn query_unique_requesters(
+    deps: Deps,
+    start_day: Int64,
+    end_day: Int64,
+) -> AppResult<UniqueRequestersResponse> {
+    day_count(start_day, end_day, MAX_LISTED_DAYS)?;
+    let mut requesters = BTreeSet::new();
+    for day in CALENDAR.range(
+        deps.storage,
+        deps.api,
+        Some(Bound::inclusive(start_day.i64())),
+        Some(Bound::inclusive(end_day.i64())),
+        Order::Ascending,
+    ) {
+        let (_, meetings) = day?;
+        requesters.extend(
+            meetings
+                .into_iter()
+                .filter(|meeting| !meeting.self_booked)
+                .map(|meeting| meeting.requester),
+        );
+    }
+    Ok(UniqueRequestersResponse {
+        count: requesters.len() as u32,
+    })
+}
+
+fn query_ical_feed(
+    deps: Deps,
+    env: Env,
+    start_day: Int64,
+    end_day: Int64,
+) -> AppResult<ICalFeedResponse> {
+    day_count(start_day, end_day, MAX_LISTED_DAYS)?;
+    let mut events = vec![];
+    for day in CALENDAR.range(
+        deps.storage,
+        deps.api,
+        Some(Bound::inclusive(start_day.i64())),
+        Some(Bound::inclusive(end_day.i64())),
+        Order::Ascending,
+    ) {
+        let (day_datetime, meetings) = day?;
+        // Overbooked meetings can share a start time, so the key of the meeting makes the UID.
+        let mut day_events = meetings
+            .into_iter()
+            .enumerate()
+            .map(|(position, meeting)| {
+                Ok(ICalEvent {
+                    uid: format!(
+                        "{day_datetime}-{}@{}",
+                        meeting_index_at(position)?,
+                        env.contract.address
+                    ),
+                    dtstart: meeting.start_time,
+                    dtend: meeting.end_time,
+                    summary: format!("Meeting with {}", meeting.requester),
+                })
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+        day_events.sort_by_key(|event| event.dtstart);
+        events.extend(day_events);
+    }
+    Ok(ICalFeedResponse { events })
+}
+
+fn query_pending_resolutions(
+    deps: Deps,
+    env: Env,
+    before: Int64,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+) -> AppResult<PendingResolutionsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = page_limit(limit);
+    let start_after: Option<(i64, u32)> = decode_cursor(start_after)?;
+    let min = start_after.map(|(day_datetime, _)| Bound::inclusive(day_datetime));
+    // Meetings ending before `before` start before it, on days whose local midnight is before it.
+    let max = Bound::exclusive(before.i64());
+
+    let mut pending = vec![];
+    'days: for day in CALENDAR.range(deps.storage, deps.api, min, Some(max), Order::Ascending) {
+        let (day_datetime, meetings) = day?;
+        for (position, meeting) in meetings.into_iter().enumerate() {
+            let meeting_index = meeting_index_at(position)?;
+            if start_after.is_some_and(|start_after| (day_datetime, meeting_index) <= start_after) {
+                continue;
+            }
+            if meeting.end_time >= before.i64() || meeting.amount_staked.is_zero() {
+                continue;
+            }
+            pending.push(PendingResolution {
+                day_datetime,
+                meeting_index,
+                meeting: MeetingResponse::try_from((&meeting, &config, &env))?,
+            });
+            if pending.len() > limit {
+                break 'days;
+            }
+        }
+    }
+    Ok(page(
+        pending,
+        limit,
+        |pending| (pending.day_datetime, pending.meeting_index),
+        None,
+    )?)
+}
+
+fn query_needs_attention(
+    deps: Deps,
+    env: Env,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+) -> AppResult<NeedsAttentionResponse> {
+    let limit = page_limit(limit);
+    let start_after: Option<(i64, u32)> = decode_cursor(start_after)?;
+    let config = CONFIG.load(deps.storage)?;
+    let now = block_time(&env)?;
+    let horizon = now.saturating_sub(ATTENTION_HORIZON_SECONDS);
+    let min = Bound::inclusive(
+        start_after.map_or(horizon, |(day_datetime, _)| day_datetime.max(horizon)),
+    );
+
+    let mut meetings = vec![];
+    'days: for day in CALENDAR.range(
+        deps.storage,
+        deps.api,
+        Some(min),
+        Some(Bound::exclusive(now)),
+        Order::Ascending,
+    ) {
+        let (day_datetime, day_meetings) = day?;
+        for (position, meeting) in day_meetings.into_iter().enumerate() {
+            let meeting_index = meeting_index_at(position)?;
+            if start_after.is_some_and(|start_after| (day_datetime, meeting_index) <= start_after) {
+                continue;
+            }
+            let reason = if meeting.disputed {
+                AttentionReason::Disputed
+            } else if meeting.ended_at() < now && !meeting.amount_staked.is_zero() {
+                AttentionReason::Unsettled
+            } else {
+                continue;
+            };
+            meetings.push(AttentionItem {
+                day_datetime,
+                meeting_index,
+                meeting: MeetingResponse::try_from((&meeting, &config, &env))?,
+                reason,
+            });
+            if meetings.len() > limit {
+                break 'days;
+            }
+        }
+    }
+    Ok(page(
+        meetings,
+        limit,
+        |item| (item.day_datetime, item.meeting_index),
+        None,
+    )?)
+}
+
+fn query_outstanding(deps: Deps, env: Env) -> StdResult<OutstandingResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let outstanding = OUTSTANDING
+        .may_load(deps.storage, &config.denom)?
+        .unwrap_or_default();
+    let balance = deps
+        .querier
+        .query_balance(env.contract.address, &config.denom)?
+        .amount;
+    Ok(OutstandingResponse {
+        denom: config.denom,
+        outstanding,
+        balance,
+    })
+}
+
+fn query_stake_value(
+    deps: Deps,
+    day_datetime: i64,
+    meeting_index: u32,
+) -> AppResult<StakeValueResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let meetings = CALENDAR
+        .may_load(deps.storage, deps.api, day_datetime)?
+        .ok_or(AppError::NoMeetingsAtGivenDayDateTime { day_datetime })?;
+    let meeting =
+        meetings
+            .get(meeting_position(meeting_index)?)
+            .ok_or(AppError::MeetingDoesNotExist {
+                day_datetime,
+                meeting_index,
+            })?;
+    let stake = Coin::new(meeting.amount_staked.into(), meeting.stake_denom(&config));
+
+    let Some(price_source) = config.price_source else {
+        return Ok(StakeValueResponse {
+            stake,
+            value: None,
+            quote: None,
+            error: None,
+        });
+    };
+    // A failing oracle only leaves the value out instead of failing the query.
+    let price: StdResult<OraclePriceResponse> = deps.querier.query_wasm_smart(
+        price_source,
+        &OracleQueryMsg::Price {
+            denom: stake.denom.clone(),
+        },
+    );
+    let value = price.and_then(|price| {
+        let value = Decimal::from_atomics(stake.amount, 0)
+            .ok()
+            .and_then(|amount| amount.checked_mul(price.price).ok())
+            .ok_or_else(|| StdError::generic_err("Stake value overflows"))?;
+        Ok((value, price.quote))
+    });
+    Ok(match value {
+        Ok((value, quote)) => StakeValueResponse {
+            stake,
+            value: Some(value),
+            quote: Some(quote),
+            error: None,
+        },
+        Err(error) => StakeValueResponse {
+            stake,
+            value: None,
+            quote: None,
+            error: Some(error.to_string()),
+        },
+    })
+}
+
+fn query_export(
+    deps: Deps,
+    start_after: Option<i64>,
+    limit: Option<u32>,
+) -> StdResult<ExportResponse> {
+    let limit = page_limit(limit);
+    let days = CALENDAR
+        .range(
+            deps.storage,
+            deps.api,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+    Ok(ExportResponse {
+        config: CONFIG.load(deps.storage)?,
+        days,
+        limit: limit as u32,
+    })
+}
+
+fn query_waitlist(
+    deps: Deps,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+) -> StdResult<WaitlistResponse> {
+    let limit = page_limit(limit);
+    let start_after: Option<(i64, u64)> = decode_cursor(start_after)?;
+    let entries = WAITLIST
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit + 1)
+        .map(|entry| {
+            let ((_, entry_id), entry) = entry?;
+            Ok((entry_id, entry))
+        })
+        .collect::<StdResult<_>>()?;
+    page(
+        entries,
+        limit,
+        |(entry_id, entry)| (entry.day_datetime, *entry_id),
+        None,
+    )
+}
+
+fn query_resolved_meetings(
+    deps: Deps,
+    env: Env,
+    day_datetime: Int64,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+) -> AppResult<ResolvedMeetingsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = page_limit(limit);
+    let start_after: Option<u64> = decode_cursor(start_after)?;
+    let meetings = RESOLVED_MEETINGS
+        .prefix(day_datetime.i64())
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit + 1)
+        .map(|entry| -> AppResult<_> {
+            let (id, meeting) = entry?;
+            Ok((id, MeetingResponse::try_from((&meeting, &config, &env))?))
+        })
+        .collect::<AppResult<_>>()?;
+    Ok(page(meetings, limit, |(id, _)| *id, None)?)
+}
+
+fn query_slash_preview(
+    deps: Deps,
+    amount_staked: Uint128,
+    duration_minutes: u32,
+    minutes_late: u32,
+) -> AppResult<SlashPreviewResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let (returned, slashed) = curve_slash_payout(
+        &config.slash_curve,
+        amount_staked,
+        minutes_late,
+        duration_minutes,
+    )?;
+    Ok(SlashPreviewResponse { slashed, returned })
+}
+
+fn query_self_test(
+    deps: Deps,
+    start_after: Option<i64>,
+    limit: Option<u32>,
+) -> AppResult<SelfTestResponse> {
+    let limit = page_limit(limit);
+    let config = CONFIG.load(deps.storage)?;
+    let timezone = validate_utc_offset(config.utc_offset)?;
+    let seconds_into_day = |time: &Time| i64::from(time.hour * 3600 + time.minute * 60);
+
+    let mut days_checked = 0;
+    let mut violations = vec![];
+    for day in CALENDAR
+        .range(
+            deps.storage,
+            deps.api,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+    {
+        let (day_datetime, meetings) = day?;
+        days_checked += 1;
+        let open = day_datetime + seconds_into_day(&config.start_time);
+        let close = day_datetime + seconds_into_day(&config.end_time);
+
+        let mut reasons: Vec<(u32, ViolationReason)> = vec![];
+        for (position, meeting) in meetings.iter().enumerate() {
+            let meeting_index = meeting_index_at(position)?;
+            // A start chrono cannot place in the timezone has no day either.
+            if day_key(timezone, meeting.start_time).ok() != Some(day_datetime) {
+                reasons.push((meeting_index, ViolationReason::WrongDay));
+            }
+            if MeetingDuration::of(meeting).is_err() {
+                reasons.push((meeting_index, ViolationReason::InvalidTimes));
+            }
+            if !in_calendar_window(open, close, meeting.start_time)
+                || meeting.end_time <= open
+                || (meeting.end_time > close && !config.allow_partial_outside)
+            {
+                reasons.push((meeting_index, ViolationReason::OutsideCalendarHours));
+            }
+            if meeting.free != meeting.amount_staked.is_zero() {
+                reasons.push((meeting_index, ViolationReason::StakeMismatch));
+            }
+            if meeting.resolution.is_some() {
+                reasons.push((meeting_index, ViolationReason::Resolved));
+            }
+        }
+        // The order of a day is not checked: a meeting is addressed by its position in the
+        // day, so days are appended to in booking order and sorting them would renumber the
+        // meetings already booked.
+        if !config.overbooking_allowed {
+            for (meeting_index, other_index) in overlapping_pairs(&meetings)? {
+                reasons.push((meeting_index, ViolationReason::Overlaps { other_index }));
+            }
+        }
+        reasons.sort_by_key(|(meeting_index, _)| *meeting_index);
+        violations.extend(
+            reasons
+                .into_iter()
+                .map(|(meeting_index, reason)| Violation {
+                    day_datetime,
+                    meeting_index,
+                    reason,
+                }),
+        );
+    }
+    Ok(SelfTestResponse {
+        days_checked,
+        violations,
+        limit: limit as u32,
+    })
+}
+
+fn query_audit_log(
+    deps: Deps,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+) -> StdResult<AuditLogResponse> {
+    let limit = page_limit(limit);
+    let start_after: Option<u64> = decode_cursor(start_after)?;
+    let events = AUDIT_LOG
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit + 1)
+        .collect::<StdResult<_>>()?;
+    // The log is append-only, so its sequence number counts every entry.
+    let total = AUDIT_LOG_SEQ.may_load(deps.storage)?.unwrap_or_default();
+    page(events, limit, |(seq, _)| *seq, Some(total))
+}
+
+fn query_due_reminders(
+    deps: Deps,
+    env: Env,
+    now: Int64,
+    lookahead: u64,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+) -> AppResult<DueRemindersResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = page_limit(limit);
+    let start_after: Option<(i64, u32)> = decode_cursor(start_after)?;
+    let lookahead = i64::try_from(lookahead).map_err(|_| AppError::ConversionOverflow {
+        value: lookahead.to_string(),
+        target: "i64".to_string(),
+    })?;
+    let max_seconds = i64::from(MAX_LISTED_DAYS) * 86_400;
+    if lookahead > max_seconds {
+        return Err(AppError::RangeTooLarge {
+            max_days: MAX_LISTED_DAYS,
+        });
+    }
+    let window_end = now.i64().saturating_add(lookahead);
+    // Meetings starting after `now` are on days whose local midnight is less than a day before.
+    let min = match start_after {
+        Some((day_datetime, _)) if day_datetime > now.i64().saturating_sub(86_400) => {
+            Bound::inclusive(day_datetime)
+        }
+        _ => Bound::exclusive(now.i64().saturating_sub(86_400)),
+    };
+    // Only the days ahead that could be listed are read.
+    let max = Bound::inclusive(now.i64().saturating_add(max_seconds));
+
+    let mut reminders = vec![];
+    'days: for day in CALENDAR.range(
+        deps.storage,
+        deps.api,
+        Some(min),
+        Some(max),
+        Order::Ascending,
+    ) {
+        let (day_datetime, meetings) = day?;
+        for (position, meeting) in meetings.into_iter().enumerate() {
+            let meeting_index = meeting_index_at(position)?;
+            if start_after.is_some_and(|start_after| (day_datetime, meeting_index) <= start_after) {
+                continue;
+            }
+            let Some(reminder_minutes) = meeting.reminder_minutes else {
+                continue;
+            };
+            let remind_at = meeting.start_time - i64::from(reminder_minutes) * 60;
+            if remind_at < now.i64() || remind_at > window_end {
+                continue;
+            }
+            reminders.push(DueReminder {
+                day_datetime,
+                meeting_index,
+                remind_at,
+                meeting: MeetingResponse::try_from((&meeting, &config, &env))?,
+            });
+            if reminders.len() > limit {
+                break 'days;
+            }
+        }
+    }
+    Ok(page(
+        reminders,
+        limit,
+        |reminder| (reminder.day_datetime, reminder.meeting_index),
+        None,
+    )?)
+}
+
+fn query_diagnose(
+    deps: Deps,
+    env: Env,
+    app: &App,
+    start_time: Int64,
+    end_time: Int64,
+    sender: String,
+) -> AppResult<DiagnoseResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let sender = deps.api.addr_validate(&sender)?;
+    let failure = validate_booking(
+        deps,
+        app,
+        &env,
+        &config,
+        &sender,
+        &config.denom,
+        start_time,
+        end_time,
+        None,
+    )
+    .err()
+    .map(|error| booking_failure(&error, &config));
+    Ok(DiagnoseResponse { failure })
+}
+
+fn query_simulate_request_meeting(
+    deps: Deps,
+    env: Env,
+    app: &App,
+    start_time: Int64,
+    end_time: Int64,
+    requester: Option<String>,
+) -> AppResult<SimulateRequestMeetingResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    // The contract never books meetings, so it has none a self gap applies to.
+    let requester = match requester {
+        Some(requester) => deps.api.addr_validate(&requester)?,
+        None => env.contract.address.clone(),
+    };
+    let response = match validate_booking(
+        deps,
+        app,
+        &env,
+        &config,
+        &requester,
+        &config.denom,
+        start_time,
+        end_time,
+        None,
+    ) {
+        Ok(booking) => SimulateRequestMeetingResponse::Accepted {
+            day_datetime: booking.day_datetime,
+            meeting_index: meeting_index_at(booking.meetings.len())?,
+            price: Coin::new(booking.expected_amount.into(), config.denom),
+        },
+        Err(error) => SimulateRequestMeetingResponse::Rejected {
+            failure: booking_failure(&error, &config),
+        },
+    };
+    Ok(response)
+}
+
+fn booking_failure(error: &AppError, config: &Config) -> BookingFailure {
+    BookingFailure {
+        code: error.code().to_string(),
+        message: error.to_string(),
+        config: offending_config(error, config),
+    }
+}
+
+/// Returns the config fields the check that failed with `error` depends on.
+fn offending_config(error: &AppError, config: &Config) -> Vec<(String, String)> {
+    let field = |name: &str, value: String| (name.to_string(), value);
+    match error {
+        AppError::DenomNotAccepted { .. } => vec![
+            field("denom", config.denom.clone()),
+            field(
+                "accepted_denoms",
+                config
+                    .accepted_denoms
+                    .iter()
+                    .map(|(denom, _)| denom.as_str())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+        ],
+        AppError::InvalidUtcOffset { .. }
+        | AppError::LocalTimeOutOfRange { .. }
+        | AppError::AmbiguousLocalTime { .. }
+        | AppError::InvalidMeetingStart { .. }
+        | AppError::InvalidMeetingEnd { .. }
+        | AppError::StartAndEndTimeNotOnSameDay {} => {
+            vec![field("utc_offset", config.utc_offset.to_string())]
+        }
+        AppError::TimeNotAlignedToGranularity { .. } => vec![field(
+            "time_granularity_seconds",
+            config.time_granularity_seconds.to_string(),
+        )],
+        AppError::StartTimeAtCalendarClose { .. }
+        | AppError::StartTimeDoesNotFallWithinCalendarBounds { .. } => vec![
+            field("start_time", config.start_time.to_string()),
+            field("end_time", config.end_time.to_string()),
+        ],
+        AppError::EndTimeDoesNotFallWithinCalendarBounds { .. } => vec![
+            field("start_time", config.start_time.to_string()),
+            field("end_time", config.end_time.to_string()),
+            field(
+                "allow_partial_outside",
+                config.allow_partial_outside.to_string(),
+            ),
+        ],
+        AppError::WeekdayNotAllowed {} => vec![field(
+            "allowed_weekdays",
+            config
+                .allowed_weekdays
+                .iter()
+                .map(|weekday| format!("{weekday:?}"))
+                .collect::<Vec<_>>()
+                .join(","),
+        )],
+        AppError::PriceSourceNotSet {} | AppError::PriceSourceUnavailable { .. } => vec![
+            field(
+                "price_source",
+                config
+                    .price_source
+                    .as_ref()
+                    .map(Addr::to_string)
+                    .unwrap_or_default(),
+            ),
+            field(
+                "pegged_price_per_minute",
+                config
+                    .pegged_price_per_minute
+                    .map(|price| price.to_string())
+                    .unwrap_or_default(),
+            ),
+        ],
+        AppError::AdminBookingNotAllowed {} => vec![field(
+            "allow_admin_booking",
+            config.allow_admin_booking.to_string(),
+        )],
+        AppError::MeetingConflictExists { .. } => vec![field(
+            "overbooking_allowed",
+            config.overbooking_allowed.to_string(),
+        )],
+        AppError::SelfGapViolated { .. } => vec![field(
+            "self_gap_minutes",
+            config.self_gap_minutes.to_string(),
+        )],
+        _ => vec![],
+    }
+}
+
+/// Returns the number of days from `start_day` to `end_day` (inclusive), failing when a query
+/// would cover more than `max_days`.
+fn day_count(start_day: Int64, end_day: Int64, max_days: u32) -> AppResult<i64> {
+    let day_count = end_day
+        .i64()
+        .saturating_sub(start_day.i64())
+        .div_euclid(86_400)
+        + 1;
+    if day_count > i64::from(max_days) {
+        return Err(AppError::RangeTooLarge { max_days });
+    }
+    Ok(day_count)
+}
+
+/// Returns the number of entries of a page of a list query asking for `limit`.
+fn page_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize
+}
+
+/// Decodes the `start_after` cursor of a list query, which is the key of the last entry of the
+/// previous page.
+fn decode_cursor<K: DeserializeOwned>(start_after: Option<Binary>) -> StdResult<Option<K>> {
+    start_after.map(|cursor| from_binary(&cursor)).transpose()
+}
+
+/// Builds a page from `items` read up to one past `limit`, so that the cursor is only set when
+/// another page follows.
+fn page<T, K: Serialize>(
+    mut items: Vec<T>,
+    limit: usize,
+    key: impl Fn(&T) -> K,
+    total: Option<u64>,
+) -> StdResult<Paged<T>> {
+    let mut next_key = None;
+    if items.len() > limit {
+        items.truncate(limit);
+        next_key = items.last().map(|item| to_binary(&key(item))).transpose()?;
+    }
+    Ok(Paged {
+        items,
+        next_key,
+        total,
+        limit: limit as u32,
+    })
+}
@@ -0,0 +1,202 @@
+#[cfg(feature = "daemon")]
+pub mod daemon;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeZone};
+use cosmwasm_std::{Coin, Timestamp};
+use cw_orch::prelude::*;
+
+use crate::contract::{interface::AppInterface, MAX_BATCH};
+use crate::msg::{
+    AppExecuteMsg, AppExecuteMsgFns, AppQueryMsg, AppQueryMsgFns, ExecuteMsg, ExportResponse,
+    MeetingResponse, QueryMsg, QuotePriceResponse,
+};
+use crate::state::{Config, Meeting};
+use crate::time::start_of_day_timestamp;
+
+/// The config and every booked day of a calendar, keyed by their original day keys.
+#[cosmwasm_schema::cw_serde]
+pub struct CalendarSnapshot {
+    pub config: Config,
+    pub days: Vec<(i64, Vec<Meeting>)>,
+}
+
+impl<Chain: CwEnv> AppInterface<Chain> {
+    /// Returns the exact coin that has to be attached to a `RequestMeeting` with the given
+    /// unix timestamps: the stake quoted by the contract itself plus the booking fee.
+    pub fn expected_stake(&self, start_time: i64, end_time: i64) -> Result<Coin, CwOrchError> {
+        let quote: QuotePriceResponse = self.query(&QueryMsg::from(AppQueryMsg::QuotePrice {
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+        }))?;
+        let mut price = quote.price;
+        price.amount += self.config()?.booking_fee;
+        Ok(price)
+    }
+
+    /// Requests a meeting, attaching the stake quoted by [`Self::expected_stake`].
+    pub fn request_meeting_with_auto_funds(
+        &self,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<Chain::Response, CwOrchError> {
+        let stake = self.expected_stake(start_time, end_time)?;
+        let funds = if stake.amount.is_zero() {
+            vec![]
+        } else {
+            vec![stake]
+        };
+        self.execute(
+            &ExecuteMsg::from(AppExecuteMsg::RequestMeeting {
+                start_time: start_time.into(),
+                end_time: end_time.into(),
+                refund_splits: None,
+                reminder_minutes: None,
+                max_total_stake: None,
+            }),
+            Some(&funds),
+        )
+    }
+
+    /// Like [`Self::request_meeting_with_auto_funds`], sending the times as timestamps.
+    pub fn request_meeting_at_with_auto_funds(
+        &self,
+        start_time: Timestamp,
+        end_time: Timestamp,
+    ) -> Result<Chain::Response, CwOrchError> {
+        // Fractions of a second are left for the contract to reject.
+        let seconds = |timestamp: Timestamp| {
+            i64::try_from(timestamp.seconds())
+                .map_err(|err| CwOrchError::StdErr(format!("invalid timestamp {timestamp}: {err}")))
+        };
+        let stake = self.expected_stake(seconds(start_time)?, seconds(end_time)?)?;
+        let funds = if stake.amount.is_zero() {
+            vec![]
+        } else {
+            vec![stake]
+        };
+        self.execute(
+            &ExecuteMsg::from(AppExecuteMsg::RequestMeetingAt {
+                start_time,
+                end_time,
+                refund_splits: None,
+                reminder_minutes: None,
+                max_total_stake: None,
+            }),
+            Some(&funds),
+        )
+    }
+
+    /// Returns the datetime of the given local date (`%Y-%m-%d`) and time (`%H:%M`) in the
+    /// calendar's configured timezone.
+    pub fn local_datetime(
+        &self,
+        date: &str,
+        time: &str,
+    ) -> Result<DateTime<FixedOffset>, CwOrchError> {
+        let utc_offset = self.config()?.utc_offset;
+        let timezone = FixedOffset::east_opt(utc_offset)
+            .ok_or_else(|| CwOrchError::StdErr(format!("invalid utc offset {utc_offset}")))?;
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|err| CwOrchError::StdErr(format!("invalid date {date}: {err}")))?;
+        let time = NaiveTime::parse_from_str(time, "%H:%M")
+            .map_err(|err| CwOrchError::StdErr(format!("invalid time {time}: {err}")))?;
+        timezone
+            .from_local_datetime(&date.and_time(time))
+            .single()
+            .ok_or_else(|| CwOrchError::StdErr(format!("{date} {time} is not a valid local time")))
+    }
+
+    /// Returns the `(day_datetime, meeting_index)` pair of the meeting starting at the given
+    /// local date and time.
+    pub fn find_meeting(&self, date: &str, start: &str) -> Result<(i64, u32), CwOrchError> {
+        let start_datetime = self.local_datetime(date, start)?;
+        let day_datetime = start_of_day_timestamp(&start_datetime);
+        let meetings = self.meetings(day_datetime)?.meetings;
+        let meeting_index = locate_meeting(&meetings, start_datetime.timestamp())?;
+        Ok((day_datetime, meeting_index))
+    }
+
+    /// Returns the stake of the meeting starting at the given local date and time.
+    pub fn return_stake_for(
+        &self,
+        date: &str,
+        start: &str,
+    ) -> Result<Chain::Response, CwOrchError> {
+        let (day_datetime, meeting_index) = self.find_meeting(date, start)?;
+        self.return_stake(day_datetime.into(), meeting_index)
+    }
+
+    /// Slashes the full stake of the meeting starting at the given local date and time.
+    pub fn slash_full_for(&self, date: &str, start: &str) -> Result<Chain::Response, CwOrchError> {
+        let (day_datetime, meeting_index) = self.find_meeting(date, start)?;
+        self.slash_full_stake(day_datetime.into(), meeting_index)
+    }
+
+    /// Pages through the export query and returns the full calendar state.
+    pub fn export_state(&self) -> Result<CalendarSnapshot, CwOrchError> {
+        let mut days: Vec<(i64, Vec<Meeting>)> = vec![];
+        loop {
+            let page: ExportResponse = self.query(&QueryMsg::from(AppQueryMsg::Export {
+                start_after: days.last().map(|(day_datetime, _)| *day_datetime),
+                limit: Some(MAX_BATCH as u32),
+            }))?;
+            if page.days.is_empty() {
+                return Ok(CalendarSnapshot {
+                    config: page.config,
+                    days,
+                });
+            }
+            days.extend(page.days);
+        }
+    }
+
+    /// Writes `snapshot` into the calendar, `MAX_BATCH` days per message. The config is
+    /// written with the first message. Fails if a day of the snapshot already has meetings
+    /// unless `force` is set.
+    ///
+    /// The stakes of imported meetings are not backed by funds held by the contract.
+    pub fn import_state(
+        &self,
+        snapshot: &CalendarSnapshot,
+        force: bool,
+    ) -> Result<(), CwOrchError> {
+        let mut chunks = snapshot.days.chunks(MAX_BATCH);
+        self.execute(
+            &ExecuteMsg::from(AppExecuteMsg::ImportState {
+                config: Some(snapshot.config.clone()),
+                days: chunks.next().unwrap_or_default().to_vec(),
+                force,
+            }),
+            None,
+        )?;
+        for chunk in chunks {
+            self.execute(
+                &ExecuteMsg::from(AppExecuteMsg::ImportState {
+                    config: None,
+                    days: chunk.to_vec(),
+                    force,
+                }),
+                None,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the index of the only meeting in `meetings` that starts at `start_time`.
+pub fn locate_meeting(meetings: &[MeetingResponse], start_time: i64) -> Result<u32, CwOrchError> {
+    let mut matches = meetings
+        .iter()
+        .enumerate()
+        .filter(|(_, meeting)| meeting.start_time == start_time)
+        .map(|(index, _)| index as u32);
+    match (matches.next(), matches.next()) {
+        (Some(index), None) => Ok(index),
+        (None, _) => Err(CwOrchError::StdErr(format!(
+            "no meeting starts at {start_time}"
+        ))),
+        (Some(_), Some(_)) => Err(CwOrchError::StdErr(format!(
+            "multiple meetings start at {start_time}"
+        ))),
+    }
+}
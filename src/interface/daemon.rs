@@ -0,0 +1,141 @@
+//! Helpers for operating the app on a live network through cw-orch's [`Daemon`].
+//!
+//! Every call blocks on the runtime whose handle was given to [`deploy_to`] or
+//! [`CalendarDeployment::connect`]. Dates are given as `%Y-%m-%d`, local to the app's configured
+//! `utc_offset`.
+
+use abstract_core::objects::{AccountId, AssetEntry};
+use abstract_interface::{Abstract, AbstractAccount, AppDeployer};
+use cosmwasm_std::Uint128;
+use cw_orch::{anyhow, daemon::ChainInfo, deploy::Deploy, prelude::*, tokio::runtime::Handle};
+
+use crate::contract::{interface::AppInterface, APP_ID, APP_VERSION, MAX_BATCH};
+use crate::msg::{
+    AppExecuteMsg, AppInstantiateMsg, AppQueryMsg, ExecuteMsg, PendingResolution,
+    PendingResolutionsResponse, QueryMsg,
+};
+
+/// The app installed on an Abstract account of a live network.
+pub struct CalendarDeployment {
+    pub app: AppInterface<Daemon>,
+    pub account: AbstractAccount<Daemon>,
+}
+
+/// Uploads and registers the current app version, then installs it on the given account.
+pub fn deploy_to(
+    handle: &Handle,
+    chain_info: ChainInfo,
+    account_id: AccountId,
+    instantiate_msg: &AppInstantiateMsg,
+) -> anyhow::Result<CalendarDeployment> {
+    let deployment = CalendarDeployment::connect(handle, chain_info, account_id)?;
+    deployment.app.deploy(APP_VERSION.parse()?)?;
+    deployment
+        .account
+        .install_app(deployment.app.clone(), instantiate_msg, None)?;
+    Ok(deployment)
+}
+
+impl CalendarDeployment {
+    /// Connects to the app installed on the given account.
+    pub fn connect(
+        handle: &Handle,
+        chain_info: ChainInfo,
+        account_id: AccountId,
+    ) -> anyhow::Result<Self> {
+        let daemon = Daemon::builder().chain(chain_info).handle(handle).build()?;
+        let abstr = Abstract::load_from(daemon.clone())?;
+        Ok(Self {
+            app: AppInterface::new(APP_ID, daemon),
+            account: AbstractAccount::new(&abstr, Some(account_id)),
+        })
+    }
+
+    /// Sets the price per minute and the denom of the stakes.
+    pub fn update_pricing(
+        &self,
+        price_per_minute: Uint128,
+        denom: AssetEntry,
+    ) -> anyhow::Result<<Daemon as TxHandler>::Response> {
+        self.execute(AppExecuteMsg::UpdateConfig {
+            price_per_minute: Some(price_per_minute),
+            denom: Some(denom),
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        })
+    }
+
+    /// Returns every meeting that ended before the start of `before_date`, or before the
+    /// current block time if that is earlier, and whose stake is still held.
+    pub fn pending_resolutions(&self, before_date: &str) -> anyhow::Result<Vec<PendingResolution>> {
+        let now = i64::try_from(self.app.get_chain().block_info()?.time.seconds())?;
+        let before = self
+            .app
+            .local_datetime(before_date, "00:00")?
+            .timestamp()
+            .min(now);
+
+        let mut pending = vec![];
+        let mut start_after = None;
+        loop {
+            let page: PendingResolutionsResponse =
+                self.app
+                    .query(&QueryMsg::from(AppQueryMsg::PendingResolutions {
+                        before: before.into(),
+                        start_after,
+                        limit: Some(MAX_BATCH as u32),
+                    }))?;
+            pending.extend(page.items);
+            start_after = page.next_key;
+            if start_after.is_none() {
+                break;
+            }
+        }
+        Ok(pending)
+    }
+
+    /// Returns the stakes of all [`Self::pending_resolutions`] in batches of `MAX_BATCH`
+    /// meetings. Returns the number of stakes returned.
+    pub fn resolve_all_pending(&self, before_date: &str) -> anyhow::Result<usize> {
+        let pending = self.pending_resolutions(before_date)?;
+        // Returned meetings are removed from their day, shifting the indices after them, so
+        // the batches are sent from the last one back.
+        for batch in pending.chunks(MAX_BATCH).rev() {
+            self.execute(AppExecuteMsg::ReturnStakes {
+                meetings: batch
+                    .iter()
+                    .map(|pending| (pending.day_datetime.into(), pending.meeting_index))
+                    .collect(),
+            })?;
+        }
+        Ok(pending.len())
+    }
+
+    /// Executes `msg` through the account manager, which is the admin of the app.
+    fn execute(&self, msg: AppExecuteMsg) -> anyhow::Result<<Daemon as TxHandler>::Response> {
+        Ok(self
+            .account
+            .manager
+            .execute_on_module(APP_ID, ExecuteMsg::from(msg))?)
+    }
+}
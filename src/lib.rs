@@ -1,8 +1,17 @@
 pub mod contract;
 pub mod error;
+pub mod events;
 mod handlers;
+#[cfg(feature = "interface")]
+pub mod interface;
+pub mod migration;
 pub mod msg;
+pub mod schedule;
 pub mod state;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod time;
+pub mod validation;
 
 #[cfg(feature = "interface")]
 pub use contract::interface::AppInterface;
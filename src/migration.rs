@@ -0,0 +1,122 @@
+//! Migrations of the stored calendar run by the migrate handler, once or in bounded steps.
+
+use chrono::FixedOffset;
+use cosmwasm_std::{Api, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Map};
+use std::collections::BTreeMap;
+
+use crate::contract::AppResult;
+use crate::error::AppError;
+use crate::state::{Meeting, WaitlistEntry, CALENDAR, WAITLIST};
+use crate::time::day_key;
+
+/// The calendar as stored before days were stored as `StoredMeeting`s, under the same namespace.
+const LEGACY_CALENDAR: Map<i64, Vec<Meeting>> = Map::new("calendar");
+
+/// Rewrites every day of a calendar stored before meetings were stored compactly as
+/// `StoredMeeting`s and returns the number of days rewritten. Must run before anything else
+/// reads the calendar, which cannot decode the legacy days.
+pub fn compact_meetings(storage: &mut dyn Storage, api: &dyn Api) -> StdResult<u32> {
+    let days: Vec<(i64, Vec<Meeting>)> = LEGACY_CALENDAR
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for (day_datetime, meetings) in &days {
+        CALENDAR.save(storage, api, *day_datetime, meetings)?;
+    }
+    Ok(days.len() as u32)
+}
+
+/// The waitlist as stored before entries were keyed by their day.
+const LEGACY_WAITLIST: Map<u64, WaitlistEntry> = Map::new("waitlist");
+
+/// Moves every entry of a waitlist stored before entries were keyed by their day under its day
+/// and returns the number of entries moved. Running it again moves nothing.
+pub fn rekey_waitlist(storage: &mut dyn Storage) -> StdResult<u32> {
+    let entries: Vec<(u64, WaitlistEntry)> = LEGACY_WAITLIST
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for (entry_id, entry) in &entries {
+        LEGACY_WAITLIST.remove(storage, *entry_id);
+        WAITLIST.save(storage, (entry.day_datetime, *entry_id), entry)?;
+    }
+    Ok(entries.len() as u32)
+}
+
+/// Progress of a [`rebucket_days`] step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rebucketed {
+    pub days_checked: u32,
+    pub meetings_moved: u32,
+    /// The last day key checked, to resume after. Unset once every day was checked.
+    pub resume_after: Option<i64>,
+}
+
+/// Moves the meetings of up to `limit` days after `start_after` to the day key of their start in
+/// `timezone`, appending them to the meetings already stored under that key.
+///
+/// Days a meeting is moved to may lie ahead of the checked days and are checked again later,
+/// which finds nothing left to move. Running the migration again once it completed moves nothing.
+///
+/// A `limit` of zero is rejected, as a step checking no day could not tell where to resume.
+pub fn rebucket_days(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    timezone: FixedOffset,
+    start_after: Option<i64>,
+    limit: u32,
+) -> AppResult<Rebucketed> {
+    if limit == 0 {
+        return Err(AppError::ZeroLimit {});
+    }
+    // One more day than handled tells whether any are left.
+    let days: Vec<i64> = CALENDAR
+        .keys(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize + 1)
+        .collect::<StdResult<_>>()?;
+    let done = days.len() <= limit as usize;
+
+    let mut rebucketed = Rebucketed {
+        days_checked: 0,
+        meetings_moved: 0,
+        resume_after: None,
+    };
+    for day_datetime in days.into_iter().take(limit as usize) {
+        let meetings = CALENDAR.load(storage, api, day_datetime)?;
+        let mut kept = Vec::with_capacity(meetings.len());
+        let mut moved: BTreeMap<i64, Vec<Meeting>> = BTreeMap::new();
+        for meeting in meetings {
+            let meeting_day = day_key(timezone, meeting.start_time)?;
+            if meeting_day == day_datetime {
+                kept.push(meeting);
+            } else {
+                moved.entry(meeting_day).or_default().push(meeting);
+            }
+        }
+        if !moved.is_empty() {
+            if kept.is_empty() {
+                CALENDAR.remove(storage, day_datetime);
+            } else {
+                CALENDAR.save(storage, api, day_datetime, &kept)?;
+            }
+            for (meeting_day, meetings) in moved {
+                rebucketed.meetings_moved += meetings.len() as u32;
+                let mut day = CALENDAR
+                    .may_load(storage, api, meeting_day)?
+                    .unwrap_or_default();
+                day.extend(meetings);
+                CALENDAR.save(storage, api, meeting_day, &day)?;
+            }
+        }
+        rebucketed.days_checked += 1;
+        rebucketed.resume_after = Some(day_datetime);
+    }
+    if done {
+        rebucketed.resume_after = None;
+    }
+    Ok(rebucketed)
+}
@@ -1,9 +1,16 @@
 use abstract_core::objects::AssetEntry;
-use chrono::NaiveTime;
+use chrono::{NaiveTime, Timelike};
 use cosmwasm_schema::QueryResponses;
-use cosmwasm_std::{Int64, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Env, Int64, Timestamp, Uint128};
+use std::fmt;
 
-use crate::{contract::App, state::Meeting};
+use crate::{
+    contract::{App, AppResult},
+    error::AppError,
+    state::{Config, Meeting, ResolutionAction, SlashCurve, StakeEvent, WaitlistEntry},
+    time::{duration_in_minutes, format_local, validate_utc_offset},
+    validation::block_time,
+};
 
 // This is used for type safety and re-exporting the contract endpoint structs.
 abstract_app::app_msg_types!(App, AppExecuteMsg, AppQueryMsg);
@@ -15,10 +22,69 @@ pub struct Time {
     pub minute: u32,
 }
 
-impl From<Time> for NaiveTime {
-    fn from(value: Time) -> Self {
-        // TODO: handle option
-        NaiveTime::from_hms_opt(value.hour, value.minute, 0).unwrap()
+impl Time {
+    /// Validates that the hour and minute make up a time of the day, from 00:00 to 23:59.
+    pub fn validate(&self) -> AppResult<()> {
+        if self.hour >= 24 || self.minute >= 60 {
+            return Err(AppError::InvalidTimeValue {
+                hour: self.hour,
+                minute: self.minute,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl From<NaiveTime> for Time {
+    fn from(value: NaiveTime) -> Self {
+        Time {
+            hour: value.hour(),
+            minute: value.minute(),
+        }
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+impl TryFrom<Time> for NaiveTime {
+    type Error = AppError;
+
+    fn try_from(value: Time) -> AppResult<Self> {
+        value.validate()?;
+        NaiveTime::from_hms_opt(value.hour, value.minute, 0).ok_or(AppError::InvalidTimeValue {
+            hour: value.hour,
+            minute: value.minute,
+        })
+    }
+}
+
+#[cosmwasm_schema::cw_serde]
+#[derive(Copy)]
+pub enum Weekday {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+impl From<chrono::Weekday> for Weekday {
+    fn from(value: chrono::Weekday) -> Self {
+        match value {
+            chrono::Weekday::Mon => Weekday::Mon,
+            chrono::Weekday::Tue => Weekday::Tue,
+            chrono::Weekday::Wed => Weekday::Wed,
+            chrono::Weekday::Thu => Weekday::Thu,
+            chrono::Weekday::Fri => Weekday::Fri,
+            chrono::Weekday::Sat => Weekday::Sat,
+            chrono::Weekday::Sun => Weekday::Sun,
+        }
     }
 }
 
@@ -30,6 +96,20 @@ pub struct AppInstantiateMsg {
     pub utc_offset: i32,
     pub start_time: Time,
     pub end_time: Time,
+    pub self_gap_minutes: u32,
+    /// Denoms other than `denom` that stakes can be paid in, with their price per minute. Each
+    /// must resolve to a native denom in the name service.
+    #[serde(default)]
+    pub accepted_denoms: Vec<(AssetEntry, Uint128)>,
+    /// Name shown by frontends listing calendars, at most `MAX_NAME_LENGTH` characters.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// At most `MAX_DESCRIPTION_LENGTH` characters.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Where to reach the owner of the calendar, at most `MAX_CONTACT_URI_LENGTH` characters.
+    #[serde(default)]
+    pub contact_uri: Option<String>,
 }
 
 /// App execute messages
@@ -37,10 +117,41 @@ pub struct AppInstantiateMsg {
 #[cfg_attr(feature = "interface", derive(cw_orch::ExecuteFns))]
 #[cfg_attr(feature = "interface", impl_into(ExecuteMsg))]
 pub enum AppExecuteMsg {
+    /// Books a meeting, setting a [`RequestMeetingResponse`] as the data of the response.
+    /// Deprecated in favour of `RequestMeetingAt`, kept while clients move over.
     RequestMeeting {
         start_time: Int64,
         end_time: Int64,
+        /// Splits the stake between several addresses when it is returned, each with a share
+        /// in basis points. The shares must add up to 10000.
+        refund_splits: Option<Vec<(String, u16)>>,
+        /// Minutes before the start of the meeting at which a reminder is due, see
+        /// `AppQueryMsg::DueReminders`.
+        reminder_minutes: Option<u32>,
+        /// Rejects the booking when the stake plus the booking fee would be above this amount,
+        /// e.g. after the price was raised since it was quoted.
+        max_total_stake: Option<Uint128>,
+    },
+    /// Like `RequestMeeting`, with the times as timestamps, which must be whole seconds.
+    RequestMeetingAt {
+        start_time: Timestamp,
+        end_time: Timestamp,
+        refund_splits: Option<Vec<(String, u16)>>,
+        reminder_minutes: Option<u32>,
+        max_total_stake: Option<Uint128>,
+    },
+    /// Like `RequestMeeting`, with the times as ISO 8601 strings in the calendar's UTC offset,
+    /// e.g. `2024-06-03T14:00:00+02:00`.
+    RequestMeetingIso {
+        start_time: String,
+        end_time: String,
+        refund_splits: Option<Vec<(String, u16)>>,
+        reminder_minutes: Option<u32>,
+        max_total_stake: Option<Uint128>,
     },
+    /// Slashes the whole stake of an ended meeting. Like every stake resolution it sets a
+    /// [`ResolutionResponse`] as the data of the response. Stakes can be resolved from the
+    /// block whose time is the end of the meeting.
     SlashFullStake {
         day_datetime: Int64,
         meeting_index: u32,
@@ -54,9 +165,226 @@ pub enum AppExecuteMsg {
         day_datetime: Int64,
         meeting_index: u32,
     },
+    /// Like `SlashFullStake`, with the day as a timestamp. The `Int64` stake messages are
+    /// deprecated in favour of these.
+    SlashFullStakeAt {
+        day_datetime: Timestamp,
+        meeting_index: u32,
+    },
+    SlashPartialStakeAt {
+        day_datetime: Timestamp,
+        meeting_index: u32,
+        minutes_late: u32,
+    },
+    ReturnStakeAt {
+        day_datetime: Timestamp,
+        meeting_index: u32,
+    },
+    /// Like `SlashFullStake`, with the day as the ISO 8601 string of its local midnight, e.g.
+    /// `2024-06-03T00:00:00+02:00`.
+    SlashFullStakeIso {
+        day_datetime: String,
+        meeting_index: u32,
+    },
+    SlashPartialStakeIso {
+        day_datetime: String,
+        meeting_index: u32,
+        minutes_late: u32,
+    },
+    ReturnStakeIso {
+        day_datetime: String,
+        meeting_index: u32,
+    },
+    /// Like `ReturnStake`, for the meeting of `requester` starting at `start_time`, which does
+    /// not change when other meetings of the day are removed.
+    ReturnStakeByKey {
+        day_datetime: Int64,
+        requester: String,
+        start_time: Int64,
+    },
     UpdateConfig {
         price_per_minute: Option<Uint128>,
         denom: Option<AssetEntry>,
+        self_gap_minutes: Option<u32>,
+        /// Replaces the denoms other than `denom` that stakes can be paid in, with their price
+        /// per minute. Each must resolve to a native denom in the name service.
+        accepted_denoms: Option<Vec<(AssetEntry, Uint128)>>,
+        denom_decimals: Option<u8>,
+        /// Replaces the weekdays meetings can be booked on, an empty list allows every day.
+        allowed_weekdays: Option<Vec<Weekday>>,
+        booking_fee: Option<Uint128>,
+        /// Seconds returned stake stays claimable before it can be swept, `0` sends returned
+        /// stake right away.
+        return_claim_deadline: Option<u64>,
+        /// Address that can attest attendance and resolve stakes besides the admin. An empty
+        /// address clears it.
+        attestor: Option<String>,
+        /// Whether meetings can end after the calendar hours close.
+        allow_partial_outside: Option<bool>,
+        /// Whether the part of a meeting after the calendar hours is priced as well.
+        price_outside_hours: Option<bool>,
+        /// Price oracle used to show the value of stakes. An empty address clears it.
+        price_source: Option<String>,
+        /// Whether resolving a stake checks that the balance covers every outstanding stake.
+        check_outstanding_balance: Option<bool>,
+        /// Seconds since local midnight that the start and end of meetings must be a multiple
+        /// of. Fails if upcoming meetings do not align to it unless `force` is set.
+        time_granularity_seconds: Option<u32>,
+        /// Whether the admin can book meetings in their own calendar.
+        allow_admin_booking: Option<bool>,
+        /// Whether meetings can be booked over other meetings.
+        overbooking_allowed: Option<bool>,
+        /// Price per minute in the quote currency of the price source that stakes are pegged
+        /// to. Zero prices stakes at `price_per_minute` again.
+        pegged_price_per_minute: Option<Decimal>,
+        slash_curve: Option<SlashCurve>,
+        /// Applies to meetings booked afterwards, booked meetings keep their deadline.
+        cancel_cutoff_secs: Option<u64>,
+        /// Profile shown by frontends listing calendars, see `AppQueryMsg::Profile`. An empty
+        /// value clears the field.
+        name: Option<String>,
+        description: Option<String>,
+        contact_uri: Option<String>,
+        /// Splits slashed stake between recipients, each with a share in basis points. The
+        /// shares must add up to 10000. Empty sends slashed stake to the admin again.
+        slash_distribution: Option<Vec<(String, u16)>>,
+        /// Applies changes that affect outstanding meetings anyway: a denom change while
+        /// meetings hold stake and a granularity upcoming meetings do not align to.
+        force: bool,
+    },
+    /// Returns every unhandled stake of `requester` on the days between `start_day` and
+    /// `end_day` (inclusive), handling at most `MAX_BATCH` meetings per call.
+    ReturnAllForRequester {
+        requester: String,
+        start_day: Int64,
+        end_day: Int64,
+    },
+    /// Deposits the attached funds as credit that `RequestMeeting` draws from when the
+    /// attached funds do not cover the stake.
+    Deposit {},
+    /// Withdraws unused credit.
+    Withdraw { amount: Uint128 },
+    /// Returns the stakes of the given `(day_datetime, meeting_index)` meetings, at most
+    /// `MAX_BATCH` per call. The indices refer to the state before the call, the returned
+    /// meetings are moved to the resolved meetings once the whole batch is handled.
+    ReturnStakes { meetings: Vec<(Int64, u32)> },
+    /// Writes exported meetings under their original day keys and replaces the config when
+    /// given. The config is checked like on instantiation, every day key must be a local
+    /// midnight given once, and every meeting must start on its day, last whole minutes, have
+    /// valid refund splits and be unresolved. The meetings of a day must not overlap unless
+    /// overbooking is allowed. Refuses to write a day that already has meetings unless `force`
+    /// is set, which overwrites it.
+    ImportState {
+        config: Option<Config>,
+        days: Vec<(i64, Vec<Meeting>)>,
+        force: bool,
+    },
+    /// Like `ImportState` without a config, writing the days of an export into the calendar.
+    /// Refuses to write a day that already has meetings unless `overwrite` is set.
+    Import {
+        days: Vec<(i64, Vec<Meeting>)>,
+        overwrite: bool,
+    },
+    /// Moves a meeting that has not started yet to new times. A cheaper meeting returns the
+    /// difference to the requester, a more expensive one requires the difference to be attached.
+    AmendMeeting {
+        day_datetime: Int64,
+        meeting_index: u32,
+        new_start: Int64,
+        new_end: Int64,
+    },
+    /// Moves the end of a meeting that has not ended yet to `new_end_time`, later the same day.
+    /// Only the requester can extend their meeting and must attach the additional stake.
+    ExtendMeeting {
+        day_datetime: Int64,
+        meeting_index: u32,
+        new_end_time: Int64,
+    },
+    /// Sends a queued refund to its recipient, who must claim it before its deadline.
+    ClaimRefund { refund_id: u64 },
+    /// Sends a queued refund that was not claimed before its deadline to the admin.
+    SweepUnclaimed { refund_id: u64 },
+    /// Records whether the requester attended a meeting. Callable by the admin or attestor.
+    Attest {
+        day_datetime: Int64,
+        meeting_index: u32,
+        attended: bool,
+    },
+    /// Returns the stake of an ended meeting attested as attended and slashes it otherwise.
+    /// Without an attestation a check-in returns the stake and a meeting ended early without
+    /// one is slashed. Callable by anyone. Sets a [`ResolutionResponse`] as the data of the
+    /// response.
+    SettleExpired {
+        day_datetime: Int64,
+        meeting_index: u32,
+    },
+    /// Holds a slot for the sender for `HOLD_TTL_SECONDS` at the stake quoted now, during which
+    /// no one else can book it.
+    HoldSlot { start_time: Int64, end_time: Int64 },
+    /// Books a held slot, attaching exactly the stake and booking fee quoted by the hold.
+    ConfirmHold { hold_id: u64 },
+    /// Flags the attested attendance of a meeting as disputed. Only the requester of the
+    /// meeting can dispute it, which keeps it from being settled until it is attested again.
+    DisputeAttendance {
+        day_datetime: Int64,
+        meeting_index: u32,
+    },
+    /// Records that the requester is present at their meeting. Only the requester can check in
+    /// and only while the meeting is in progress.
+    CheckIn {
+        day_datetime: Int64,
+        meeting_index: u32,
+    },
+    /// Stores `payload` on a meeting of the sender, or clears it when unset. The contract never
+    /// reads the payload, which is meant to be encrypted by the requester, and returns it with
+    /// the meeting. At most `MAX_ENCRYPTED_PAYLOAD_BYTES` long.
+    SetEncryptedPayload {
+        day_datetime: Int64,
+        meeting_index: u32,
+        payload: Option<Binary>,
+    },
+    /// Ends a meeting in progress now instead of at its scheduled end, after which it can be
+    /// resolved. Callable by the admin or attestor.
+    EndMeeting {
+        day_datetime: Int64,
+        meeting_index: u32,
+    },
+    /// Queues the sender for a slot that is taken, attaching the stake and booking fee up front.
+    /// The entry is booked once a cancellation frees the slot.
+    JoinWaitlist { start_time: Int64, end_time: Int64 },
+    /// Removes a waitlist entry of the sender for the day at `day_datetime` and refunds what
+    /// was paid for it.
+    LeaveWaitlist { day_datetime: Int64, entry_id: u64 },
+    /// Cancels a meeting that has not started yet and returns its stake. Only the requester
+    /// can cancel their meeting. The freed slot is booked for the oldest waitlist entries
+    /// that fit into it.
+    CancelMeeting {
+        day_datetime: Int64,
+        meeting_index: u32,
+    },
+    /// Makes `to` the requester of the meetings of the sender that have not started yet, for
+    /// moving to a new account. Their refund splits are cleared so that stakes return to `to`,
+    /// and they count as self-booked when `to` is the admin. Transfers at most `MAX_BATCH`
+    /// meetings from at most `MAX_BATCH` days after `start_after` per call. The `has_more`
+    /// attribute tells whether another call is needed, which resumes from the `next_key`
+    /// attribute passed as `start_after`.
+    TransferAllMeetings {
+        to: String,
+        start_after: Option<Int64>,
+    },
+    /// Blocks a slot in the calendar for the admin's own time, without stake. Callable by the
+    /// admin whether or not they can book meetings.
+    BlockSlot { start_time: Int64, end_time: Int64 },
+    /// Stops or resumes every payout of the contract: resolving stakes, refunds and their claims,
+    /// withdrawals and recoveries. Bookings and queries keep working while frozen. Callable by
+    /// the admin.
+    SetPayoutFreeze { frozen: bool },
+    /// Sends coins that were stranded in the contract to `recipient`. Only the part of the
+    /// balance not held as stake, queued refund or credit can be recovered.
+    RecoverFunds {
+        denom: String,
+        amount: Uint128,
+        recipient: String,
     },
 }
 
@@ -68,12 +396,198 @@ pub enum AppExecuteMsg {
 pub enum AppQueryMsg {
     #[returns(ConfigResponse)]
     Config {},
+    /// Returns the name, description and contact of the calendar without the rest of the
+    /// config.
+    #[returns(ProfileResponse)]
+    Profile {},
     #[returns(MeetingsResponse)]
     Meetings { datetime: i64 },
+    /// Returns the meetings of each of `days`, at most `MAX_LISTED_DAYS`, in the order given.
+    /// Days without meetings are listed with none.
+    #[returns(MeetingsForDaysResponse)]
+    MeetingsForDays { days: Vec<i64> },
+    /// Returns the funds that must be attached to a `RequestMeeting` with the given times.
+    #[returns(QuotePriceResponse)]
+    QuotePrice { start_time: Int64, end_time: Int64 },
+    /// Returns the accepted denom requiring the lowest stake for a meeting with the given times.
+    #[returns(QuotePriceResponse)]
+    CheapestDenom { start_time: Int64, end_time: Int64 },
+    /// Returns the contract version and the optional features that are enabled.
+    #[returns(InfoResponse)]
+    Info {},
+    #[returns(CreditResponse)]
+    Credit { address: String },
+    /// Returns the maximum number of mutually non-overlapping meetings booked on the given day.
+    #[returns(MaxNonOverlappingResponse)]
+    MaxNonOverlapping { day_datetime: i64 },
+    /// Returns aggregates over the meetings of the given day without listing them.
+    #[returns(DaySummaryResponse)]
+    DaySummary { day_datetime: i64 },
+    /// Returns a [`Paged`] list of the meetings that ended before `before` and whose stake has
+    /// not been handled yet, ordered by day and index.
+    #[returns(PendingResolutionsResponse)]
+    PendingResolutions {
+        before: Int64,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    },
+    /// Returns the config and the meetings of every day after `start_after`, ordered by day.
+    #[returns(ExportResponse)]
+    Export {
+        start_after: Option<i64>,
+        limit: Option<u32>,
+    },
+    /// Returns a [`Paged`] list of the meetings whose reminder is due between `now` and
+    /// `lookahead` seconds later, ordered by day and index, for a keeper polling it to send
+    /// notifications. `lookahead` covers at most `MAX_LISTED_DAYS` days, and only meetings
+    /// starting within that many days of `now` are listed.
+    #[returns(DueRemindersResponse)]
+    DueReminders {
+        now: Int64,
+        lookahead: u64,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    },
+    /// Returns a [`Paged`] list of the stake movements with their sequence number, oldest
+    /// first.
+    #[returns(AuditLogResponse)]
+    AuditLog {
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    },
+    /// Returns the additional stake `ExtendMeeting` would require to move the end of the
+    /// meeting to `new_end_time`, and whether it would run into another meeting.
+    #[returns(ExtensionQuoteResponse)]
+    ExtensionQuote {
+        day_datetime: Int64,
+        meeting_index: u32,
+        new_end_time: Int64,
+    },
+    /// Returns the number of distinct requesters of the meetings on the days between
+    /// `start_day` and `end_day` (inclusive), covering at most `MAX_LISTED_DAYS` days, leaving
+    /// out the admin's own bookings.
+    #[returns(UniqueRequestersResponse)]
+    UniqueRequesters { start_day: Int64, end_day: Int64 },
+    /// Returns the meetings on the days between `start_day` and `end_day` (inclusive), covering
+    /// at most `MAX_LISTED_DAYS` days, as iCalendar event fields, ordered by start time.
+    #[returns(ICalFeedResponse)]
+    ICalFeed { start_day: Int64, end_day: Int64 },
+    /// Returns the free minutes within the calendar hours of every day between `start_day` and
+    /// `end_day` (inclusive), which are local midnights, covering at most `MAX_HEATMAP_DAYS`
+    /// days. Days on a weekday that is not allowed have none.
+    #[returns(AvailabilityHeatmapResponse)]
+    AvailabilityHeatmap { start_day: Int64, end_day: Int64 },
+    /// Returns the longest gap between the meetings of the given day within the calendar hours.
+    #[returns(LongestFreeBlockResponse)]
+    LongestFreeBlock { day_datetime: i64 },
+    /// Returns the timestamps at which the calendar hours of the day keyed by `day_datetime`, a
+    /// local midnight, open and close.
+    #[returns(DayBoundsResponse)]
+    DayBounds { day_datetime: Int64 },
+    /// Returns a [`Paged`] list of the meetings on the days of the last
+    /// `ATTENTION_HORIZON_SECONDS` an admin has to act on, tagged with why: ended meetings whose
+    /// stake has not been handled and meetings whose attendance is disputed. Ordered by day and
+    /// index.
+    #[returns(NeedsAttentionResponse)]
+    NeedsAttention {
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    },
+    /// Returns the pairs of meetings of the given day that overlap, which only happens when
+    /// overbooking is allowed.
+    #[returns(OverlapsResponse)]
+    Overlaps { day_datetime: i64 },
+    /// Returns the times of the given day taken by meetings, merging meetings that overlap or
+    /// follow each other without a gap into a single block.
+    #[returns(BusyBlocksResponse)]
+    BusyBlocks { day_datetime: i64 },
+    /// Returns the value of the stake of a meeting in the quote currency of the price source.
+    #[returns(StakeValueResponse)]
+    StakeValueInQuote {
+        day_datetime: i64,
+        meeting_index: u32,
+    },
+    /// Returns the total stake of unresolved meetings in the config denom next to the balance
+    /// of the contract, which falls short when funds were moved out of the contract.
+    #[returns(OutstandingResponse)]
+    Outstanding {},
+    /// Returns a [`Paged`] list of the waitlist entries with their id, by day and oldest first
+    /// within a day.
+    #[returns(WaitlistResponse)]
+    Waitlist {
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    },
+    /// Returns a [`Paged`] list of the meetings of the day at `day_datetime` whose stake was
+    /// resolved, with how it was resolved, by resolution id.
+    #[returns(ResolvedMeetingsResponse)]
+    ResolvedMeetings {
+        day_datetime: Int64,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    },
+    /// Returns how a partial slash of `amount_staked` for a meeting of `duration_minutes` would
+    /// split it under the configured slash curve when the requester is `minutes_late`, without
+    /// looking at any meeting.
+    #[returns(SlashPreviewResponse)]
+    SlashPreview {
+        amount_staked: Uint128,
+        duration_minutes: u32,
+        minutes_late: u32,
+    },
+    /// Checks the meetings of up to `limit` days after `start_after` against the invariants the
+    /// calendar keeps and returns every violation found. Meetings are kept in booking order, as
+    /// their `meeting_index` is their position in the day, so their order is not checked.
+    #[returns(SelfTestResponse)]
+    SelfTest {
+        start_after: Option<i64>,
+        limit: Option<u32>,
+    },
+    /// Runs the checks of `RequestMeeting` for `sender` booking between `start_time` and
+    /// `end_time` in the config denom and returns the first one failing, with the config values
+    /// it depends on. The funds attached are not checked.
+    #[returns(DiagnoseResponse)]
+    Diagnose {
+        start_time: Int64,
+        end_time: Int64,
+        sender: String,
+    },
+    /// Runs `RequestMeeting` between `start_time` and `end_time` for `requester` against the
+    /// current state without booking anything. Without a requester the checks on the requester,
+    /// such as the self gap, pass.
+    #[returns(SimulateRequestMeetingResponse)]
+    SimulateRequestMeeting {
+        start_time: Int64,
+        end_time: Int64,
+        requester: Option<String>,
+    },
+}
+
+/// Query a price oracle set as `price_source` has to answer.
+#[cosmwasm_schema::cw_serde]
+#[derive(QueryResponses)]
+pub enum OracleQueryMsg {
+    /// Returns the price of one unit of `denom` in the quote currency of the oracle.
+    #[returns(OraclePriceResponse)]
+    Price { denom: String },
 }
 
 #[cosmwasm_schema::cw_serde]
-pub enum AppMigrateMsg {}
+pub struct OraclePriceResponse {
+    pub price: Decimal,
+    pub quote: String,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub enum AppMigrateMsg {
+    /// Runs the one-time migrations of the stored state.
+    Upgrade {},
+    /// Runs the one-time migrations, then moves the meetings of up to `limit` more days, at least
+    /// one and at most `MAX_BATCH`, to the day key of their start under the current
+    /// `utc_offset`, resuming after the last day the previous call checked. Call it again until
+    /// it reports `rebucket_done`.
+    RebucketDays { limit: u32 },
+}
 
 #[cosmwasm_schema::cw_serde]
 pub struct ConfigResponse {
@@ -81,9 +595,440 @@ pub struct ConfigResponse {
     pub utc_offset: i32,
     pub start_time: Time,
     pub end_time: Time,
+    pub self_gap_minutes: u32,
+    pub accepted_denoms: Vec<(String, Uint128)>,
+    pub denom_decimals: u8,
+    pub allowed_weekdays: Vec<Weekday>,
+    pub booking_fee: Uint128,
+    pub return_claim_deadline: Option<u64>,
+    pub attestor: Option<Addr>,
+    pub allow_partial_outside: bool,
+    pub price_outside_hours: bool,
+    pub price_source: Option<Addr>,
+    pub check_outstanding_balance: bool,
+    pub time_granularity_seconds: u32,
+    pub allow_admin_booking: bool,
+    pub overbooking_allowed: bool,
+    pub pegged_price_per_minute: Option<Decimal>,
+    pub slash_curve: SlashCurve,
+    pub cancel_cutoff_secs: u64,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub contact_uri: Option<String>,
+    pub slash_distribution: Vec<(Addr, u16)>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct ProfileResponse {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub contact_uri: Option<String>,
 }
 
 #[cosmwasm_schema::cw_serde]
 pub struct MeetingsResponse {
-    pub meetings: Vec<Meeting>,
+    pub meetings: Vec<MeetingResponse>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct MeetingsForDaysResponse {
+    /// The day keys of the query, each with its meetings.
+    pub days: Vec<(i64, Vec<MeetingResponse>)>,
+}
+
+/// A meeting as returned by queries and response data. Kept apart from the stored [`Meeting`]
+/// so that state fields can be added without changing the API.
+#[cosmwasm_schema::cw_serde]
+pub struct MeetingResponse {
+    pub start_time: i64,
+    pub end_time: i64,
+    /// `start_time` as an RFC 3339 datetime in the calendar's UTC offset.
+    pub start_local: String,
+    /// `end_time` as an RFC 3339 datetime in the calendar's UTC offset.
+    pub end_local: String,
+    pub requester: Addr,
+    pub amount_staked: Uint128,
+    pub denom: String,
+    /// Scheduled length of the meeting in minutes.
+    pub duration_minutes: u32,
+    pub status: MeetingStatus,
+    pub refund_splits: Option<Vec<(Addr, u16)>>,
+    pub reminder_minutes: Option<u32>,
+    pub free: bool,
+    pub attended: Option<bool>,
+    pub disputed: bool,
+    pub checked_in: bool,
+    pub ended_early_at: Option<i64>,
+    pub self_booked: bool,
+    pub cancel_deadline: Option<i64>,
+    pub encrypted_payload: Option<Binary>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub enum MeetingStatus {
+    Upcoming,
+    InProgress,
+    /// The meeting ended and its stake is waiting to be resolved.
+    Ended,
+    Resolved {
+        action: ResolutionAction,
+        resolver: Addr,
+        resolved_at: i64,
+        amount_to_requester: Uint128,
+        amount_to_admin: Uint128,
+    },
+}
+
+impl TryFrom<(&Meeting, &Config, &Env)> for MeetingResponse {
+    type Error = AppError;
+
+    fn try_from((meeting, config, env): (&Meeting, &Config, &Env)) -> AppResult<Self> {
+        let now = block_time(env)?;
+        let status = match &meeting.resolution {
+            Some(resolution) => MeetingStatus::Resolved {
+                action: resolution.action.clone(),
+                resolver: resolution.resolver.clone(),
+                resolved_at: resolution.resolved_at,
+                amount_to_requester: resolution.amount_to_requester,
+                amount_to_admin: resolution.amount_to_admin,
+            },
+            None if now < meeting.start_time => MeetingStatus::Upcoming,
+            None if meeting.has_ended(now) => MeetingStatus::Ended,
+            None => MeetingStatus::InProgress,
+        };
+        // Stored meetings were validated on booking, so their times are in range and ordered,
+        // as is the UTC offset of the config.
+        let local = |timestamp: i64| {
+            validate_utc_offset(config.utc_offset)
+                .and_then(|timezone| format_local(timezone, timestamp))
+                .unwrap_or_default()
+        };
+        Ok(MeetingResponse {
+            start_time: meeting.start_time,
+            end_time: meeting.end_time,
+            start_local: local(meeting.start_time),
+            end_local: local(meeting.end_time),
+            requester: meeting.requester.clone(),
+            amount_staked: meeting.amount_staked,
+            denom: meeting.stake_denom(config).to_string(),
+            duration_minutes: duration_in_minutes(meeting.start_time, meeting.end_time)
+                .unwrap_or_default(),
+            status,
+            refund_splits: meeting.refund_splits.clone(),
+            reminder_minutes: meeting.reminder_minutes,
+            free: meeting.free,
+            attended: meeting.attended,
+            disputed: meeting.disputed,
+            checked_in: meeting.checked_in,
+            ended_early_at: meeting.ended_early_at,
+            self_booked: meeting.self_booked,
+            cancel_deadline: meeting.cancel_deadline,
+            encrypted_payload: meeting.encrypted_payload.clone(),
+        })
+    }
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct QuotePriceResponse {
+    pub price: Coin,
+    /// `price.amount` formatted with the decimals of the config denom, or the raw amount for
+    /// other denoms.
+    pub display_amount: String,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct CreditResponse {
+    pub amount: Uint128,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct MaxNonOverlappingResponse {
+    pub count: u32,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct OverlapsResponse {
+    /// Indices of the overlapping meetings, the lower index first.
+    pub pairs: Vec<(u32, u32)>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct BusyBlocksResponse {
+    /// `(start_time, end_time)` of each block, ordered by start time.
+    pub blocks: Vec<(i64, i64)>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct DaySummaryResponse {
+    /// Number of meetings, leaving out those the admin booked in their own calendar.
+    pub count: u32,
+    /// Stake of the counted meetings.
+    pub total_staked: Uint128,
+    /// Number of meetings and blocked slots the admin booked in their own calendar.
+    pub self_booked: u32,
+    /// Start time of the earliest meeting, `None` if the day has no meetings.
+    pub first_start: Option<i64>,
+    /// End time of the latest meeting, `None` if the day has no meetings.
+    pub last_end: Option<i64>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct PendingResolution {
+    pub day_datetime: i64,
+    pub meeting_index: u32,
+    pub meeting: MeetingResponse,
+}
+
+/// A page of a list query, holding at most the `limit` of the query, which defaults to
+/// `DEFAULT_PAGE_LIMIT` and is capped at `MAX_PAGE_LIMIT`.
+#[cosmwasm_schema::cw_serde]
+pub struct Paged<T> {
+    pub items: Vec<T>,
+    /// Opaque cursor to pass as `start_after` for the next page, `None` on the last page.
+    pub next_key: Option<Binary>,
+    /// Number of entries across all pages, only set when it is known without reading them.
+    pub total: Option<u64>,
+    /// The limit the page was read with, after applying the default and the cap.
+    pub limit: u32,
+}
+
+pub type PendingResolutionsResponse = Paged<PendingResolution>;
+
+/// Why a meeting needs the attention of the admin.
+#[cosmwasm_schema::cw_serde]
+#[derive(Copy)]
+pub enum AttentionReason {
+    /// The meeting ended but its stake has not been handled.
+    Unsettled,
+    /// The requester disputed the attested attendance.
+    Disputed,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct AttentionItem {
+    pub day_datetime: i64,
+    pub meeting_index: u32,
+    pub meeting: MeetingResponse,
+    pub reason: AttentionReason,
+}
+
+pub type NeedsAttentionResponse = Paged<AttentionItem>;
+
+#[cosmwasm_schema::cw_serde]
+pub struct OutstandingResponse {
+    pub denom: String,
+    pub outstanding: Uint128,
+    pub balance: Uint128,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct StakeValueResponse {
+    pub stake: Coin,
+    /// Value of the stake in `quote`, `None` when no price source is set or it failed to answer.
+    pub value: Option<Decimal>,
+    pub quote: Option<String>,
+    /// Why the price source failed to answer.
+    pub error: Option<String>,
+}
+
+/// Meetings are exported as stored rather than as [`MeetingResponse`], so that they can be
+/// written back with `AppExecuteMsg::ImportState`.
+#[cosmwasm_schema::cw_serde]
+pub struct ExportResponse {
+    pub config: Config,
+    pub days: Vec<(i64, Vec<Meeting>)>,
+    /// The limit the days were read with, after applying the default and the cap.
+    pub limit: u32,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct DueReminder {
+    pub day_datetime: i64,
+    pub meeting_index: u32,
+    /// Unix timestamp at which the reminder is due.
+    pub remind_at: i64,
+    pub meeting: MeetingResponse,
+}
+
+pub type DueRemindersResponse = Paged<DueReminder>;
+
+pub type WaitlistResponse = Paged<(u64, WaitlistEntry)>;
+
+/// Data of the response to `AppExecuteMsg::RequestMeeting`, locating the booked meeting.
+#[cosmwasm_schema::cw_serde]
+pub struct RequestMeetingResponse {
+    pub day_datetime: i64,
+    pub meeting_index: u32,
+    pub amount_staked: Uint128,
+    pub meeting: MeetingResponse,
+}
+
+/// Data of the response to a stake resolution, with how the stake was split. The meeting is
+/// located by where it was before being resolved.
+#[cosmwasm_schema::cw_serde]
+pub struct ResolutionResponse {
+    pub day_datetime: i64,
+    pub meeting_index: u32,
+    pub action: ResolutionAction,
+    pub amount_to_requester: Uint128,
+    pub amount_to_admin: Uint128,
+    /// The meeting as resolved.
+    pub meeting: MeetingResponse,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct SlashPreviewResponse {
+    pub slashed: Uint128,
+    pub returned: Uint128,
+}
+
+/// Which invariant a stored meeting violates.
+#[cosmwasm_schema::cw_serde]
+pub enum ViolationReason {
+    /// The meeting does not start on the day it is stored under.
+    WrongDay,
+    /// The meeting does not end after it starts, or lasts longer than a day.
+    InvalidTimes,
+    /// The meeting starts outside the calendar hours, or ends after them although partially
+    /// outside meetings are not allowed.
+    OutsideCalendarHours,
+    /// The meeting overlaps the meeting at `other_index` of the same day although overbooking
+    /// is not allowed.
+    Overlaps { other_index: u32 },
+    /// The meeting is marked free but holds a stake, or holds none but is not marked free.
+    StakeMismatch,
+    /// The meeting was resolved but is still on the calendar.
+    Resolved,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct Violation {
+    pub day_datetime: i64,
+    pub meeting_index: u32,
+    pub reason: ViolationReason,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct SelfTestResponse {
+    pub days_checked: u32,
+    pub violations: Vec<Violation>,
+    /// The limit the days were read with, after applying the default and the cap.
+    pub limit: u32,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct DiagnoseResponse {
+    /// The first check the booking fails, `None` when it passes all of them.
+    pub failure: Option<BookingFailure>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct BookingFailure {
+    /// Stable code of the error `RequestMeeting` would return, see [`crate::error`].
+    pub code: String,
+    pub message: String,
+    /// The config fields the failing check depends on, as `(field, value)`.
+    pub config: Vec<(String, String)>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub enum SimulateRequestMeetingResponse {
+    /// The meeting would be booked at `meeting_index` of the day at `day_datetime` for `price`,
+    /// the stake and booking fee to attach in the config denom.
+    Accepted {
+        day_datetime: i64,
+        meeting_index: u32,
+        price: Coin,
+    },
+    /// `RequestMeeting` would fail with the error of `failure`.
+    Rejected { failure: BookingFailure },
+}
+
+pub type ResolvedMeetingsResponse = Paged<(u64, MeetingResponse)>;
+
+pub type AuditLogResponse = Paged<(u64, StakeEvent)>;
+
+#[cosmwasm_schema::cw_serde]
+pub struct ExtensionQuoteResponse {
+    pub additional_stake: Coin,
+    /// Whether the extension overlaps the next meeting, in which case it would be rejected.
+    pub conflicts: bool,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct UniqueRequestersResponse {
+    pub count: u32,
+}
+
+/// The fields of a `VEVENT` for a booked meeting.
+#[cosmwasm_schema::cw_serde]
+pub struct ICalEvent {
+    /// `{day_datetime}-{meeting_index}@{contract}`, unique across the calendar even when
+    /// overbooked meetings share a start time.
+    pub uid: String,
+    /// Unix timestamp of the start of the meeting.
+    pub dtstart: i64,
+    /// Unix timestamp of the end of the meeting.
+    pub dtend: i64,
+    pub summary: String,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct AvailabilityHeatmapResponse {
+    /// `(day_datetime, free_minutes)` of each day, in order.
+    pub days: Vec<(i64, u32)>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct ICalFeedResponse {
+    pub events: Vec<ICalEvent>,
+}
+
+/// A free interval between two unix timestamps.
+#[cosmwasm_schema::cw_serde]
+pub struct Slot {
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct DayBoundsResponse {
+    pub open_ts: i64,
+    pub close_ts: i64,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct LongestFreeBlockResponse {
+    /// `None` when the calendar hours of the day are fully booked.
+    pub slot: Option<Slot>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct InfoResponse {
+    pub contract: String,
+    pub version: String,
+    pub features: FeatureFlags,
+}
+
+/// Optional calendar features and whether they are currently active.
+#[cosmwasm_schema::cw_serde]
+pub struct FeatureFlags {
+    /// Bookings do not require a stake.
+    pub free_mode: bool,
+    /// Meetings can be booked over other meetings.
+    pub overbooking_allowed: bool,
+    /// Meetings can end after the calendar hours close.
+    pub allow_partial_outside: bool,
+    /// The part of a meeting after the calendar hours is priced as well.
+    pub price_outside_hours: bool,
+    /// Resolving a stake checks that the balance covers every outstanding stake.
+    pub check_outstanding_balance: bool,
+    /// The admin can book meetings in their own calendar.
+    pub allow_admin_booking: bool,
+    /// Stakes are priced in the quote currency of the price source.
+    pub pegged_pricing: bool,
+    /// Returned stake is queued for its recipient to claim instead of sent right away.
+    pub claimable_refunds: bool,
+    /// Stakes cannot be paid out until the admin unfreezes payouts.
+    pub payouts_frozen: bool,
 }
@@ -0,0 +1,123 @@
+use crate::contract::AppResult;
+use crate::error::AppError;
+use crate::msg::Slot;
+use crate::state::Meeting;
+use crate::validation::meeting_index_at;
+
+/// Returns whether `time` lies in the calendar window `[open, close)`.
+///
+/// The window is half-open like meetings themselves: a meeting can start at `open` but not at
+/// `close`, and correspondingly end at `close` but not at `open`. Used for local times of the
+/// day as well as timestamps.
+pub fn in_calendar_window<T: PartialOrd>(open: T, close: T, time: T) -> bool {
+    open <= time && time < close
+}
+
+/// Returns the maximum number of mutually non-overlapping meetings among `meetings`.
+///
+/// Meetings that touch at their boundaries do not overlap.
+pub fn max_non_overlapping(meetings: &[Meeting]) -> u32 {
+    let mut intervals: Vec<(i64, i64)> = meetings
+        .iter()
+        .map(|meeting| (meeting.start_time, meeting.end_time))
+        .collect();
+    intervals.sort_by_key(|&(_, end_time)| end_time);
+
+    let mut count = 0;
+    let mut last_end_time = i64::MIN;
+    for (start_time, end_time) in intervals {
+        if start_time >= last_end_time {
+            count += 1;
+            last_end_time = end_time;
+        }
+    }
+    count
+}
+
+/// Returns the index pairs of the meetings that overlap, ordered by the lower index first.
+///
+/// Meetings that touch at their boundaries do not overlap.
+pub fn overlapping_pairs(meetings: &[Meeting]) -> AppResult<Vec<(u32, u32)>> {
+    let mut pairs = vec![];
+    for (index, meeting) in meetings.iter().enumerate() {
+        for (other_index, other) in meetings.iter().enumerate().skip(index + 1) {
+            if meeting.start_time < other.end_time && other.start_time < meeting.end_time {
+                pairs.push((meeting_index_at(index)?, meeting_index_at(other_index)?));
+            }
+        }
+    }
+    Ok(pairs)
+}
+
+/// Returns the `(start_time, end_time)` spans covered by `meetings`, ordered by start time.
+///
+/// Meetings that overlap or touch at their boundaries are merged into a single span.
+pub fn busy_blocks(meetings: &[Meeting]) -> Vec<(i64, i64)> {
+    let mut intervals: Vec<(i64, i64)> = meetings
+        .iter()
+        .map(|meeting| (meeting.start_time, meeting.end_time))
+        .collect();
+    intervals.sort_unstable();
+
+    let mut blocks: Vec<(i64, i64)> = vec![];
+    for (start_time, end_time) in intervals {
+        match blocks.last_mut() {
+            Some(last) if start_time <= last.1 => last.1 = last.1.max(end_time),
+            _ => blocks.push((start_time, end_time)),
+        }
+    }
+    blocks
+}
+
+/// Returns the gaps between `meetings` within `open..close`, ordered by start time. Only gaps
+/// starting [`in_calendar_window`] are listed, so every gap can be booked.
+pub fn free_slots(meetings: &[Meeting], open: i64, close: i64) -> Vec<Slot> {
+    let mut intervals: Vec<(i64, i64)> = meetings
+        .iter()
+        .map(|meeting| (meeting.start_time, meeting.end_time))
+        .collect();
+    intervals.sort_unstable();
+
+    let mut slots = vec![];
+    let mut free_from = open;
+    for (start_time, end_time) in intervals {
+        if start_time > free_from {
+            slots.push(Slot {
+                start_time: free_from,
+                end_time: start_time.min(close),
+            });
+        }
+        free_from = free_from.max(end_time);
+        if !in_calendar_window(open, close, free_from) {
+            return slots;
+        }
+    }
+    if in_calendar_window(open, close, free_from) {
+        slots.push(Slot {
+            start_time: free_from,
+            end_time: close,
+        });
+    }
+    slots
+}
+
+/// Returns the total length in minutes of the [`free_slots`].
+pub fn free_minutes(meetings: &[Meeting], open: i64, close: i64) -> AppResult<u32> {
+    let seconds: i64 = free_slots(meetings, open, close)
+        .iter()
+        .map(|slot| slot.end_time - slot.start_time)
+        .sum();
+    u32::try_from(seconds / 60).map_err(|_| AppError::ConversionOverflow {
+        value: (seconds / 60).to_string(),
+        target: "u32".to_string(),
+    })
+}
+
+/// Returns the longest of the [`free_slots`], the earliest one on ties, or `None` when there is
+/// no gap.
+pub fn longest_free_slot(meetings: &[Meeting], open: i64, close: i64) -> Option<Slot> {
+    free_slots(meetings, open, close)
+        .into_iter()
+        .rev()
+        .max_by_key(|slot| slot.end_time - slot.start_time)
+}
@@ -1,7 +1,11 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_schema::serde::{Deserialize, Serialize};
+use cosmwasm_std::{
+    Addr, Api, Binary, CanonicalAddr, Coin, ConversionOverflowError, Decimal, Order, StdError,
+    StdResult, Storage, Timestamp, Uint128,
+};
+use cw_storage_plus::{Bound, Item, Map, Path};
 
-use crate::msg::Time;
+use crate::msg::{Time, Weekday};
 
 #[cosmwasm_schema::cw_serde]
 pub struct Config {
@@ -10,6 +14,124 @@ pub struct Config {
     pub utc_offset: i32,
     pub start_time: Time,
     pub end_time: Time,
+    /// Minimum number of minutes between two meetings of the same requester on a day.
+    #[serde(default)]
+    pub self_gap_minutes: u32,
+    /// Denoms other than `denom` that stakes can be paid in, with their price per minute.
+    #[serde(default)]
+    pub accepted_denoms: Vec<(String, Uint128)>,
+    /// Number of decimals of `denom`, used to display quoted amounts.
+    #[serde(default)]
+    pub denom_decimals: u8,
+    /// Local weekdays meetings can be booked on, every day when empty.
+    #[serde(default)]
+    pub allowed_weekdays: Vec<Weekday>,
+    /// Fee charged on top of the stake of every booking, in the denom of the stake. It is paid
+    /// to the admin right away and never returned.
+    #[serde(default)]
+    pub booking_fee: Uint128,
+    /// Seconds returned stake stays claimable by its recipient before the admin can sweep it.
+    /// Returned stake is sent right away when unset.
+    #[serde(default)]
+    pub return_claim_deadline: Option<u64>,
+    /// Address that can attest attendance and resolve stakes besides the admin.
+    #[serde(default)]
+    pub attestor: Option<Addr>,
+    /// Whether meetings starting within the calendar hours can end after they close, on the
+    /// same day.
+    #[serde(default)]
+    pub allow_partial_outside: bool,
+    /// Whether the part of a meeting after the calendar hours is priced as well. Only the part
+    /// within them is priced when unset.
+    #[serde(default)]
+    pub price_outside_hours: bool,
+    /// Price oracle answering `OracleQueryMsg::Price`, used to show the value of stakes.
+    #[serde(default)]
+    pub price_source: Option<Addr>,
+    /// Whether resolving a stake first checks that the balance of the contract covers every
+    /// outstanding stake in its denom, not only the one being paid out.
+    #[serde(default)]
+    pub check_outstanding_balance: bool,
+    /// Seconds since local midnight that the start and end of meetings must be a multiple of,
+    /// one minute when unset.
+    #[serde(default)]
+    pub time_granularity_seconds: u32,
+    /// Whether the admin can book meetings in their own calendar.
+    #[serde(default)]
+    pub allow_admin_booking: bool,
+    /// Whether meetings can be booked over other meetings, e.g. for waitlists.
+    #[serde(default)]
+    pub overbooking_allowed: bool,
+    /// Price per minute in the quote currency of `price_source`. When set, stakes are converted
+    /// to their denom at the oracle price when booking, fixing their value in the quote
+    /// currency, and `price_per_minute` and the accepted denom prices are not used.
+    #[serde(default)]
+    pub pegged_price_per_minute: Option<Decimal>,
+    /// How much of the stake a partial slash takes for the minutes the requester was late.
+    #[serde(default)]
+    pub slash_curve: SlashCurve,
+    /// Seconds before the start of a meeting after which it can no longer be cancelled. The
+    /// resulting deadline is stored on the meeting when it is booked.
+    #[serde(default)]
+    pub cancel_cutoff_secs: u64,
+    /// Profile shown by frontends listing calendars. Calendars created before it was stored
+    /// have none.
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub contact_uri: Option<String>,
+    /// Recipients of slashed stake with their share in basis points, adding up to 10000. Slashed
+    /// stake goes to the admin when empty.
+    #[serde(default)]
+    pub slash_distribution: Vec<(Addr, u16)>,
+}
+
+/// How the part of the stake taken by a partial slash grows with the minutes the requester was
+/// late. Every curve rounds the slashed part down.
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+pub enum SlashCurve {
+    /// Slashes the share of the meeting the requester missed.
+    #[default]
+    Linear,
+    /// Slashes the square of the share of the meeting the requester missed, forgiving being
+    /// slightly late more than `Linear`.
+    Quadratic,
+    /// Slashes nothing up to `grace_minutes` late and everything from `full_after_minutes`
+    /// late, growing linearly in between.
+    Threshold {
+        grace_minutes: u32,
+        full_after_minutes: u32,
+    },
+}
+
+impl Config {
+    /// Returns the granularity meeting times must align to in seconds.
+    pub fn granularity_seconds(&self) -> u32 {
+        match self.time_granularity_seconds {
+            0 => 60,
+            granularity_seconds => granularity_seconds,
+        }
+    }
+
+    /// Returns the deadline for cancelling a meeting starting at `start_time` under the current
+    /// cutoff.
+    pub fn cancel_deadline(&self, start_time: i64) -> i64 {
+        start_time.saturating_sub(i64::try_from(self.cancel_cutoff_secs).unwrap_or(i64::MAX))
+    }
+
+    /// Returns the price per minute of stakes paid in `denom`, `None` if it is not accepted.
+    pub fn price_per_minute_in(&self, denom: &str) -> Option<Uint128> {
+        if denom == self.denom {
+            return Some(self.price_per_minute);
+        }
+        self.accepted_denoms
+            .iter()
+            .find(|(accepted, _)| accepted == denom)
+            .map(|(_, price_per_minute)| *price_per_minute)
+    }
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -18,8 +140,409 @@ pub struct Meeting {
     pub end_time: i64,
     pub requester: Addr,
     pub amount_staked: Uint128,
+    /// Recipients of returned stake and their share in basis points. Returned stake goes to
+    /// the requester when unset.
+    #[serde(default)]
+    pub refund_splits: Option<Vec<(Addr, u16)>>,
+    /// Denom of the stake. Meetings booked before other denoms were accepted are staked in the
+    /// config denom.
+    #[serde(default)]
+    pub denom: Option<String>,
+    /// Minutes before the start of the meeting at which a reminder is due.
+    #[serde(default)]
+    pub reminder_minutes: Option<u32>,
+    /// Whether the meeting was booked at no cost, in which case there is no stake to resolve.
+    #[serde(default)]
+    pub free: bool,
+    /// Whether the requester attended the meeting, as attested by the admin or attestor.
+    #[serde(default)]
+    pub attended: Option<bool>,
+    /// Whether the requester disputed the attested attendance. Disputed meetings cannot be
+    /// settled until their attendance is attested again.
+    #[serde(default)]
+    pub disputed: bool,
+    /// Whether the requester checked in while the meeting was in progress.
+    #[serde(default)]
+    pub checked_in: bool,
+    /// When the admin or attestor ended the meeting before its scheduled end.
+    #[serde(default)]
+    pub ended_early_at: Option<i64>,
+    /// Whether the admin booked the meeting in their own calendar, which statistics leave out.
+    #[serde(default)]
+    pub self_booked: bool,
+    /// How the stake of the meeting was resolved. Unset for unresolved meetings and for
+    /// meetings resolved before resolutions were recorded.
+    #[serde(default)]
+    pub resolution: Option<Resolution>,
+    /// Until when the requester can cancel the meeting, fixed when it was booked. Meetings
+    /// booked before deadlines were stored can be cancelled until they start.
+    #[serde(default)]
+    pub cancel_deadline: Option<i64>,
+    /// Opaque data the requester attached to the meeting, encrypted off-chain. Never read by the
+    /// contract.
+    #[serde(default)]
+    pub encrypted_payload: Option<Binary>,
+}
+
+impl Meeting {
+    /// Returns when the meeting ended or is scheduled to end.
+    pub fn ended_at(&self) -> i64 {
+        self.ended_early_at.unwrap_or(self.end_time)
+    }
+
+    /// Returns whether the meeting has ended at `now`. A meeting covers `[start_time, end)`, so
+    /// it has ended from the second it ends at, which is also when its stake can be resolved.
+    pub fn has_ended(&self, now: i64) -> bool {
+        now >= self.ended_at()
+    }
+
+    /// Returns the denom the stake of this meeting is held in.
+    pub fn stake_denom<'a>(&'a self, config: &'a Config) -> &'a str {
+        self.denom.as_deref().unwrap_or(&config.denom)
+    }
+}
+
+#[cosmwasm_schema::cw_serde]
+pub enum ResolutionAction {
+    /// The stake was returned to the requester.
+    Return,
+    /// The stake was slashed to the admin.
+    FullSlash,
+    /// The stake was split between the requester and the admin for a late requester.
+    PartialSlash { minutes_late: u32 },
+    /// The meeting was free and had no stake to resolve.
+    Free,
+}
+
+/// A [`Meeting`] as stored in the calendar, with short keys, timestamps as unsigned seconds,
+/// addresses as canonical bytes and unset or default fields left out, as busy days are read and
+/// written whole.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "cosmwasm_schema::serde")]
+pub struct StoredMeeting {
+    #[serde(rename = "s")]
+    pub start_time: u64,
+    #[serde(rename = "e")]
+    pub end_time: u64,
+    #[serde(rename = "r")]
+    pub requester: CanonicalAddr,
+    #[serde(rename = "a", default, skip_serializing_if = "Uint128::is_zero")]
+    pub amount_staked: Uint128,
+    #[serde(rename = "rs", default, skip_serializing_if = "Option::is_none")]
+    pub refund_splits: Option<Vec<(CanonicalAddr, u16)>>,
+    #[serde(rename = "d", default, skip_serializing_if = "Option::is_none")]
+    pub denom: Option<String>,
+    #[serde(rename = "rm", default, skip_serializing_if = "Option::is_none")]
+    pub reminder_minutes: Option<u32>,
+    #[serde(rename = "f", default, skip_serializing_if = "is_false")]
+    pub free: bool,
+    #[serde(rename = "at", default, skip_serializing_if = "Option::is_none")]
+    pub attended: Option<bool>,
+    #[serde(rename = "di", default, skip_serializing_if = "is_false")]
+    pub disputed: bool,
+    #[serde(rename = "ci", default, skip_serializing_if = "is_false")]
+    pub checked_in: bool,
+    #[serde(rename = "ee", default, skip_serializing_if = "Option::is_none")]
+    pub ended_early_at: Option<i64>,
+    #[serde(rename = "sb", default, skip_serializing_if = "is_false")]
+    pub self_booked: bool,
+    #[serde(rename = "re", default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<Resolution>,
+    #[serde(rename = "cd", default, skip_serializing_if = "Option::is_none")]
+    pub cancel_deadline: Option<i64>,
+    #[serde(rename = "ep", default, skip_serializing_if = "Option::is_none")]
+    pub encrypted_payload: Option<Binary>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl StoredMeeting {
+    /// Encodes `meeting` as stored in the calendar.
+    pub fn from_meeting(api: &dyn Api, meeting: &Meeting) -> StdResult<Self> {
+        let seconds = |timestamp: i64| {
+            u64::try_from(timestamp).map_err(|_| {
+                StdError::from(ConversionOverflowError::new(
+                    "i64",
+                    "u64",
+                    timestamp.to_string(),
+                ))
+            })
+        };
+        Ok(StoredMeeting {
+            start_time: seconds(meeting.start_time)?,
+            end_time: seconds(meeting.end_time)?,
+            requester: api.addr_canonicalize(meeting.requester.as_str())?,
+            amount_staked: meeting.amount_staked,
+            refund_splits: meeting
+                .refund_splits
+                .as_ref()
+                .map(|splits| {
+                    splits
+                        .iter()
+                        .map(|(recipient, bps)| {
+                            Ok((api.addr_canonicalize(recipient.as_str())?, *bps))
+                        })
+                        .collect::<StdResult<Vec<_>>>()
+                })
+                .transpose()?,
+            denom: meeting.denom.clone(),
+            reminder_minutes: meeting.reminder_minutes,
+            free: meeting.free,
+            attended: meeting.attended,
+            disputed: meeting.disputed,
+            checked_in: meeting.checked_in,
+            ended_early_at: meeting.ended_early_at,
+            self_booked: meeting.self_booked,
+            resolution: meeting.resolution.clone(),
+            cancel_deadline: meeting.cancel_deadline,
+            encrypted_payload: meeting.encrypted_payload.clone(),
+        })
+    }
+
+    /// Decodes the meeting as stored in the calendar.
+    pub fn into_meeting(self, api: &dyn Api) -> StdResult<Meeting> {
+        let seconds = |timestamp: u64| {
+            i64::try_from(timestamp).map_err(|_| {
+                StdError::from(ConversionOverflowError::new(
+                    "u64",
+                    "i64",
+                    timestamp.to_string(),
+                ))
+            })
+        };
+        Ok(Meeting {
+            start_time: seconds(self.start_time)?,
+            end_time: seconds(self.end_time)?,
+            requester: api.addr_humanize(&self.requester)?,
+            amount_staked: self.amount_staked,
+            refund_splits: self
+                .refund_splits
+                .map(|splits| {
+                    splits
+                        .into_iter()
+                        .map(|(recipient, bps)| Ok((api.addr_humanize(&recipient)?, bps)))
+                        .collect::<StdResult<Vec<_>>>()
+                })
+                .transpose()?,
+            denom: self.denom,
+            reminder_minutes: self.reminder_minutes,
+            free: self.free,
+            attended: self.attended,
+            disputed: self.disputed,
+            checked_in: self.checked_in,
+            ended_early_at: self.ended_early_at,
+            self_booked: self.self_booked,
+            resolution: self.resolution,
+            cancel_deadline: self.cancel_deadline,
+            encrypted_payload: self.encrypted_payload,
+        })
+    }
+}
+
+/// Encodes the meetings of a day as stored in the calendar.
+pub fn to_stored(api: &dyn Api, meetings: &[Meeting]) -> StdResult<Vec<StoredMeeting>> {
+    meetings
+        .iter()
+        .map(|meeting| StoredMeeting::from_meeting(api, meeting))
+        .collect()
+}
+
+/// Decodes the meetings of a day as stored in the calendar.
+pub fn from_stored(api: &dyn Api, meetings: Vec<StoredMeeting>) -> StdResult<Vec<Meeting>> {
+    meetings
+        .into_iter()
+        .map(|meeting| meeting.into_meeting(api))
+        .collect()
+}
+
+/// The meetings of each day keyed by its local midnight, stored as [`StoredMeeting`]s and read
+/// and written as [`Meeting`]s like a `Map<i64, Vec<Meeting>>`.
+pub struct Calendar(Map<'static, i64, Vec<StoredMeeting>>);
+
+impl Calendar {
+    pub fn key(&self, day_datetime: i64) -> Path<Vec<StoredMeeting>> {
+        self.0.key(day_datetime)
+    }
+
+    pub fn has(&self, storage: &dyn Storage, day_datetime: i64) -> bool {
+        self.0.has(storage, day_datetime)
+    }
+
+    pub fn load(
+        &self,
+        storage: &dyn Storage,
+        api: &dyn Api,
+        day_datetime: i64,
+    ) -> StdResult<Vec<Meeting>> {
+        from_stored(api, self.0.load(storage, day_datetime)?)
+    }
+
+    pub fn may_load(
+        &self,
+        storage: &dyn Storage,
+        api: &dyn Api,
+        day_datetime: i64,
+    ) -> StdResult<Option<Vec<Meeting>>> {
+        self.0
+            .may_load(storage, day_datetime)?
+            .map(|meetings| from_stored(api, meetings))
+            .transpose()
+    }
+
+    pub fn save(
+        &self,
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        day_datetime: i64,
+        meetings: &[Meeting],
+    ) -> StdResult<()> {
+        self.0
+            .save(storage, day_datetime, &to_stored(api, meetings)?)
+    }
+
+    pub fn remove(&self, storage: &mut dyn Storage, day_datetime: i64) {
+        self.0.remove(storage, day_datetime)
+    }
+
+    pub fn range<'c>(
+        &self,
+        storage: &'c dyn Storage,
+        api: &'c dyn Api,
+        min: Option<Bound<'static, i64>>,
+        max: Option<Bound<'static, i64>>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<(i64, Vec<Meeting>)>> + 'c> {
+        Box::new(self.0.range(storage, min, max, order).map(move |day| {
+            let (day_datetime, meetings) = day?;
+            Ok((day_datetime, from_stored(api, meetings)?))
+        }))
+    }
+
+    pub fn keys<'c>(
+        &self,
+        storage: &'c dyn Storage,
+        min: Option<Bound<'static, i64>>,
+        max: Option<Bound<'static, i64>>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<i64>> + 'c> {
+        self.0.keys(storage, min, max, order)
+    }
+}
+
+/// Who resolved the stake of a meeting, when, and where the stake went.
+#[cosmwasm_schema::cw_serde]
+pub struct Resolution {
+    pub action: ResolutionAction,
+    /// The sender of the message that resolved the stake.
+    pub resolver: Addr,
+    pub resolved_at: i64,
+    /// Stake sent to the requester or the recipients of its refund splits.
+    pub amount_to_requester: Uint128,
+    pub amount_to_admin: Uint128,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub enum StakeMovement {
+    /// Stake sent back to the requester or the recipients of its refund splits.
+    Return,
+    /// Stake sent to the admin.
+    Slash,
+    /// Queued returned stake sent to its recipient, who claimed it.
+    Claim,
+    /// Queued returned stake left unclaimed past its deadline, sent to the admin.
+    Sweep,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct StakeEvent {
+    pub movement: StakeMovement,
+    /// The sender of the message that moved the stake.
+    pub actor: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub denom: String,
+    pub timestamp: Timestamp,
+}
+
+/// Returned stake waiting to be claimed by its recipient.
+#[cosmwasm_schema::cw_serde]
+pub struct QueuedRefund {
+    pub recipient: Addr,
+    pub amount: Coin,
+    /// Time after which the refund can no longer be claimed and the admin can sweep it.
+    pub claim_deadline: Timestamp,
+}
+
+/// A slot reserved for `requester` until it is paid for with `ConfirmHold`.
+#[cosmwasm_schema::cw_serde]
+pub struct Hold {
+    pub requester: Addr,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Stake quoted when the slot was held.
+    pub stake: Coin,
+    /// Booking fee quoted when the slot was held, paid on top of the stake.
+    pub booking_fee: Uint128,
+    /// Time after which the hold can no longer be confirmed.
+    pub expires_at: Timestamp,
+}
+
+/// A booking waiting for its slot to be freed, paid for up front.
+#[cosmwasm_schema::cw_serde]
+pub struct WaitlistEntry {
+    pub requester: Addr,
+    /// Key of the day the slot is on.
+    pub day_datetime: i64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub stake: Coin,
+    /// Booking fee paid on top of the stake, paid out to the admin once the entry is booked.
+    pub booking_fee: Uint128,
 }
 
 // unix start-time of the day -> vector of meetings in that day.
-pub const CALENDAR: Map<i64, Vec<Meeting>> = Map::new("calendar");
+pub const CALENDAR: Calendar = Calendar(Map::new("calendar"));
+// whether the days of the calendar are stored as `StoredMeeting`s, see `migrate_handler`.
+pub const COMPACT_MEETINGS: Item<bool> = Item::new("compact_meetings");
 pub const CONFIG: Item<Config> = Item::new("config");
+// whether the day keys of the calendar are local midnights, see `migrate_handler`.
+pub const LOCAL_DAY_KEYS: Item<bool> = Item::new("local_day_keys");
+// last day key checked by an unfinished `RebucketDays` migration.
+pub const REBUCKET_CURSOR: Item<i64> = Item::new("rebucket_cursor");
+// requester -> deposited funds that can be used to pay for future meetings.
+pub const CREDITS: Map<&Addr, Uint128> = Map::new("credits");
+// total of `CREDITS`, kept up to date by every deposit, withdrawal and use of credit.
+pub const CREDITS_TOTAL: Item<Uint128> = Item::new("credits_total");
+// sequence number -> stake movement, append-only.
+pub const AUDIT_LOG: Map<u64, StakeEvent> = Map::new("audit_log");
+// sequence number of the next audit log entry.
+pub const AUDIT_LOG_SEQ: Item<u64> = Item::new("audit_log_seq");
+// refund id -> returned stake waiting to be claimed.
+pub const QUEUED_REFUNDS: Map<u64, QueuedRefund> = Map::new("queued_refunds");
+// id of the next queued refund.
+pub const QUEUED_REFUNDS_SEQ: Item<u64> = Item::new("queued_refunds_seq");
+// denom -> total of the unclaimed `QUEUED_REFUNDS`.
+pub const QUEUED_REFUNDS_TOTAL: Map<&str, Uint128> = Map::new("queued_refunds_total");
+// (day_datetime, resolution id) -> meeting removed from its day once its stake was resolved.
+pub const RESOLVED_MEETINGS: Map<(i64, u64), Meeting> = Map::new("resolved_meetings");
+// id of the next resolved meeting.
+pub const RESOLVED_MEETINGS_SEQ: Item<u64> = Item::new("resolved_meetings_seq");
+// hold id -> slot waiting to be paid for.
+pub const HOLDS: Map<u64, Hold> = Map::new("holds");
+// id of the next hold.
+pub const HOLDS_SEQ: Item<u64> = Item::new("holds_seq");
+// number of meetings whose stake is held by the contract, kept up to date by every write of a
+// day that changes stakes.
+pub const STAKED_MEETINGS: Item<u32> = Item::new("staked_meetings");
+// denom -> total stake of unresolved meetings, kept up to date alongside `STAKED_MEETINGS`.
+pub const OUTSTANDING: Map<&str, Uint128> = Map::new("outstanding");
+// (day_datetime, entry id) -> booking waiting for its slot on that day, promoted oldest first.
+pub const WAITLIST: Map<(i64, u64), WaitlistEntry> = Map::new("waitlist_by_day");
+// id of the next waitlist entry.
+pub const WAITLIST_SEQ: Item<u64> = Item::new("waitlist_seq");
+// denom -> total stake and booking fee paid by the `WAITLIST` entries.
+pub const WAITLIST_TOTAL: Map<&str, Uint128> = Map::new("waitlist_total");
+// whether every payout of the contract is stopped, see `SetPayoutFreeze`.
+pub const PAYOUTS_FROZEN: Item<bool> = Item::new("payouts_frozen");
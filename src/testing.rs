@@ -0,0 +1,203 @@
+//! Helpers for tests running the app on a cw-orch [`Mock`] environment.
+//!
+//! Dates are given as `%Y-%m-%d` and times as `%H:%M`, both local to the app's configured
+//! `utc_offset`.
+
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+
+use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{DepsMut, Env, Order, QuerierWrapper, Record, Storage, Timestamp};
+use cw_orch::{anyhow, prelude::*};
+
+use crate::contract::{interface::AppInterface, APP};
+use crate::state::{Meeting, CALENDAR};
+
+/// Returns the unix timestamp of the given local date and time.
+pub fn local_timestamp(app: &AppInterface<Mock>, date: &str, time: &str) -> anyhow::Result<i64> {
+    Ok(app.local_datetime(date, time)?.timestamp())
+}
+
+/// Sets the block time to the given local date and time.
+pub fn set_local_time(app: &AppInterface<Mock>, date: &str, time: &str) -> anyhow::Result<()> {
+    let timestamp = local_timestamp(app, date, time)?;
+    app.get_chain().app.borrow_mut().update_block(|block| {
+        block.time = Timestamp::from_seconds(timestamp as u64);
+    });
+    Ok(())
+}
+
+/// Moves the block time forward by `minutes`.
+pub fn advance_minutes(app: &AppInterface<Mock>, minutes: u64) -> anyhow::Result<()> {
+    app.get_chain().wait_seconds(minutes * 60)?;
+    Ok(())
+}
+
+/// Clears the admin of the app, as if it had been renounced.
+pub fn clear_admin(app: &AppInterface<Mock>) -> anyhow::Result<()> {
+    let address = app.address()?;
+    let chain = app.get_chain();
+    let mut mock = chain.app.borrow_mut();
+    let mut storage = mock.contract_storage_mut(&address);
+    let querier: MockQuerier = MockQuerier::new(&[]);
+    APP.admin.set(
+        DepsMut {
+            storage: storage.as_mut(),
+            api: &MockApi::default(),
+            querier: QuerierWrapper::new(&querier),
+        },
+        None,
+    )?;
+    Ok(())
+}
+
+/// Writes `meetings` as the meetings of `day_datetime`, bypassing every check of the app, as if
+/// its storage had been corrupted.
+pub fn write_day(
+    app: &AppInterface<Mock>,
+    day_datetime: i64,
+    meetings: &[Meeting],
+) -> anyhow::Result<()> {
+    let address = app.address()?;
+    let chain = app.get_chain();
+    let mut mock = chain.app.borrow_mut();
+    let mut storage = mock.contract_storage_mut(&address);
+    CALENDAR.save(
+        storage.as_mut(),
+        &MockApi::default(),
+        day_datetime,
+        meetings,
+    )?;
+    Ok(())
+}
+
+/// Gas the Cosmos SDK charges for each access to the store, from its default `KVGasConfig`.
+const READ_COST_FLAT: u64 = 1_000;
+const READ_COST_PER_BYTE: u64 = 3;
+const WRITE_COST_FLAT: u64 = 2_000;
+const WRITE_COST_PER_BYTE: u64 = 30;
+const DELETE_COST: u64 = 1_000;
+const ITER_NEXT_COST_FLAT: u64 = 30;
+
+/// Storage charging the gas a chain charges for each access to it, as the mock environment
+/// meters no gas.
+pub struct MeteredStorage<'a> {
+    storage: &'a mut dyn Storage,
+    gas: Cell<u64>,
+    reads: RefCell<BTreeMap<Vec<u8>, u32>>,
+}
+
+/// The gas charged by a [`MeteredStorage`] and the number of times each key was read from it.
+pub struct StorageUsage {
+    pub gas: u64,
+    reads: BTreeMap<Vec<u8>, u32>,
+}
+
+impl StorageUsage {
+    /// Returns the number of times `key` was read.
+    pub fn reads(&self, key: &[u8]) -> u32 {
+        self.reads.get(key).copied().unwrap_or_default()
+    }
+}
+
+impl<'a> MeteredStorage<'a> {
+    pub fn new(storage: &'a mut dyn Storage) -> Self {
+        MeteredStorage {
+            storage,
+            gas: Cell::new(0),
+            reads: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns the gas charged and the keys read since the storage was wrapped.
+    pub fn into_usage(self) -> StorageUsage {
+        StorageUsage {
+            gas: self.gas.get(),
+            reads: self.reads.into_inner(),
+        }
+    }
+
+    /// Returns the gas charged since the last call, or since the storage was wrapped.
+    pub fn take_gas(&self) -> u64 {
+        self.gas.take()
+    }
+
+    fn charge(&self, flat: u64, per_byte: u64, bytes: usize) {
+        let bytes = u64::try_from(bytes).unwrap_or(u64::MAX);
+        self.gas
+            .set(self.gas.get() + flat + per_byte.saturating_mul(bytes));
+    }
+}
+
+impl Storage for MeteredStorage<'_> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let value = self.storage.get(key);
+        *self.reads.borrow_mut().entry(key.to_vec()).or_default() += 1;
+        let value_len = value.as_ref().map_or(0, Vec::len);
+        self.charge(READ_COST_FLAT, READ_COST_PER_BYTE, key.len() + value_len);
+        value
+    }
+
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'b> {
+        Box::new(
+            self.storage
+                .range(start, end, order)
+                .inspect(move |(key, value)| {
+                    self.charge(
+                        ITER_NEXT_COST_FLAT,
+                        READ_COST_PER_BYTE,
+                        key.len() + value.len(),
+                    )
+                }),
+        )
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.charge(
+            WRITE_COST_FLAT,
+            WRITE_COST_PER_BYTE,
+            key.len() + value.len(),
+        );
+        self.storage.set(key, value);
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.charge(DELETE_COST, 0, 0);
+        self.storage.remove(key);
+    }
+}
+
+/// Runs `run` on a copy of the storage of the app at the current block, returning its result
+/// and how it used the storage as metered by [`MeteredStorage`]. The app is left as it was, so
+/// the gas of a message can be measured before it is executed.
+pub fn with_metered_storage<T>(
+    app: &AppInterface<Mock>,
+    run: impl FnOnce(DepsMut, Env) -> anyhow::Result<T>,
+) -> anyhow::Result<(T, StorageUsage)> {
+    let address = app.address()?;
+    let chain = app.get_chain();
+    let mock = chain.app.borrow();
+    let mut storage = MockStorage::new();
+    for (key, value) in mock
+        .contract_storage(&address)
+        .range(None, None, Order::Ascending)
+    {
+        storage.set(&key, &value);
+    }
+    let mut metered = MeteredStorage::new(&mut storage);
+    let mut env = mock_env();
+    env.block = mock.block_info();
+    env.contract.address = address;
+    let deps = DepsMut {
+        storage: &mut metered,
+        api: mock.api(),
+        querier: QuerierWrapper::new(&*mock),
+    };
+    let result = run(deps, env)?;
+    Ok((result, metered.into_usage()))
+}
@@ -0,0 +1,118 @@
+//! Pure date and time helpers deriving calendar days, durations and local times from unix
+//! timestamps under a fixed UTC offset.
+
+use chrono::{DateTime, FixedOffset, LocalResult, NaiveTime, TimeZone};
+use cosmwasm_std::Int64;
+
+use crate::contract::AppResult;
+use crate::error::AppError;
+use crate::state::Config;
+use crate::validation::validate_meeting_times;
+
+/// The largest UTC offset in use, in seconds.
+pub const MAX_UTC_OFFSET: i32 = 14 * 3600;
+
+/// Returns the timezone of the given UTC offset in seconds, which must be within 14 hours.
+pub fn validate_utc_offset(utc_offset: i32) -> AppResult<FixedOffset> {
+    if !(-MAX_UTC_OFFSET..=MAX_UTC_OFFSET).contains(&utc_offset) {
+        return Err(AppError::InvalidUtcOffset { offset: utc_offset });
+    }
+    FixedOffset::east_opt(utc_offset).ok_or(AppError::InvalidUtcOffset { offset: utc_offset })
+}
+
+/// Converts a unix timestamp into a datetime in the given timezone.
+pub fn get_date_time(timezone: FixedOffset, timestamp: Int64) -> AppResult<DateTime<FixedOffset>> {
+    let utc_offset = timezone.local_minus_utc();
+    match timezone.timestamp_opt(timestamp.i64(), 0) {
+        LocalResult::Single(value) => Ok(value),
+        LocalResult::None => Err(AppError::LocalTimeOutOfRange {
+            timestamp: timestamp.i64(),
+            utc_offset,
+        }),
+        // Fixed offsets map every timestamp to a single local time, timezones with transitions
+        // would not.
+        LocalResult::Ambiguous(_, _) => Err(AppError::AmbiguousLocalTime {
+            timestamp: timestamp.i64(),
+            utc_offset,
+        }),
+    }
+}
+
+/// Formats a unix timestamp as an RFC 3339 datetime in the given timezone, e.g.
+/// `2024-03-01T10:00:00+01:00`, for people reading events.
+pub fn format_local(timezone: FixedOffset, timestamp: i64) -> AppResult<String> {
+    Ok(get_date_time(timezone, timestamp.into())?.to_rfc3339())
+}
+
+/// Parses an RFC 3339 datetime such as `2024-06-03T14:00:00+02:00` into a unix timestamp, the
+/// inverse of [`format_local`]. The datetime must be written in the given timezone and in whole
+/// seconds, so that a mistyped offset is not booked at an unexpected time.
+pub fn parse_local(timezone: FixedOffset, input: &str) -> AppResult<Int64> {
+    let datetime =
+        DateTime::parse_from_rfc3339(input).map_err(|error| AppError::InvalidIsoTime {
+            input: input.to_string(),
+            reason: error.to_string(),
+        })?;
+    if datetime.offset() != &timezone {
+        return Err(AppError::IsoTimeOffsetMismatch {
+            input: input.to_string(),
+            utc_offset: timezone.local_minus_utc(),
+        });
+    }
+    if datetime.timestamp_subsec_nanos() != 0 {
+        return Err(AppError::InvalidIsoTime {
+            input: input.to_string(),
+            reason: "fractions of a second are not supported".to_string(),
+        });
+    }
+    Ok(datetime.timestamp().into())
+}
+
+/// Returns the unix timestamp of the local midnight starting the day the given datetime falls on,
+/// which is the key of that day in the calendar.
+pub fn start_of_day_timestamp(datetime: &DateTime<FixedOffset>) -> i64 {
+    // A fixed offset maps every local time to exactly one instant.
+    datetime
+        .timezone()
+        .from_local_datetime(&datetime.date_naive().and_time(NaiveTime::default()))
+        .unwrap()
+        .timestamp()
+}
+
+/// Returns the calendar key of the local day the unix timestamp falls on in the given timezone.
+pub fn day_key(timezone: FixedOffset, timestamp: i64) -> AppResult<i64> {
+    Ok(start_of_day_timestamp(&get_date_time(
+        timezone,
+        timestamp.into(),
+    )?))
+}
+
+/// Returns the number of whole minutes between the given unix timestamps, which must be in
+/// order.
+pub fn duration_in_minutes(start_time: i64, end_time: i64) -> AppResult<u32> {
+    if start_time >= end_time {
+        return Err(AppError::EndTimeMustBeAfterStartTime {});
+    }
+    let seconds = end_time
+        .checked_sub(start_time)
+        .ok_or_else(|| AppError::ConversionOverflow {
+            value: format!("{end_time} - {start_time}"),
+            target: "i64".to_string(),
+        })?;
+    u32::try_from(seconds / 60).map_err(|_| AppError::ConversionOverflow {
+        value: (seconds / 60).to_string(),
+        target: "u32".to_string(),
+    })
+}
+
+/// Validates that a meeting between the given unix timestamps can be booked at `now` under the
+/// calendar hours, granularity and weekdays of `config`, see [`validate_meeting_times`].
+pub fn validate_window(config: &Config, start_time: i64, end_time: i64, now: i64) -> AppResult<()> {
+    let timezone = validate_utc_offset(config.utc_offset)?;
+    validate_meeting_times(
+        config,
+        now,
+        &get_date_time(timezone, start_time.into())?,
+        &get_date_time(timezone, end_time.into())?,
+    )
+}
@@ -0,0 +1,524 @@
+use chrono::{DateTime, Datelike, FixedOffset, NaiveTime, Timelike};
+use cosmwasm_std::{Addr, Api, Coin, Env, Int64, MessageInfo, Timestamp, Uint128};
+use cw_utils::{must_pay, PaymentError};
+
+use crate::contract::AppResult;
+use crate::error::AppError;
+use crate::msg::Time;
+use crate::schedule::in_calendar_window;
+use crate::state::{Config, Meeting, SlashCurve};
+use crate::time::{day_key, duration_in_minutes, get_date_time, validate_utc_offset};
+
+/// Basis points making up a whole.
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// The latest timestamp accepted from messages, the last second of the year 9999.
+pub const MAX_TIMESTAMP: i64 = 253_402_300_799;
+
+/// The longest a meeting can last in seconds, as it starts and ends on the same day.
+pub const MAX_MEETING_SECONDS: i64 = 24 * 3600;
+
+/// Returns the given unix timestamp if it is not before 1970 nor after [`MAX_TIMESTAMP`].
+pub fn validate_timestamp(timestamp: Int64) -> AppResult<i64> {
+    let value = timestamp.i64();
+    if !(0..=MAX_TIMESTAMP).contains(&value) {
+        return Err(AppError::TimestampOutOfRange { value });
+    }
+    Ok(value)
+}
+
+/// Returns the given timestamp in whole seconds, rejecting any fraction of a second so that
+/// it books the same meeting as the matching `Int64` seconds.
+pub fn timestamp_seconds(timestamp: Timestamp) -> AppResult<Int64> {
+    if timestamp.subsec_nanos() != 0 {
+        return Err(AppError::TimestampNotWholeSeconds { timestamp });
+    }
+    let seconds = timestamp.seconds();
+    i64::try_from(seconds)
+        .map(Int64::from)
+        .map_err(|_| AppError::ConversionOverflow {
+            value: seconds.to_string(),
+            target: "i64".to_string(),
+        })
+}
+
+/// Returns the block time as a unix timestamp.
+pub fn block_time(env: &Env) -> AppResult<i64> {
+    let seconds = env.block.time.seconds();
+    i64::try_from(seconds).map_err(|_| AppError::ConversionOverflow {
+        value: seconds.to_string(),
+        target: "i64".to_string(),
+    })
+}
+
+/// Returns the amount of `denom` sent with the message, which must be a single coin of it.
+pub fn must_pay_in(info: &MessageInfo, denom: &str) -> AppResult<Uint128> {
+    must_pay(info, denom).map_err(|error| payment_required(&info.funds, denom, error))
+}
+
+/// Maps a [`PaymentError`] of a payment in `denom` to an error naming the denom and the funds
+/// that were sent instead.
+pub fn payment_required(funds: &[Coin], denom: &str, error: PaymentError) -> AppError {
+    let sent = funds
+        .iter()
+        .map(|coin| coin.denom.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let reason = match error {
+        PaymentError::NoFunds {} => "no funds sent".to_string(),
+        PaymentError::MissingDenom(_) | PaymentError::ExtraDenom(_) => format!("sent {sent}"),
+        PaymentError::MultipleDenoms {} => {
+            format!("sent {sent}, send a single coin of {denom} instead")
+        }
+        error => return AppError::PaymentError(error),
+    };
+    AppError::PaymentRequired {
+        denom: denom.to_string(),
+        reason,
+    }
+}
+
+/// Returns the position of a meeting in the vector of its day.
+pub fn meeting_position(meeting_index: u32) -> AppResult<usize> {
+    usize::try_from(meeting_index).map_err(|_| AppError::ConversionOverflow {
+        value: meeting_index.to_string(),
+        target: "usize".to_string(),
+    })
+}
+
+/// Returns the index of the meeting at `position` in the vector of its day.
+pub fn meeting_index_at(position: usize) -> AppResult<u32> {
+    u32::try_from(position).map_err(|_| AppError::ConversionOverflow {
+        value: position.to_string(),
+        target: "u32".to_string(),
+    })
+}
+
+/// The duration of a meeting in minutes, which ends after it starts, lasts a whole number of
+/// minutes and lasts at most [`MAX_MEETING_SECONDS`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MeetingDuration(u32);
+
+impl MeetingDuration {
+    /// Returns the duration between the given unix timestamps, failing if they do not span a
+    /// valid meeting.
+    pub fn between(start_time: i64, end_time: i64) -> AppResult<Self> {
+        if start_time >= end_time {
+            return Err(AppError::EndTimeMustBeAfterStartTime {});
+        }
+        let seconds = end_time
+            .checked_sub(start_time)
+            .filter(|seconds| *seconds <= MAX_MEETING_SECONDS)
+            .ok_or(AppError::MeetingTooLong {
+                start_time,
+                end_time,
+            })?;
+        // Also rules out spans shorter than a minute, which would last zero minutes.
+        if seconds % 60 != 0 {
+            return Err(AppError::MeetingNotWholeMinutes {
+                start_time,
+                end_time,
+            });
+        }
+        duration_in_minutes(start_time, end_time).map(Self)
+    }
+
+    /// Returns the duration of a stored meeting, failing if it was corrupted.
+    pub fn of(meeting: &Meeting) -> AppResult<Self> {
+        Self::between(meeting.start_time, meeting.end_time)
+    }
+
+    pub fn minutes(self) -> u32 {
+        self.0
+    }
+}
+
+/// Validates that the calendar hours are valid times of the day and that they start before they
+/// end.
+pub fn validate_calendar_hours(start_time: &Time, end_time: &Time) -> AppResult<()> {
+    start_time.validate()?;
+    end_time.validate()?;
+    if (start_time.hour, start_time.minute) >= (end_time.hour, end_time.minute) {
+        return Err(AppError::CalendarStartNotBeforeEnd {});
+    }
+    Ok(())
+}
+
+/// Validates a profile field of the config, returning `None` for an empty value, which clears
+/// it.
+pub fn validate_profile_field(
+    field: &str,
+    value: String,
+    max_length: usize,
+) -> AppResult<Option<String>> {
+    if value.chars().any(char::is_control) {
+        return Err(AppError::ProfileFieldHasControlCharacters {
+            field: field.to_string(),
+        });
+    }
+    let length = value.chars().count();
+    if length > max_length {
+        return Err(AppError::ProfileFieldTooLong {
+            field: field.to_string(),
+            length,
+            max: max_length,
+        });
+    }
+    Ok(Some(value).filter(|value| !value.is_empty()))
+}
+
+/// Validates that a denom is not empty, has at most 128 characters and has no whitespace or
+/// control characters.
+pub fn validate_denom(denom: &str) -> AppResult<()> {
+    if denom.is_empty()
+        || denom.chars().count() > 128
+        || denom.chars().any(|c| c.is_whitespace() || c.is_control())
+    {
+        return Err(AppError::InvalidDenom {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Validates that a granularity is a whole number of minutes that divides a day.
+pub fn validate_granularity(granularity_seconds: u32) -> AppResult<()> {
+    if granularity_seconds == 0
+        || !granularity_seconds.is_multiple_of(60)
+        || !86_400_u32.is_multiple_of(granularity_seconds)
+    {
+        return Err(AppError::InvalidGranularity {
+            granularity_seconds,
+        });
+    }
+    Ok(())
+}
+
+/// Validates that a local time of a meeting is aligned to the granularity of the calendar.
+pub fn validate_alignment(config: &Config, time: NaiveTime) -> AppResult<()> {
+    let granularity_seconds = config.granularity_seconds();
+    if !time
+        .num_seconds_from_midnight()
+        .is_multiple_of(granularity_seconds)
+    {
+        return Err(AppError::TimeNotAlignedToGranularity {
+            time: time.into(),
+            granularity_seconds,
+        });
+    }
+    Ok(())
+}
+
+/// Validates that `day_datetime` is the calendar key of a day, a local midnight in the given
+/// timezone.
+pub fn validate_day_key(timezone: FixedOffset, day_datetime: i64) -> AppResult<()> {
+    if day_key(timezone, day_datetime)? != day_datetime {
+        return Err(AppError::DayKeyMismatch { day_datetime });
+    }
+    Ok(())
+}
+
+/// Validates that `meeting` starts on the day keyed by `day_datetime`.
+pub fn validate_meeting_day(
+    timezone: FixedOffset,
+    day_datetime: i64,
+    meeting: &Meeting,
+) -> AppResult<()> {
+    if day_key(timezone, meeting.start_time)? != day_datetime {
+        return Err(AppError::DayKeyMismatch { day_datetime });
+    }
+    Ok(())
+}
+
+/// Validates that a meeting between the given datetimes can be booked at `now`.
+pub fn validate_meeting_times(
+    config: &Config,
+    now: i64,
+    meeting_start_datetime: &DateTime<FixedOffset>,
+    meeting_end_datetime: &DateTime<FixedOffset>,
+) -> AppResult<()> {
+    let meeting_start_time: NaiveTime = meeting_start_datetime.time();
+    let meeting_end_time: NaiveTime = meeting_end_datetime.time();
+
+    // Check that date falls between the given range.
+    let calendar_start_time = NaiveTime::try_from(config.start_time.clone())?;
+    let calendar_end_time = NaiveTime::try_from(config.end_time.clone())?;
+
+    if meeting_start_datetime.date_naive() != meeting_end_datetime.date_naive() {
+        return Err(AppError::StartAndEndTimeNotOnSameDay {});
+    }
+
+    if meeting_start_time.second() != 0 || meeting_start_time.nanosecond() != 0 {
+        return Err(AppError::StartTimeNotRoundedToNearestMinute {});
+    }
+
+    if meeting_end_time.second() != 0 || meeting_end_time.nanosecond() != 0 {
+        return Err(AppError::EndTimeNotRoundedToNearestMinute {});
+    }
+
+    for time in [meeting_start_time, meeting_end_time] {
+        validate_alignment(config, time)?;
+    }
+
+    if now > meeting_start_datetime.timestamp() {
+        return Err(AppError::StartTimeMustBeInFuture {});
+    }
+
+    if meeting_start_time >= meeting_end_time {
+        return Err(AppError::EndTimeMustBeAfterStartTime {});
+    }
+
+    if meeting_start_time == calendar_end_time {
+        return Err(AppError::StartTimeAtCalendarClose {
+            time: meeting_start_time.into(),
+        });
+    }
+    if !in_calendar_window(calendar_start_time, calendar_end_time, meeting_start_time) {
+        return Err(AppError::StartTimeDoesNotFallWithinCalendarBounds {
+            time: meeting_start_time.into(),
+            calendar_start: config.start_time.clone(),
+            calendar_end: config.end_time.clone(),
+        });
+    }
+
+    if meeting_end_time <= calendar_start_time
+        || (meeting_end_time > calendar_end_time && !config.allow_partial_outside)
+    {
+        return Err(AppError::EndTimeDoesNotFallWithinCalendarBounds {
+            time: meeting_end_time.into(),
+            calendar_start: config.start_time.clone(),
+            calendar_end: config.end_time.clone(),
+        });
+    }
+
+    if !config.allowed_weekdays.is_empty()
+        && !config
+            .allowed_weekdays
+            .contains(&meeting_start_datetime.weekday().into())
+    {
+        return Err(AppError::WeekdayNotAllowed {});
+    }
+
+    Ok(())
+}
+
+/// Validates that `meeting` has not ended yet and can be extended to end at `new_end_datetime`
+/// on the same day and within the calendar hours.
+pub fn validate_extension(
+    config: &Config,
+    now: i64,
+    meeting: &Meeting,
+    new_end_datetime: &DateTime<FixedOffset>,
+) -> AppResult<()> {
+    if now >= meeting.ended_at() {
+        return Err(AppError::MeetingAlreadyEnded {});
+    }
+
+    let new_end_time: NaiveTime = new_end_datetime.time();
+    if new_end_time.second() != 0 || new_end_time.nanosecond() != 0 {
+        return Err(AppError::EndTimeNotRoundedToNearestMinute {});
+    }
+    validate_alignment(config, new_end_time)?;
+
+    if new_end_datetime.timestamp() <= meeting.end_time {
+        return Err(AppError::ExtensionMustEndLater {});
+    }
+
+    let start_datetime = get_date_time(*new_end_datetime.offset(), meeting.start_time.into())?;
+    if start_datetime.date_naive() != new_end_datetime.date_naive() {
+        return Err(AppError::StartAndEndTimeNotOnSameDay {});
+    }
+
+    let calendar_end_time = NaiveTime::try_from(config.end_time.clone())?;
+    if new_end_time > calendar_end_time && !config.allow_partial_outside {
+        return Err(AppError::EndTimeDoesNotFallWithinCalendarBounds {
+            time: new_end_time.into(),
+            calendar_start: config.start_time.clone(),
+            calendar_end: config.end_time.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the end of the priced part of a meeting between `start_time` and `end_time`, which
+/// stops at the close of the calendar hours when meetings may run past it but that part is not
+/// priced.
+pub fn priced_end_time(config: &Config, start_time: i64, end_time: i64) -> AppResult<i64> {
+    if !config.allow_partial_outside || config.price_outside_hours {
+        return Ok(end_time);
+    }
+    let timezone = validate_utc_offset(config.utc_offset)?;
+    let close = day_key(timezone, start_time)?
+        + i64::from(config.end_time.hour) * 3600
+        + i64::from(config.end_time.minute) * 60;
+    Ok(end_time.min(close.max(start_time)))
+}
+
+/// Returns the earliest starting meeting that overlaps with the interval between `start_time`
+/// and `end_time`, the first booked one among those starting at the same time. Intervals are
+/// half-open, so back-to-back meetings do not overlap.
+pub fn find_conflict(meetings: &[Meeting], start_time: i64, end_time: i64) -> Option<&Meeting> {
+    meetings
+        .iter()
+        .filter(|meeting| start_time < meeting.end_time && meeting.start_time < end_time)
+        .min_by_key(|meeting| meeting.start_time)
+}
+
+/// Returns whether a meeting of `requester` between `start_time` and `end_time` would be less
+/// than `self_gap_minutes` away from another of their meetings.
+pub fn violates_self_gap(
+    meetings: &[Meeting],
+    requester: &Addr,
+    start_time: i64,
+    end_time: i64,
+    self_gap_minutes: u32,
+) -> bool {
+    let self_gap = i64::from(self_gap_minutes) * 60;
+    meetings
+        .iter()
+        .filter(|meeting| &meeting.requester == requester)
+        .any(|meeting| {
+            let gap = (meeting.start_time - end_time).max(start_time - meeting.end_time);
+            gap < self_gap
+        })
+}
+
+/// Splits a stake between the requester and the admin for a requester that was `minutes_late`
+/// to a meeting lasting `meeting_duration_in_minutes`.
+///
+/// Returns `(amount_to_requester, amount_to_admin)`. The slashed amount is rounded down, so the
+/// rounding remainder goes to the requester and the two always add up to `amount_staked`.
+pub fn partial_slash_payout(
+    amount_staked: Uint128,
+    minutes_late: u32,
+    meeting_duration_in_minutes: u32,
+) -> AppResult<(Uint128, Uint128)> {
+    if meeting_duration_in_minutes == 0 {
+        return Err(AppError::ZeroMeetingDuration {});
+    }
+    if minutes_late > meeting_duration_in_minutes {
+        return Err(AppError::MinutesLateCannotExceedDurationOfMeeting {});
+    }
+    // Cannot overflow since the ratio is at most one.
+    let amount_to_slash =
+        amount_staked.multiply_ratio(minutes_late, meeting_duration_in_minutes as u128);
+    let amount_to_return = amount_staked - amount_to_slash;
+    debug_assert_eq!(amount_staked, amount_to_return + amount_to_slash);
+    Ok((amount_to_return, amount_to_slash))
+}
+
+/// Returns the amounts of `amount_staked` returned to the requester and slashed when they were
+/// `minutes_late` to a meeting lasting `meeting_duration_in_minutes`, following `curve`.
+pub fn curve_slash_payout(
+    curve: &SlashCurve,
+    amount_staked: Uint128,
+    minutes_late: u32,
+    meeting_duration_in_minutes: u32,
+) -> AppResult<(Uint128, Uint128)> {
+    if meeting_duration_in_minutes == 0 {
+        return Err(AppError::ZeroMeetingDuration {});
+    }
+    if minutes_late > meeting_duration_in_minutes {
+        return Err(AppError::MinutesLateCannotExceedDurationOfMeeting {});
+    }
+    // Cannot overflow since every ratio is at most one.
+    let amount_to_slash = match *curve {
+        SlashCurve::Linear => {
+            return partial_slash_payout(amount_staked, minutes_late, meeting_duration_in_minutes)
+        }
+        SlashCurve::Quadratic => amount_staked.multiply_ratio(
+            u128::from(minutes_late).pow(2),
+            u128::from(meeting_duration_in_minutes).pow(2),
+        ),
+        SlashCurve::Threshold {
+            grace_minutes,
+            full_after_minutes,
+        } => {
+            if minutes_late <= grace_minutes {
+                Uint128::zero()
+            } else if minutes_late >= full_after_minutes {
+                amount_staked
+            } else {
+                amount_staked.multiply_ratio(
+                    minutes_late - grace_minutes,
+                    full_after_minutes - grace_minutes,
+                )
+            }
+        }
+    };
+    Ok((amount_staked - amount_to_slash, amount_to_slash))
+}
+
+/// Validates that a threshold curve forgives fewer minutes than it takes to slash everything.
+pub fn validate_slash_curve(curve: &SlashCurve) -> AppResult<()> {
+    if let SlashCurve::Threshold {
+        grace_minutes,
+        full_after_minutes,
+    } = *curve
+    {
+        if grace_minutes >= full_after_minutes {
+            return Err(AppError::InvalidSlashCurve {
+                grace_minutes,
+                full_after_minutes,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Validates the recipients of refund splits and that their shares add up to exactly 10000
+/// basis points.
+pub fn validate_refund_splits(
+    api: &dyn Api,
+    refund_splits: Vec<(String, u16)>,
+) -> AppResult<Vec<(Addr, u16)>> {
+    let total_bps: u32 = refund_splits.iter().map(|(_, bps)| u32::from(*bps)).sum();
+    if total_bps != BPS_DENOMINATOR {
+        return Err(AppError::InvalidRefundSplits { total_bps });
+    }
+    refund_splits
+        .into_iter()
+        .map(|(recipient, bps)| Ok((api.addr_validate(&recipient)?, bps)))
+        .collect()
+}
+
+/// Validates the recipients of a slash distribution and that their shares add up to exactly
+/// 10000 basis points. An empty distribution sends slashed stake to the admin.
+pub fn validate_slash_distribution(
+    api: &dyn Api,
+    slash_distribution: Vec<(String, u16)>,
+) -> AppResult<Vec<(Addr, u16)>> {
+    if slash_distribution.is_empty() {
+        return Ok(vec![]);
+    }
+    let total_bps: u32 = slash_distribution
+        .iter()
+        .map(|(_, bps)| u32::from(*bps))
+        .sum();
+    if total_bps != BPS_DENOMINATOR {
+        return Err(AppError::InvalidSlashDistribution { total_bps });
+    }
+    slash_distribution
+        .into_iter()
+        .map(|(recipient, bps)| Ok((api.addr_validate(&recipient)?, bps)))
+        .collect()
+}
+
+/// Splits `amount` between the recipients according to their share in basis points. The
+/// rounding remainder goes to the last recipient so the parts always add up to `amount`.
+pub fn split_amount(amount: Uint128, splits: &[(Addr, u16)]) -> Vec<(Addr, Uint128)> {
+    let mut remaining = amount;
+    let mut parts: Vec<(Addr, Uint128)> = splits
+        .iter()
+        .map(|(recipient, bps)| {
+            let part = amount.multiply_ratio(*bps, BPS_DENOMINATOR);
+            remaining -= part;
+            (recipient.clone(), part)
+        })
+        .collect();
+    if let Some((_, last)) = parts.last_mut() {
+        *last += remaining;
+    }
+    parts
+}
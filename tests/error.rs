@@ -0,0 +1,246 @@
+use std::collections::HashSet;
+
+use app::{error::AppError, msg::Time};
+use cosmwasm_std::{StdError, Timestamp, Uint128};
+use cw_controllers::AdminError;
+use cw_utils::PaymentError;
+
+fn all_variants() -> Vec<AppError> {
+    vec![
+        AppError::Std(StdError::generic_err("std")),
+        AppError::Abstract(StdError::generic_err("abstract").into()),
+        AppError::AbstractSdk(StdError::generic_err("abstract sdk").into()),
+        AppError::Asset(StdError::generic_err("asset").into()),
+        AppError::Admin(AdminError::NotAdmin {}),
+        AppError::DappError(StdError::generic_err("app").into()),
+        AppError::PaymentError(PaymentError::NoFunds {}),
+        AppError::StartTimeMustBeInFuture {},
+        AppError::StartTimeDoesNotFallWithinCalendarBounds {
+            time: Time { hour: 9, minute: 0 },
+            calendar_start: Time { hour: 9, minute: 0 },
+            calendar_end: Time { hour: 9, minute: 0 },
+        },
+        AppError::StartTimeAtCalendarClose {
+            time: Time { hour: 9, minute: 0 },
+        },
+        AppError::EndTimeDoesNotFallWithinCalendarBounds {
+            time: Time { hour: 9, minute: 0 },
+            calendar_start: Time { hour: 9, minute: 0 },
+            calendar_end: Time { hour: 9, minute: 0 },
+        },
+        AppError::EndTimeMustBeAfterStartTime {},
+        AppError::MeetingConflictExists {
+            existing_start: 1,
+            existing_end: 1,
+            existing_start_local: "x".to_string(),
+            existing_end_local: "x".to_string(),
+        },
+        AppError::InvalidUtcOffset { offset: 1 },
+        AppError::WeekdayNotAllowed {},
+        AppError::InvalidTimeValue { hour: 1, minute: 1 },
+        AppError::CalendarStartNotBeforeEnd {},
+        AppError::LocalTimeOutOfRange {
+            timestamp: 1,
+            utc_offset: 1,
+        },
+        AppError::AmbiguousLocalTime {
+            timestamp: 1,
+            utc_offset: 1,
+        },
+        AppError::InvalidMeetingStart {
+            reason: Box::new(AppError::WeekdayNotAllowed {}),
+        },
+        AppError::InvalidMeetingEnd {
+            reason: Box::new(AppError::WeekdayNotAllowed {}),
+        },
+        AppError::TimestampOutOfRange { value: 1 },
+        AppError::TimestampNotWholeSeconds {
+            timestamp: Timestamp::from_nanos(1),
+        },
+        AppError::InvalidIsoTime {
+            input: "x".to_string(),
+            reason: "x".to_string(),
+        },
+        AppError::IsoTimeOffsetMismatch {
+            input: "x".to_string(),
+            utc_offset: 1,
+        },
+        AppError::Overflow {
+            lhs: Uint128::one(),
+            rhs: Uint128::one(),
+        },
+        AppError::ConversionOverflow {
+            value: "x".to_string(),
+            target: "x".to_string(),
+        },
+        AppError::StartAndEndTimeNotOnSameDay {},
+        AppError::StartTimeNotRoundedToNearestMinute {},
+        AppError::EndTimeNotRoundedToNearestMinute {},
+        AppError::InvalidStakeAmountSent {
+            expected_amount: Uint128::one(),
+            amount_sent: Uint128::one(),
+        },
+        AppError::PaymentNotRequired {},
+        AppError::PaymentRequired {
+            denom: "x".to_string(),
+            reason: "x".to_string(),
+        },
+        AppError::NoMeetingsAtGivenDayDateTime { day_datetime: 1 },
+        AppError::MeetingDoesNotExist {
+            day_datetime: 1,
+            meeting_index: 1,
+        },
+        AppError::MeetingNotFoundByKey {
+            day_datetime: 1,
+            requester: "x".to_string(),
+            start_time: 1,
+        },
+        AppError::DayKeyMismatch { day_datetime: 1 },
+        AppError::MeetingAlreadyStarted {},
+        AppError::PastCancellationDeadline {},
+        AppError::MeetingAlreadyEnded {},
+        AppError::ExtensionMustEndLater {},
+        AppError::NotMeetingRequester {},
+        AppError::RefundNotFound { refund_id: 1 },
+        AppError::NotRefundRecipient {},
+        AppError::RefundClaimExpired {},
+        AppError::AdminBookingNotAllowed {},
+        AppError::PayoutsFrozen {},
+        AppError::RefundClaimNotExpired {},
+        AppError::HoldNotFound { hold_id: 1 },
+        AppError::HoldExpired {},
+        AppError::SlotHeld {},
+        AppError::SlotAvailable {},
+        AppError::WaitlistEntryNotFound { entry_id: 1 },
+        AppError::NotAttestor {},
+        AppError::MeetingNotAttested {
+            day_datetime: 1,
+            meeting_index: 1,
+        },
+        AppError::MeetingDisputed {
+            day_datetime: 1,
+            meeting_index: 1,
+        },
+        AppError::MeetingNotInProgress {
+            day_datetime: 1,
+            meeting_index: 1,
+        },
+        AppError::MeetingNotFinishedYet {
+            day_datetime: 1,
+            meeting_index: 1,
+        },
+        AppError::StakeAlreadyHandled {
+            day_datetime: 1,
+            meeting_index: 1,
+        },
+        AppError::MeetingTooLong {
+            start_time: 1,
+            end_time: 1,
+        },
+        AppError::MinutesLateCannotExceedDurationOfMeeting {},
+        AppError::InvalidSlashCurve {
+            grace_minutes: 1,
+            full_after_minutes: 1,
+        },
+        AppError::SelfGapViolated {
+            self_gap_minutes: 1,
+        },
+        AppError::InvalidRefundSplits { total_bps: 1 },
+        AppError::BatchTooLarge { max: 1 },
+        AppError::MeetingsHoldStake { count: 1 },
+        AppError::DayNotEmpty { day_datetime: 1 },
+        AppError::ImportedMeetingsOverlap {
+            day_datetime: 1,
+            start_time: 1,
+        },
+        AppError::DenomNotAccepted {
+            denom: "x".to_string(),
+        },
+        AppError::StakeAboveMax {
+            expected_amount: Uint128::one(),
+            max_total_stake: Uint128::one(),
+        },
+        AppError::UnresolvableAcceptedDenom {
+            entry: "x".to_string(),
+        },
+        AppError::NonNativeAcceptedDenom {
+            entry: "x".to_string(),
+        },
+        AppError::InsufficientRecoverableFunds {
+            available: Uint128::one(),
+        },
+        AppError::InsufficientCredit {
+            available: Uint128::one(),
+        },
+        AppError::InvalidGranularity {
+            granularity_seconds: 1,
+        },
+        AppError::TimeNotAlignedToGranularity {
+            time: Time { hour: 9, minute: 0 },
+            granularity_seconds: 1,
+        },
+        AppError::MeetingsNotAligned {
+            count: 1,
+            granularity_seconds: 1,
+        },
+        AppError::InsufficientContractBalance {
+            needed: Uint128::one(),
+            available: Uint128::one(),
+        },
+        AppError::PriceSourceNotSet {},
+        AppError::PriceSourceUnavailable {
+            denom: "x".to_string(),
+            reason: "x".to_string(),
+        },
+        AppError::RangeTooLarge { max_days: 1 },
+        AppError::ProfileFieldTooLong {
+            field: "x".to_string(),
+            length: 2,
+            max: 1,
+        },
+        AppError::ProfileFieldHasControlCharacters {
+            field: "x".to_string(),
+        },
+        AppError::InvalidSlashDistribution { total_bps: 1 },
+        AppError::EncryptedPayloadTooLarge { size: 2, max: 1 },
+        AppError::ZeroMeetingDuration {},
+        AppError::MeetingNotWholeMinutes {
+            start_time: 0,
+            end_time: 1,
+        },
+        AppError::InvalidDenom {
+            denom: "x".to_string(),
+        },
+        AppError::DuplicateImportedDay { day_datetime: 1 },
+        AppError::ZeroLimit {},
+        AppError::NoAdminSet {},
+        AppError::ImportedMeetingResolved {
+            day_datetime: 1,
+            start_time: 1,
+        },
+    ]
+}
+
+#[test]
+fn error_codes_are_unique() {
+    let variants = all_variants();
+    let codes: HashSet<&str> = variants.iter().map(AppError::code).collect();
+    assert_eq!(variants.len(), codes.len());
+    assert_eq!(92, codes.len());
+}
+
+#[test]
+fn error_messages_start_with_their_code() {
+    for error in all_variants() {
+        let code = error.code();
+        assert!(
+            error.to_string().starts_with(&format!("{code}: ")),
+            "{error} does not start with {code}"
+        );
+    }
+    // Wrapped errors prefix the message of the dependency.
+    assert_eq!(
+        format!("CAL-001: {}", StdError::generic_err("std")),
+        all_variants()[0].to_string()
+    );
+}
@@ -1,18 +1,57 @@
 use abstract_core::objects::{gov_type::GovernanceDetails, AccountId, AssetEntry};
 use abstract_interface::{Abstract, AbstractAccount, AppDeployer, VCExecFns};
 use app::{
-    contract::{APP_ID, APP_VERSION},
+    contract::{
+        APP_ID, APP_VERSION, DEFAULT_PAGE_LIMIT, MAX_ENCRYPTED_PAYLOAD_BYTES, MAX_HEATMAP_DAYS,
+        MAX_LISTED_DAYS, MAX_NAME_LENGTH, MAX_PAGE_LIMIT,
+    },
     error::AppError,
-    msg::{AppExecuteMsg, AppInstantiateMsg, ConfigResponse, Time},
-    state::Meeting,
+    events::{
+        CONFIG_UPDATED, KEY_ACTION, KEY_AMOUNT_STAKED, KEY_AMOUNT_TO_ADMIN,
+        KEY_AMOUNT_TO_REQUESTER, KEY_CHANGED, KEY_DAY_DATETIME, KEY_DENOM, KEY_END_TIME,
+        KEY_MEETING_INDEX, KEY_MINUTES_LATE, KEY_PRICE_PER_MINUTE, KEY_REQUESTER, KEY_RESOLVER,
+        KEY_SCHEMA_VERSION, KEY_START_TIME, KEY_UPDATED_BY, KEY_UTC_OFFSET, MEETING_BOOKED,
+        SCHEMA_VERSION, STAKE_RESOLVED,
+    },
+    interface::CalendarSnapshot,
+    msg::{
+        AppExecuteMsg, AppInstantiateMsg, AppQueryMsg, AttentionItem, AttentionReason,
+        AuditLogResponse, AvailabilityHeatmapResponse, BookingFailure, BusyBlocksResponse,
+        ConfigResponse, DayBoundsResponse, DaySummaryResponse, DiagnoseResponse,
+        DueRemindersResponse, ExportResponse, ExtensionQuoteResponse, FeatureFlags, ICalEvent,
+        ICalFeedResponse, InfoResponse, LongestFreeBlockResponse, MeetingResponse, MeetingStatus,
+        MeetingsForDaysResponse, NeedsAttentionResponse, OraclePriceResponse, OracleQueryMsg,
+        OutstandingResponse, OverlapsResponse, PendingResolutionsResponse, ProfileResponse,
+        QueryMsg, QuotePriceResponse, RequestMeetingResponse, ResolutionResponse,
+        ResolvedMeetingsResponse, SelfTestResponse, SimulateRequestMeetingResponse,
+        SlashPreviewResponse, Slot, StakeValueResponse, Time, UniqueRequestersResponse, Violation,
+        ViolationReason, WaitlistResponse, Weekday,
+    },
+    state::{
+        Config, Meeting, Resolution, ResolutionAction, SlashCurve, StakeEvent, StakeMovement,
+        CALENDAR, CONFIG,
+    },
+    testing::{
+        advance_minutes, clear_admin, local_timestamp, set_local_time, with_metered_storage,
+        write_day,
+    },
+    time::format_local,
     *,
 };
-use chrono::{DateTime, Days, FixedOffset, NaiveDateTime, NaiveTime, TimeZone, Timelike};
+use chrono::{
+    DateTime, Days, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike,
+};
 use cw_asset::AssetInfo;
 // Use prelude to get all the necessary imports
 use cw_orch::{anyhow, deploy::Deploy, prelude::*};
 
-use cosmwasm_std::{coins, Addr, BlockInfo, Uint128};
+use cosmwasm_std::{
+    coins, from_binary,
+    testing::{mock_env, mock_info},
+    to_binary, Addr, Binary, BlockInfo, Decimal, Deps, DepsMut, Empty, Env, Event, Int64,
+    MessageInfo, OverflowError, OverflowOperation, Response, StdError, StdResult, Timestamp,
+    Uint128,
+};
 
 // consts for testing
 const ADMIN: &str = "admin";
@@ -75,6 +114,9 @@ fn request_meeting(
         &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
             start_time: meeting_start_datetime.timestamp().into(),
             end_time: meeting_end_datetime.timestamp().into(),
+            refund_splits: None,
+            reminder_minutes: None,
+            max_total_stake: None,
         }),
         Some(&[funds]),
     )?;
@@ -89,6 +131,33 @@ fn setup() -> anyhow::Result<(
     Abstract<Mock>,
     AppInterface<Mock>,
     Mock,
+)> {
+    setup_with(AppInstantiateMsg {
+        price_per_minute: Uint128::from(1u128),
+        denom: AssetEntry::from(DENOM),
+        utc_offset: 0,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 0,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![],
+        name: None,
+        description: None,
+        contact_uri: None,
+    })
+}
+
+/// Set up the test environment with the contract installed using the given instantiate message
+#[allow(clippy::type_complexity)]
+fn setup_with(
+    instantiate_msg: AppInstantiateMsg,
+) -> anyhow::Result<(
+    AbstractAccount<Mock>,
+    Abstract<Mock>,
+    AppInterface<Mock>,
+    Mock,
 )> {
     // Create a sender
     let sender = Addr::unchecked(ADMIN);
@@ -129,24 +198,57 @@ fn setup() -> anyhow::Result<(
 
     app.deploy(APP_VERSION.parse()?)?;
 
-    account.install_app(
-        app.clone(),
-        &AppInstantiateMsg {
-            price_per_minute: Uint128::from(1u128),
-            denom: AssetEntry::from(DENOM),
-            utc_offset: 0,
-            start_time: Time { hour: 9, minute: 0 },
-            end_time: Time {
-                hour: 17,
-                minute: 0,
-            },
-        },
-        None,
-    )?;
+    account.install_app(app.clone(), &instantiate_msg, None)?;
 
     Ok((account, abstr_deployment, app, mock))
 }
 
+/// Returns the attributes of the events of type `ty` emitted by the app, without the contract
+/// address added by the chain.
+fn typed_events(events: &[Event], ty: &str) -> Vec<Vec<(String, String)>> {
+    events
+        .iter()
+        .filter(|event| event.ty == format!("wasm-{ty}"))
+        .map(|event| {
+            event
+                .attributes
+                .iter()
+                .filter(|attribute| attribute.key != "_contract_address")
+                .map(|attribute| (attribute.key.clone(), attribute.value.clone()))
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns `meetings` as the queries return them at the current block.
+fn meeting_responses(
+    app: &AppInterface<Mock>,
+    meetings: Vec<Meeting>,
+) -> anyhow::Result<Vec<MeetingResponse>> {
+    let config = app.export_state()?.config;
+    let mut env = mock_env();
+    env.block = app.get_chain().block_info()?;
+    Ok(meetings
+        .iter()
+        .map(|meeting| MeetingResponse::try_from((meeting, &config, &env)))
+        .collect::<Result<_, _>>()?)
+}
+
+/// The error for a booking running into the meeting between `start_time` and `end_time`.
+fn conflict_error(
+    app: &AppInterface<Mock>,
+    start_time: i64,
+    end_time: i64,
+) -> anyhow::Result<AppError> {
+    let timezone = FixedOffset::east_opt(app.config()?.utc_offset).unwrap();
+    Ok(AppError::MeetingConflictExists {
+        existing_start: start_time,
+        existing_end: end_time,
+        existing_start_local: format_local(timezone, start_time)?,
+        existing_end_local: format_local(timezone, end_time)?,
+    })
+}
+
 #[test]
 fn successful_install() -> anyhow::Result<()> {
     // Set up the environment and contract
@@ -163,11 +265,128 @@ fn successful_install() -> anyhow::Result<()> {
                 hour: 17,
                 minute: 0,
             },
+            self_gap_minutes: 0,
+            accepted_denoms: vec![],
+            denom_decimals: 0,
+            allowed_weekdays: vec![],
+            booking_fee: Uint128::zero(),
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: false,
+            price_outside_hours: false,
+            price_source: None,
+            check_outstanding_balance: false,
+            time_granularity_seconds: 0,
+            allow_admin_booking: false,
+            overbooking_allowed: false,
+            pegged_price_per_minute: None,
+            slash_curve: SlashCurve::Linear,
+            cancel_cutoff_secs: 0,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: vec![],
         }
     );
     Ok(())
 }
 
+#[test]
+fn cannot_install_with_out_of_range_utc_offset() -> anyhow::Result<()> {
+    for utc_offset in [100_000, -100_000] {
+        let error = setup_with(AppInstantiateMsg {
+            price_per_minute: Uint128::from(1u128),
+            denom: AssetEntry::from(DENOM),
+            utc_offset,
+            start_time: Time { hour: 9, minute: 0 },
+            end_time: Time {
+                hour: 17,
+                minute: 0,
+            },
+            self_gap_minutes: 0,
+            accepted_denoms: vec![],
+            name: None,
+            description: None,
+            contact_uri: None,
+        })
+        .unwrap_err();
+
+        assert!(error
+            .root_cause()
+            .to_string()
+            .contains(&AppError::InvalidUtcOffset { offset: utc_offset }.to_string()));
+    }
+    Ok(())
+}
+
+#[test]
+fn cannot_install_with_invalid_calendar_hours() -> anyhow::Result<()> {
+    let cases = [
+        (
+            Time { hour: 9, minute: 0 },
+            Time { hour: 9, minute: 0 },
+            AppError::CalendarStartNotBeforeEnd {},
+        ),
+        (
+            Time {
+                hour: 17,
+                minute: 0,
+            },
+            Time {
+                hour: 9,
+                minute: 30,
+            },
+            AppError::CalendarStartNotBeforeEnd {},
+        ),
+        (
+            Time { hour: 9, minute: 0 },
+            Time {
+                hour: 24,
+                minute: 0,
+            },
+            AppError::InvalidTimeValue {
+                hour: 24,
+                minute: 0,
+            },
+        ),
+        (
+            Time {
+                hour: 9,
+                minute: 60,
+            },
+            Time {
+                hour: 17,
+                minute: 0,
+            },
+            AppError::InvalidTimeValue {
+                hour: 9,
+                minute: 60,
+            },
+        ),
+    ];
+    for (start_time, end_time, expected) in cases {
+        let error = setup_with(AppInstantiateMsg {
+            price_per_minute: Uint128::from(1u128),
+            denom: AssetEntry::from(DENOM),
+            utc_offset: 0,
+            start_time,
+            end_time,
+            self_gap_minutes: 0,
+            accepted_denoms: vec![],
+            name: None,
+            description: None,
+            contact_uri: None,
+        })
+        .unwrap_err();
+
+        assert!(error
+            .root_cause()
+            .to_string()
+            .contains(&expected.to_string()));
+    }
+    Ok(())
+}
+
 #[test]
 fn request_meeting_at_start_of_day() -> anyhow::Result<()> {
     // Set up the environment and contract
@@ -198,12 +417,27 @@ fn request_meeting_at_start_of_day() -> anyhow::Result<()> {
     )?;
 
     assert_eq!(
-        vec![Meeting {
-            start_time: meeting_start_datetime.timestamp(),
-            end_time: meeting_end_datetime.timestamp(),
-            requester: sender,
-            amount_staked: Uint128::from(60u128),
-        }],
+        meeting_responses(
+            &app,
+            vec![Meeting {
+                start_time: meeting_start_datetime.timestamp(),
+                end_time: meeting_end_datetime.timestamp(),
+                requester: sender,
+                amount_staked: Uint128::from(60u128),
+                refund_splits: None,
+                denom: Some(DENOM.to_string()),
+                reminder_minutes: None,
+                free: false,
+                attended: None,
+                disputed: false,
+                checked_in: false,
+                ended_early_at: None,
+                self_booked: false,
+                resolution: None,
+                cancel_deadline: Some(meeting_start_datetime.timestamp()),
+                encrypted_payload: None,
+            }]
+        )?,
         meetings_response.meetings
     );
 
@@ -240,12 +474,27 @@ fn request_meeting_at_end_of_day() -> anyhow::Result<()> {
     )?;
 
     assert_eq!(
-        vec![Meeting {
-            start_time: meeting_start_datetime.timestamp(),
-            end_time: meeting_end_datetime.timestamp(),
-            requester: sender,
-            amount_staked: Uint128::from(60u128),
-        }],
+        meeting_responses(
+            &app,
+            vec![Meeting {
+                start_time: meeting_start_datetime.timestamp(),
+                end_time: meeting_end_datetime.timestamp(),
+                requester: sender,
+                amount_staked: Uint128::from(60u128),
+                refund_splits: None,
+                denom: Some(DENOM.to_string()),
+                reminder_minutes: None,
+                free: false,
+                attended: None,
+                disputed: false,
+                checked_in: false,
+                ended_early_at: None,
+                self_booked: false,
+                resolution: None,
+                cancel_deadline: Some(meeting_start_datetime.timestamp()),
+                encrypted_payload: None,
+            }]
+        )?,
         meetings_response.meetings
     );
 
@@ -298,20 +547,47 @@ fn request_multiple_meetings_on_same_day() -> anyhow::Result<()> {
     )?;
 
     assert_eq!(
-        vec![
-            Meeting {
-                start_time: meeting_start_datetime1.timestamp(),
-                end_time: meeting_end_datetime1.timestamp(),
-                requester: sender1,
-                amount_staked: Uint128::from(60u128),
-            },
-            Meeting {
-                start_time: meeting_start_datetime2.timestamp(),
-                end_time: meeting_end_datetime2.timestamp(),
-                requester: sender2,
-                amount_staked: Uint128::from(60u128),
-            }
-        ],
+        meeting_responses(
+            &app,
+            vec![
+                Meeting {
+                    start_time: meeting_start_datetime1.timestamp(),
+                    end_time: meeting_end_datetime1.timestamp(),
+                    requester: sender1,
+                    amount_staked: Uint128::from(60u128),
+                    refund_splits: None,
+                    denom: Some(DENOM.to_string()),
+                    reminder_minutes: None,
+                    free: false,
+                    attended: None,
+                    disputed: false,
+                    checked_in: false,
+                    ended_early_at: None,
+                    self_booked: false,
+                    resolution: None,
+                    cancel_deadline: Some(meeting_start_datetime1.timestamp()),
+                    encrypted_payload: None,
+                },
+                Meeting {
+                    start_time: meeting_start_datetime2.timestamp(),
+                    end_time: meeting_end_datetime2.timestamp(),
+                    requester: sender2,
+                    amount_staked: Uint128::from(60u128),
+                    refund_splits: None,
+                    denom: Some(DENOM.to_string()),
+                    reminder_minutes: None,
+                    free: false,
+                    attended: None,
+                    disputed: false,
+                    checked_in: false,
+                    ended_early_at: None,
+                    self_booked: false,
+                    resolution: None,
+                    cancel_deadline: Some(meeting_start_datetime2.timestamp()),
+                    encrypted_payload: None,
+                }
+            ]
+        )?,
         meetings_response.meetings
     );
 
@@ -374,20 +650,47 @@ fn request_back_to_back_meetings_on_left() -> anyhow::Result<()> {
     )?;
 
     assert_eq!(
-        vec![
-            Meeting {
-                start_time: meeting_start_datetime1.timestamp(),
-                end_time: meeting_end_datetime1.timestamp(),
-                requester: sender1,
-                amount_staked: Uint128::from(60u128),
-            },
-            Meeting {
-                start_time: meeting_start_datetime2.timestamp(),
-                end_time: meeting_end_datetime2.timestamp(),
-                requester: sender2,
-                amount_staked: Uint128::from(60u128),
-            }
-        ],
+        meeting_responses(
+            &app,
+            vec![
+                Meeting {
+                    start_time: meeting_start_datetime1.timestamp(),
+                    end_time: meeting_end_datetime1.timestamp(),
+                    requester: sender1,
+                    amount_staked: Uint128::from(60u128),
+                    refund_splits: None,
+                    denom: Some(DENOM.to_string()),
+                    reminder_minutes: None,
+                    free: false,
+                    attended: None,
+                    disputed: false,
+                    checked_in: false,
+                    ended_early_at: None,
+                    self_booked: false,
+                    resolution: None,
+                    cancel_deadline: Some(meeting_start_datetime1.timestamp()),
+                    encrypted_payload: None,
+                },
+                Meeting {
+                    start_time: meeting_start_datetime2.timestamp(),
+                    end_time: meeting_end_datetime2.timestamp(),
+                    requester: sender2,
+                    amount_staked: Uint128::from(60u128),
+                    refund_splits: None,
+                    denom: Some(DENOM.to_string()),
+                    reminder_minutes: None,
+                    free: false,
+                    attended: None,
+                    disputed: false,
+                    checked_in: false,
+                    ended_early_at: None,
+                    self_booked: false,
+                    resolution: None,
+                    cancel_deadline: Some(meeting_start_datetime2.timestamp()),
+                    encrypted_payload: None,
+                }
+            ]
+        )?,
         meetings_response.meetings
     );
 
@@ -450,20 +753,47 @@ fn request_back_to_back_meetings_on_right() -> anyhow::Result<()> {
     )?;
 
     assert_eq!(
-        vec![
-            Meeting {
-                start_time: meeting_start_datetime1.timestamp(),
-                end_time: meeting_end_datetime1.timestamp(),
-                requester: sender1,
-                amount_staked: Uint128::from(60u128),
-            },
-            Meeting {
-                start_time: meeting_start_datetime2.timestamp(),
-                end_time: meeting_end_datetime2.timestamp(),
-                requester: sender2,
-                amount_staked: Uint128::from(60u128),
-            }
-        ],
+        meeting_responses(
+            &app,
+            vec![
+                Meeting {
+                    start_time: meeting_start_datetime1.timestamp(),
+                    end_time: meeting_end_datetime1.timestamp(),
+                    requester: sender1,
+                    amount_staked: Uint128::from(60u128),
+                    refund_splits: None,
+                    denom: Some(DENOM.to_string()),
+                    reminder_minutes: None,
+                    free: false,
+                    attended: None,
+                    disputed: false,
+                    checked_in: false,
+                    ended_early_at: None,
+                    self_booked: false,
+                    resolution: None,
+                    cancel_deadline: Some(meeting_start_datetime1.timestamp()),
+                    encrypted_payload: None,
+                },
+                Meeting {
+                    start_time: meeting_start_datetime2.timestamp(),
+                    end_time: meeting_end_datetime2.timestamp(),
+                    requester: sender2,
+                    amount_staked: Uint128::from(60u128),
+                    refund_splits: None,
+                    denom: Some(DENOM.to_string()),
+                    reminder_minutes: None,
+                    free: false,
+                    attended: None,
+                    disputed: false,
+                    checked_in: false,
+                    ended_early_at: None,
+                    self_booked: false,
+                    resolution: None,
+                    cancel_deadline: Some(meeting_start_datetime2.timestamp()),
+                    encrypted_payload: None,
+                }
+            ]
+        )?,
         meetings_response.meetings
     );
 
@@ -514,12 +844,27 @@ fn request_meetings_on_different_days() -> anyhow::Result<()> {
     )?;
 
     assert_eq!(
-        vec![Meeting {
-            start_time: meeting_start_datetime1.timestamp(),
-            end_time: meeting_end_datetime1.timestamp(),
-            requester: sender1,
-            amount_staked: Uint128::from(60u128),
-        }],
+        meeting_responses(
+            &app,
+            vec![Meeting {
+                start_time: meeting_start_datetime1.timestamp(),
+                end_time: meeting_end_datetime1.timestamp(),
+                requester: sender1,
+                amount_staked: Uint128::from(60u128),
+                refund_splits: None,
+                denom: Some(DENOM.to_string()),
+                reminder_minutes: None,
+                free: false,
+                attended: None,
+                disputed: false,
+                checked_in: false,
+                ended_early_at: None,
+                self_booked: false,
+                resolution: None,
+                cancel_deadline: Some(meeting_start_datetime1.timestamp()),
+                encrypted_payload: None,
+            }]
+        )?,
         meetings_response1.meetings
     );
 
@@ -531,12 +876,27 @@ fn request_meetings_on_different_days() -> anyhow::Result<()> {
     )?;
 
     assert_eq!(
-        vec![Meeting {
-            start_time: meeting_start_datetime2.timestamp(),
-            end_time: meeting_end_datetime2.timestamp(),
-            requester: sender2,
-            amount_staked: Uint128::from(60u128),
-        }],
+        meeting_responses(
+            &app,
+            vec![Meeting {
+                start_time: meeting_start_datetime2.timestamp(),
+                end_time: meeting_end_datetime2.timestamp(),
+                requester: sender2,
+                amount_staked: Uint128::from(60u128),
+                refund_splits: None,
+                denom: Some(DENOM.to_string()),
+                reminder_minutes: None,
+                free: false,
+                attended: None,
+                disputed: false,
+                checked_in: false,
+                ended_early_at: None,
+                self_booked: false,
+                resolution: None,
+                cancel_deadline: Some(meeting_start_datetime2.timestamp()),
+                encrypted_payload: None,
+            }]
+        )?,
         meetings_response2.meetings
     );
 
@@ -561,7 +921,7 @@ fn cannot_request_multiple_meetings_with_same_start_time() -> anyhow::Result<()>
     let sender1 = Addr::unchecked("sender1");
     app.set_sender(&sender1);
 
-    request_meeting_with_start_time(
+    let (conflicting_start, conflicting_end) = request_meeting_with_start_time(
         day_datetime,
         Time {
             hour: 11,
@@ -584,7 +944,12 @@ fn cannot_request_multiple_meetings_with_same_start_time() -> anyhow::Result<()>
     .unwrap_err();
 
     assert_eq!(
-        AppError::MeetingConflictExists {}.to_string(),
+        conflict_error(
+            &app,
+            conflicting_start.timestamp(),
+            conflicting_end.timestamp()
+        )?
+        .to_string(),
         error.root_cause().to_string()
     );
 
@@ -609,7 +974,7 @@ fn cannot_request_meeting_contained_in_another() -> anyhow::Result<()> {
     let sender1 = Addr::unchecked("sender1");
     app.set_sender(&sender1);
 
-    request_meeting(
+    let (conflicting_start, conflicting_end) = request_meeting(
         day_datetime,
         Time {
             hour: 11,
@@ -642,7 +1007,75 @@ fn cannot_request_meeting_contained_in_another() -> anyhow::Result<()> {
     .unwrap_err();
 
     assert_eq!(
-        AppError::MeetingConflictExists {}.to_string(),
+        conflict_error(
+            &app,
+            conflicting_start.timestamp(),
+            conflicting_end.timestamp()
+        )?
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cannot_request_meeting_enveloping_another() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    let block_info: BlockInfo = mock.block_info()?;
+
+    let config: ConfigResponse = app.config()?;
+
+    let timezone: FixedOffset = FixedOffset::east_opt(config.utc_offset).unwrap();
+    let current_datetime = timezone
+        .timestamp_opt(block_info.time.seconds() as i64, 0)
+        .unwrap();
+
+    let day_datetime = current_datetime.checked_add_days(Days::new(1)).unwrap();
+
+    let sender1 = Addr::unchecked("sender1");
+    app.set_sender(&sender1);
+
+    let (conflicting_start, conflicting_end) = request_meeting(
+        day_datetime,
+        Time {
+            hour: 12,
+            minute: 0,
+        },
+        Time {
+            hour: 12,
+            minute: 30,
+        },
+        app.clone(),
+        Coin::new(30, DENOM),
+    )?;
+
+    let sender2 = Addr::unchecked("sender2");
+    app.set_sender(&sender2);
+
+    let error = request_meeting(
+        day_datetime,
+        Time {
+            hour: 11,
+            minute: 0,
+        },
+        Time {
+            hour: 13,
+            minute: 0,
+        },
+        app.clone(),
+        Coin::new(120, DENOM),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        conflict_error(
+            &app,
+            conflicting_start.timestamp(),
+            conflicting_end.timestamp()
+        )?
+        .to_string(),
         error.root_cause().to_string()
     );
 
@@ -667,7 +1100,7 @@ fn cannot_request_meeting_with_left_intersection() -> anyhow::Result<()> {
     let sender1 = Addr::unchecked("sender1");
     app.set_sender(&sender1);
 
-    request_meeting(
+    let (conflicting_start, conflicting_end) = request_meeting(
         day_datetime,
         Time {
             hour: 11,
@@ -700,7 +1133,12 @@ fn cannot_request_meeting_with_left_intersection() -> anyhow::Result<()> {
     .unwrap_err();
 
     assert_eq!(
-        AppError::MeetingConflictExists {}.to_string(),
+        conflict_error(
+            &app,
+            conflicting_start.timestamp(),
+            conflicting_end.timestamp()
+        )?
+        .to_string(),
         error.root_cause().to_string()
     );
 
@@ -725,7 +1163,7 @@ fn cannot_request_meeting_with_right_intersection() -> anyhow::Result<()> {
     let sender1 = Addr::unchecked("sender1");
     app.set_sender(&sender1);
 
-    request_meeting(
+    let (conflicting_start, conflicting_end) = request_meeting(
         day_datetime,
         Time {
             hour: 11,
@@ -758,7 +1196,12 @@ fn cannot_request_meeting_with_right_intersection() -> anyhow::Result<()> {
     .unwrap_err();
 
     assert_eq!(
-        AppError::MeetingConflictExists {}.to_string(),
+        conflict_error(
+            &app,
+            conflicting_start.timestamp(),
+            conflicting_end.timestamp()
+        )?
+        .to_string(),
         error.root_cause().to_string()
     );
 
@@ -881,7 +1324,15 @@ fn cannot_request_meeting_with_start_time_out_of_calendar_bounds() -> anyhow::Re
     .unwrap_err();
 
     assert_eq!(
-        AppError::StartTimeDoesNotFallWithinCalendarBounds {}.to_string(),
+        AppError::StartTimeDoesNotFallWithinCalendarBounds {
+            time: Time {
+                hour: config.start_time.hour - 1,
+                minute: 30,
+            },
+            calendar_start: config.start_time.clone(),
+            calendar_end: config.end_time.clone(),
+        }
+        .to_string(),
         error.root_cause().to_string()
     );
 
@@ -922,7 +1373,15 @@ fn cannot_request_meeting_with_end_time_out_of_calendar_bounds() -> anyhow::Resu
     .unwrap_err();
 
     assert_eq!(
-        AppError::EndTimeDoesNotFallWithinCalendarBounds {}.to_string(),
+        AppError::EndTimeDoesNotFallWithinCalendarBounds {
+            time: Time {
+                hour: config.end_time.hour + 1,
+                minute: 30,
+            },
+            calendar_start: config.start_time.clone(),
+            calendar_end: config.end_time.clone(),
+        }
+        .to_string(),
         error.root_cause().to_string()
     );
 
@@ -962,6 +1421,9 @@ fn cannot_request_meeting_with_start_and_end_being_on_different_days() -> anyhow
             &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
                 start_time: meeting_start_datetime.timestamp().into(),
                 end_time: meeting_end_datetime.timestamp().into(),
+                refund_splits: None,
+                reminder_minutes: None,
+                max_total_stake: None,
             }),
             Some(&[Coin::new(60, DENOM)]),
         )
@@ -1009,7 +1471,8 @@ fn cannot_request_meeting_with_insufficient_funds() -> anyhow::Result<()> {
 
     assert_eq!(
         AppError::InvalidStakeAmountSent {
-            expected_amount: Uint128::from(60u128)
+            expected_amount: Uint128::from(60u128),
+            amount_sent: Uint128::from(30u128)
         }
         .to_string(),
         error.root_cause().to_string(),
@@ -1034,7 +1497,7 @@ fn slash_full_stake() -> anyhow::Result<()> {
     let sender = Addr::unchecked("sender");
     app.set_sender(&sender);
 
-    let (meeting_start_datetime, meeting_end_datetime) = request_meeting_with_start_time(
+    let (meeting_start_datetime, _) = request_meeting_with_start_time(
         current_datetime.checked_add_days(Days::new(1)).unwrap(),
         config.start_time,
         app.clone(),
@@ -1053,15 +1516,7 @@ fn slash_full_stake() -> anyhow::Result<()> {
 
     let meetings_response = app.meetings(day_datetime)?;
 
-    assert_eq!(
-        vec![Meeting {
-            start_time: meeting_start_datetime.timestamp(),
-            end_time: meeting_end_datetime.timestamp(),
-            requester: sender,
-            amount_staked: Uint128::zero(),
-        }],
-        meetings_response.meetings
-    );
+    assert_eq!(Vec::<MeetingResponse>::new(), meetings_response.meetings);
 
     assert_eq!(
         Uint128::from(60u128),
@@ -1087,7 +1542,7 @@ fn return_stake() -> anyhow::Result<()> {
     let sender = Addr::unchecked("sender");
     app.set_sender(&sender);
 
-    let (meeting_start_datetime, meeting_end_datetime) = request_meeting_with_start_time(
+    let (meeting_start_datetime, _) = request_meeting_with_start_time(
         current_datetime.checked_add_days(Days::new(1)).unwrap(),
         config.start_time,
         app.clone(),
@@ -1111,15 +1566,7 @@ fn return_stake() -> anyhow::Result<()> {
 
     let meetings_response = app.meetings(day_datetime)?;
 
-    assert_eq!(
-        vec![Meeting {
-            start_time: meeting_start_datetime.timestamp(),
-            end_time: meeting_end_datetime.timestamp(),
-            requester: sender.clone(),
-            amount_staked: Uint128::zero(),
-        }],
-        meetings_response.meetings
-    );
+    assert_eq!(Vec::<MeetingResponse>::new(), meetings_response.meetings);
 
     assert_eq!(
         Uint128::from(INITIAL_BALANCE),
@@ -1145,7 +1592,7 @@ fn slash_partial_stake() -> anyhow::Result<()> {
     let sender = Addr::unchecked("sender");
     app.set_sender(&sender);
 
-    let (meeting_start_datetime, meeting_end_datetime) = request_meeting_with_start_time(
+    let (meeting_start_datetime, _) = request_meeting_with_start_time(
         current_datetime.checked_add_days(Days::new(1)).unwrap(),
         config.start_time,
         app.clone(),
@@ -1170,15 +1617,7 @@ fn slash_partial_stake() -> anyhow::Result<()> {
 
     let meetings_response = app.meetings(day_datetime)?;
 
-    assert_eq!(
-        vec![Meeting {
-            start_time: meeting_start_datetime.timestamp(),
-            end_time: meeting_end_datetime.timestamp(),
-            requester: sender.clone(),
-            amount_staked: Uint128::zero(),
-        }],
-        meetings_response.meetings
-    );
+    assert_eq!(Vec::<MeetingResponse>::new(), meetings_response.meetings);
 
     assert_eq!(
         Uint128::from(INITIAL_BALANCE - 20),
@@ -1192,3 +1631,7768 @@ fn slash_partial_stake() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn return_all_for_requester() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    let block_info: BlockInfo = mock.block_info()?;
+
+    let config: ConfigResponse = app.config()?;
+
+    let timezone: FixedOffset = FixedOffset::east_opt(config.utc_offset).unwrap();
+    let current_datetime = timezone
+        .timestamp_opt(block_info.time.seconds() as i64, 0)
+        .unwrap();
+
+    let sender1 = Addr::unchecked("sender1");
+    app.set_sender(&sender1);
+
+    let (meeting_start_datetime1, _) = request_meeting_with_start_time(
+        current_datetime.checked_add_days(Days::new(1)).unwrap(),
+        Time {
+            hour: 10,
+            minute: 0,
+        },
+        app.clone(),
+    )?;
+    let (meeting_start_datetime2, _) = request_meeting_with_start_time(
+        current_datetime.checked_add_days(Days::new(2)).unwrap(),
+        Time {
+            hour: 10,
+            minute: 0,
+        },
+        app.clone(),
+    )?;
+
+    let sender2 = Addr::unchecked("sender2");
+    app.set_sender(&sender2);
+
+    let (meeting_start_datetime3, meeting_end_datetime3) = request_meeting_with_start_time(
+        current_datetime.checked_add_days(Days::new(1)).unwrap(),
+        Time {
+            hour: 12,
+            minute: 0,
+        },
+        app.clone(),
+    )?;
+
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 120),
+        mock.query_balance(&sender1, DENOM)?
+    );
+
+    let day_datetime1 = meeting_start_datetime1
+        .date()
+        .and_time(NaiveTime::default())
+        .timestamp();
+    let day_datetime2 = meeting_start_datetime2
+        .date()
+        .and_time(NaiveTime::default())
+        .timestamp();
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::ReturnAllForRequester {
+            requester: sender1.to_string(),
+            start_day: day_datetime1.into(),
+            end_day: day_datetime2.into(),
+        }),
+        None,
+    )?;
+
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender1, DENOM)?
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender2, DENOM)?
+    );
+
+    let meetings_response = app.meetings(day_datetime1)?;
+    assert_eq!(
+        meeting_responses(
+            &app,
+            vec![Meeting {
+                start_time: meeting_start_datetime3.timestamp(),
+                end_time: meeting_end_datetime3.timestamp(),
+                requester: sender2,
+                amount_staked: Uint128::from(60u128),
+                refund_splits: None,
+                denom: Some(DENOM.to_string()),
+                reminder_minutes: None,
+                free: false,
+                attended: None,
+                disputed: false,
+                checked_in: false,
+                ended_early_at: None,
+                self_booked: false,
+                resolution: None,
+                cancel_deadline: Some(meeting_start_datetime3.timestamp()),
+                encrypted_payload: None,
+            }]
+        )?,
+        meetings_response.meetings
+    );
+
+    let meetings_response = app.meetings(day_datetime2)?;
+    assert!(meetings_response.meetings.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn expected_stake_matches_booking_requirement() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    let block_info: BlockInfo = mock.block_info()?;
+
+    let config: ConfigResponse = app.config()?;
+
+    let timezone: FixedOffset = FixedOffset::east_opt(config.utc_offset).unwrap();
+    let current_datetime = timezone
+        .timestamp_opt(block_info.time.seconds() as i64, 0)
+        .unwrap();
+
+    let meeting_start_datetime: NaiveDateTime = current_datetime
+        .checked_add_days(Days::new(1))
+        .unwrap()
+        .date_naive()
+        .and_time(NaiveTime::from_hms_opt(10, 0, 0).unwrap());
+    let meeting_end_datetime: NaiveDateTime = meeting_start_datetime
+        .with_hour(11)
+        .unwrap()
+        .with_minute(30)
+        .unwrap();
+
+    let stake = app.expected_stake(
+        meeting_start_datetime.timestamp(),
+        meeting_end_datetime.timestamp(),
+    )?;
+    assert_eq!(Coin::new(90, DENOM), stake);
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+
+    app.request_meeting_with_auto_funds(
+        meeting_start_datetime.timestamp(),
+        meeting_end_datetime.timestamp(),
+    )?;
+
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 90),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    let meetings_response = app.meetings(
+        meeting_start_datetime
+            .date()
+            .and_time(NaiveTime::default())
+            .timestamp(),
+    )?;
+
+    assert_eq!(
+        meeting_responses(
+            &app,
+            vec![Meeting {
+                start_time: meeting_start_datetime.timestamp(),
+                end_time: meeting_end_datetime.timestamp(),
+                requester: sender,
+                amount_staked: Uint128::from(90u128),
+                refund_splits: None,
+                denom: Some(DENOM.to_string()),
+                reminder_minutes: None,
+                free: false,
+                attended: None,
+                disputed: false,
+                checked_in: false,
+                ended_early_at: None,
+                self_booked: false,
+                resolution: None,
+                cancel_deadline: Some(meeting_start_datetime.timestamp()),
+                encrypted_payload: None,
+            }]
+        )?,
+        meetings_response.meetings
+    );
+
+    Ok(())
+}
+
+/// Returns the quote for a meeting on 2024-03-01 between the given local times.
+fn quote(app: &AppInterface<Mock>, start: &str, end: &str) -> anyhow::Result<QuotePriceResponse> {
+    Ok(app.query(&QueryMsg::from(AppQueryMsg::QuotePrice {
+        start_time: local_timestamp(app, "2024-03-01", start)?.into(),
+        end_time: local_timestamp(app, "2024-03-01", end)?.into(),
+    }))?)
+}
+
+#[test]
+fn quote_displays_amount_with_denom_decimals() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    assert_eq!("60", quote(&app, "10:00", "11:00")?.display_amount);
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: Some(Uint128::from(25_000u128)),
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: Some(6),
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    let response = quote(&app, "10:00", "11:00")?;
+    assert_eq!(Coin::new(1_500_000, DENOM), response.price);
+    assert_eq!("1.5", response.display_amount);
+    assert_eq!("2.25", quote(&app, "10:00", "11:30")?.display_amount);
+    assert_eq!("0.025", quote(&app, "10:00", "10:01")?.display_amount);
+    assert_eq!("12", quote(&app, "09:00", "17:00")?.display_amount);
+
+    Ok(())
+}
+
+#[test]
+fn info_reflects_version_and_features() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, _mock) = setup()?;
+
+    let info = app.info()?;
+    assert_eq!(
+        InfoResponse {
+            contract: APP_ID.to_string(),
+            version: APP_VERSION.to_string(),
+            features: FeatureFlags {
+                free_mode: false,
+                overbooking_allowed: false,
+                allow_partial_outside: false,
+                price_outside_hours: false,
+                check_outstanding_balance: false,
+                allow_admin_booking: false,
+                pegged_pricing: false,
+                claimable_refunds: false,
+                payouts_frozen: false,
+            },
+        },
+        info
+    );
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: Some(Uint128::zero()),
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: Some(3600),
+            attestor: None,
+            allow_partial_outside: Some(true),
+            price_outside_hours: Some(true),
+            price_source: None,
+            check_outstanding_balance: Some(true),
+            time_granularity_seconds: None,
+            allow_admin_booking: Some(true),
+            overbooking_allowed: Some(true),
+            pegged_price_per_minute: Some(Decimal::one()),
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::SetPayoutFreeze { frozen: true }),
+        None,
+    )?;
+    assert_eq!(
+        FeatureFlags {
+            free_mode: true,
+            overbooking_allowed: true,
+            allow_partial_outside: true,
+            price_outside_hours: true,
+            check_outstanding_balance: true,
+            allow_admin_booking: true,
+            pegged_pricing: true,
+            claimable_refunds: true,
+            payouts_frozen: true,
+        },
+        app.info()?.features
+    );
+
+    Ok(())
+}
+
+#[test]
+fn request_meeting_from_deposited_credit() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    let block_info: BlockInfo = mock.block_info()?;
+
+    let config: ConfigResponse = app.config()?;
+
+    let timezone: FixedOffset = FixedOffset::east_opt(config.utc_offset).unwrap();
+    let current_datetime = timezone
+        .timestamp_opt(block_info.time.seconds() as i64, 0)
+        .unwrap();
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::Deposit {}),
+        Some(&[Coin::new(100, DENOM)]),
+    )?;
+    assert_eq!(
+        Uint128::from(100u128),
+        app.credit(sender.to_string())?.amount
+    );
+
+    let meeting_start_datetime: NaiveDateTime = current_datetime
+        .checked_add_days(Days::new(1))
+        .unwrap()
+        .date_naive()
+        .and_time(NaiveTime::from_hms_opt(10, 0, 0).unwrap());
+    let meeting_end_datetime: NaiveDateTime = meeting_start_datetime.with_hour(11).unwrap();
+
+    // Book without attaching funds, the stake is drawn from credit.
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+            start_time: meeting_start_datetime.timestamp().into(),
+            end_time: meeting_end_datetime.timestamp().into(),
+            refund_splits: None,
+            reminder_minutes: None,
+            max_total_stake: None,
+        }),
+        None,
+    )?;
+
+    let meetings_response = app.meetings(
+        meeting_start_datetime
+            .date()
+            .and_time(NaiveTime::default())
+            .timestamp(),
+    )?;
+    assert_eq!(
+        meeting_responses(
+            &app,
+            vec![Meeting {
+                start_time: meeting_start_datetime.timestamp(),
+                end_time: meeting_end_datetime.timestamp(),
+                requester: sender.clone(),
+                amount_staked: Uint128::from(60u128),
+                refund_splits: None,
+                denom: Some(DENOM.to_string()),
+                reminder_minutes: None,
+                free: false,
+                attended: None,
+                disputed: false,
+                checked_in: false,
+                ended_early_at: None,
+                self_booked: false,
+                resolution: None,
+                cancel_deadline: Some(meeting_start_datetime.timestamp()),
+                encrypted_payload: None,
+            }]
+        )?,
+        meetings_response.meetings
+    );
+    assert_eq!(
+        Uint128::from(40u128),
+        app.credit(sender.to_string())?.amount
+    );
+
+    // Cannot withdraw more than the remaining credit.
+    let error: anyhow::Error = app.withdraw(Uint128::from(41u128)).unwrap_err().into();
+    assert_eq!(
+        AppError::InsufficientCredit {
+            available: Uint128::from(40u128)
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    app.withdraw(Uint128::from(40u128))?;
+    assert_eq!(Uint128::zero(), app.credit(sender.to_string())?.amount);
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn max_non_overlapping_meetings() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    let block_info: BlockInfo = mock.block_info()?;
+
+    let config: ConfigResponse = app.config()?;
+
+    let timezone: FixedOffset = FixedOffset::east_opt(config.utc_offset).unwrap();
+    let current_datetime = timezone
+        .timestamp_opt(block_info.time.seconds() as i64, 0)
+        .unwrap();
+
+    let day_datetime = current_datetime.checked_add_days(Days::new(1)).unwrap();
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+
+    let (meeting_start_datetime, _) = request_meeting_with_start_time(
+        day_datetime,
+        Time {
+            hour: 10,
+            minute: 0,
+        },
+        app.clone(),
+    )?;
+    request_meeting_with_start_time(
+        day_datetime,
+        Time {
+            hour: 11,
+            minute: 0,
+        },
+        app.clone(),
+    )?;
+
+    let day_datetime = meeting_start_datetime
+        .date()
+        .and_time(NaiveTime::default())
+        .timestamp();
+
+    assert_eq!(2, app.max_non_overlapping(day_datetime)?.count);
+    assert_eq!(0, app.max_non_overlapping(day_datetime + 86400)?.count);
+
+    Ok(())
+}
+
+#[test]
+fn day_summary_matches_meetings() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender1"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "13:00")?,
+        local_timestamp(&app, "2024-03-01", "13:30")?,
+    )?;
+    app.set_sender(&Addr::unchecked("sender2"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let meetings = app.meetings(day_datetime)?.meetings;
+    let summary = app.day_summary(day_datetime)?;
+
+    assert_eq!(meetings.len() as u32, summary.count);
+    assert_eq!(
+        meetings
+            .iter()
+            .map(|meeting| meeting.amount_staked)
+            .sum::<Uint128>(),
+        summary.total_staked
+    );
+    assert_eq!(
+        meetings.iter().map(|meeting| meeting.start_time).min(),
+        summary.first_start
+    );
+    assert_eq!(
+        meetings.iter().map(|meeting| meeting.end_time).max(),
+        summary.last_end
+    );
+    assert_eq!(Uint128::from(90u128), summary.total_staked);
+    assert_eq!(
+        Some(local_timestamp(&app, "2024-03-01", "10:00")?),
+        summary.first_start
+    );
+
+    let empty_summary = app.day_summary(local_timestamp(&app, "2024-03-02", "00:00")?)?;
+    assert_eq!(0, empty_summary.count);
+    assert_eq!(Uint128::zero(), empty_summary.total_staked);
+    assert_eq!(None, empty_summary.first_start);
+    assert_eq!(None, empty_summary.last_end);
+
+    Ok(())
+}
+
+#[test]
+fn self_gap_between_requester_meetings() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    let block_info: BlockInfo = mock.block_info()?;
+
+    let config: ConfigResponse = app.config()?;
+
+    let timezone: FixedOffset = FixedOffset::east_opt(config.utc_offset).unwrap();
+    let current_datetime = timezone
+        .timestamp_opt(block_info.time.seconds() as i64, 0)
+        .unwrap();
+
+    let day_datetime = current_datetime.checked_add_days(Days::new(1)).unwrap();
+
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: Some(30),
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+    assert_eq!(30, app.config()?.self_gap_minutes);
+
+    let sender1 = Addr::unchecked("sender1");
+    app.set_sender(&sender1);
+
+    request_meeting_with_start_time(
+        day_datetime,
+        Time {
+            hour: 10,
+            minute: 0,
+        },
+        app.clone(),
+    )?;
+
+    // 15 minutes after the end of the first meeting is too close.
+    let error = request_meeting_with_start_time(
+        day_datetime,
+        Time {
+            hour: 11,
+            minute: 15,
+        },
+        app.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        AppError::SelfGapViolated {
+            self_gap_minutes: 30
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Other requesters are not affected by the gap.
+    let sender2 = Addr::unchecked("sender2");
+    app.set_sender(&sender2);
+    request_meeting_with_start_time(
+        day_datetime,
+        Time {
+            hour: 11,
+            minute: 0,
+        },
+        app.clone(),
+    )?;
+
+    // 30 minutes before the start of the first meeting is far enough.
+    app.set_sender(&sender1);
+    request_meeting(
+        day_datetime,
+        Time { hour: 9, minute: 0 },
+        Time {
+            hour: 9,
+            minute: 30,
+        },
+        app.clone(),
+        Coin::new(30, DENOM),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn slash_full_stake_once_meeting_has_ended() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    app.set_sender(&Addr::unchecked("contract2"));
+
+    // 10:30, the meeting is still ongoing.
+    advance_minutes(&app, 150)?;
+    let error: anyhow::Error = app
+        .slash_full_stake(day_datetime.into(), 0)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::MeetingNotFinishedYet {
+            day_datetime,
+            meeting_index: 0
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // 11:01, the meeting has ended.
+    advance_minutes(&app, 31)?;
+    app.slash_full_stake(day_datetime.into(), 0)?;
+
+    assert_eq!(
+        Uint128::from(60u128),
+        mock.query_balance(&Addr::unchecked("contract2"), DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn stake_can_be_resolved_from_the_end_of_the_meeting() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    for hour in ["10:00", "11:00", "12:00"] {
+        let start_time = local_timestamp(&app, "2024-03-01", hour)?;
+        app.request_meeting_with_auto_funds(start_time, start_time + 30 * 60)?;
+    }
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+
+    // One second before the end of the first meeting.
+    set_local_time(&app, "2024-03-01", "10:30")?;
+    app.get_chain()
+        .app
+        .borrow_mut()
+        .update_block(|block| block.time = block.time.minus_seconds(1));
+    let error: anyhow::Error = app.return_stake(day_datetime.into(), 0).unwrap_err().into();
+    assert_eq!(
+        AppError::MeetingNotFinishedYet {
+            day_datetime,
+            meeting_index: 0
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Exactly at the end of the first meeting.
+    app.get_chain().wait_seconds(1)?;
+    app.return_stake(day_datetime.into(), 0)?;
+
+    // One second after the end of the meeting now at index 0.
+    set_local_time(&app, "2024-03-01", "11:30")?;
+    app.get_chain().wait_seconds(1)?;
+    app.return_stake(day_datetime.into(), 0)?;
+
+    let meetings = app.meetings(day_datetime)?.meetings;
+    assert_eq!(1, meetings.len());
+    assert_eq!(MeetingStatus::Upcoming, meetings[0].status);
+
+    Ok(())
+}
+
+#[test]
+fn slash_partial_stake_at_local_time() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-02", "14:00")?,
+        local_timestamp(&app, "2024-03-02", "14:30")?,
+    )?;
+
+    set_local_time(&app, "2024-03-02", "15:00")?;
+    app.set_sender(&Addr::unchecked("contract2"));
+    // 10 minutes late for a 30 minute meeting
+    app.slash_partial_stake(local_timestamp(&app, "2024-03-02", "00:00")?.into(), 0, 10)?;
+
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 10),
+        mock.query_balance(&sender, DENOM)?
+    );
+    assert_eq!(
+        Uint128::from(10u128),
+        mock.query_balance(&Addr::unchecked("contract2"), DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn resolve_stakes_by_local_date_and_time() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender1 = Addr::unchecked("sender1");
+    app.set_sender(&sender1);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+
+    let sender2 = Addr::unchecked("sender2");
+    app.set_sender(&sender2);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+        local_timestamp(&app, "2024-03-01", "12:00")?,
+    )?;
+
+    set_local_time(&app, "2024-03-01", "13:00")?;
+    app.set_sender(&Addr::unchecked("contract2"));
+
+    app.slash_full_for("2024-03-01", "11:00")?;
+    app.return_stake_for("2024-03-01", "10:00")?;
+
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender1, DENOM)?
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender2, DENOM)?
+    );
+
+    let error = app.return_stake_for("2024-03-01", "10:30").unwrap_err();
+    assert!(error.to_string().contains("no meeting starts at"));
+
+    Ok(())
+}
+
+#[test]
+fn book_in_cheapest_accepted_denom() -> anyhow::Result<()> {
+    const OTHER_DENOM: &str = "juno>other";
+
+    // Set up the environment and contract
+    let (_account, abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    abstr.ans_host.execute(
+        &abstract_core::ans_host::ExecuteMsg::UpdateAssetAddresses {
+            to_add: vec![(
+                OTHER_DENOM.to_owned(),
+                AssetInfo::native(OTHER_DENOM).into(),
+            )],
+            to_remove: vec![],
+        },
+        None,
+    )?;
+    let sender = Addr::unchecked("sender");
+    mock.set_balance(
+        &sender,
+        vec![
+            Coin::new(INITIAL_BALANCE, DENOM),
+            Coin::new(INITIAL_BALANCE, OTHER_DENOM),
+        ],
+    )?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: Some(Uint128::from(3u128)),
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: Some(vec![(AssetEntry::from(OTHER_DENOM), Uint128::from(2u128))]),
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    let start_time = local_timestamp(&app, "2024-03-01", "10:00")?;
+    let end_time = local_timestamp(&app, "2024-03-01", "11:00")?;
+    let cheapest: QuotePriceResponse = app.query(&QueryMsg::from(AppQueryMsg::CheapestDenom {
+        start_time: start_time.into(),
+        end_time: end_time.into(),
+    }))?;
+    assert_eq!(Coin::new(120, OTHER_DENOM), cheapest.price);
+    assert_eq!(
+        Coin::new(180, DENOM),
+        app.expected_stake(start_time, end_time)?
+    );
+
+    app.set_sender(&sender);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+            refund_splits: None,
+            reminder_minutes: None,
+            max_total_stake: None,
+        }),
+        Some(&[cheapest.price]),
+    )?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 120),
+        mock.query_balance(&sender, OTHER_DENOM)?
+    );
+
+    set_local_time(&app, "2024-03-01", "12:00")?;
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.return_stake_for("2024-03-01", "10:00")?;
+
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender, OTHER_DENOM)?
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn return_stake_with_refund_splits() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    app.set_sender(&sender);
+
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+                start_time: local_timestamp(&app, "2024-03-01", "10:00")?.into(),
+                end_time: local_timestamp(&app, "2024-03-01", "11:00")?.into(),
+                refund_splits: Some(vec![(alice.to_string(), 6000), (bob.to_string(), 3000)]),
+                reminder_minutes: None,
+                max_total_stake: None,
+            }),
+            Some(&[Coin::new(60, DENOM)]),
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::InvalidRefundSplits { total_bps: 9000 }.to_string(),
+        error.root_cause().to_string()
+    );
+
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+            start_time: local_timestamp(&app, "2024-03-01", "10:00")?.into(),
+            end_time: local_timestamp(&app, "2024-03-01", "11:00")?.into(),
+            refund_splits: Some(vec![(alice.to_string(), 6000), (bob.to_string(), 4000)]),
+            reminder_minutes: None,
+            max_total_stake: None,
+        }),
+        Some(&[Coin::new(60, DENOM)]),
+    )?;
+
+    set_local_time(&app, "2024-03-01", "12:00")?;
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.return_stake_for("2024-03-01", "10:00")?;
+
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender, DENOM)?
+    );
+    assert_eq!(Uint128::from(36u128), mock.query_balance(&alice, DENOM)?);
+    assert_eq!(Uint128::from(24u128), mock.query_balance(&bob, DENOM)?);
+
+    Ok(())
+}
+
+/// The `(day_datetime, meeting_index)` pairs of a page of meetings and the cursor of the next.
+type MeetingKeysPage = (Vec<(i64, u32)>, Option<Binary>);
+
+/// Returns a page of two pending resolutions.
+fn pending_resolutions(
+    app: &AppInterface<Mock>,
+    before: i64,
+    start_after: Option<Binary>,
+) -> anyhow::Result<MeetingKeysPage> {
+    let response: PendingResolutionsResponse =
+        app.query(&QueryMsg::from(AppQueryMsg::PendingResolutions {
+            before: before.into(),
+            start_after,
+            limit: Some(2),
+        }))?;
+    let pairs = response
+        .items
+        .iter()
+        .map(|pending| (pending.day_datetime, pending.meeting_index))
+        .collect();
+    Ok((pairs, response.next_key))
+}
+
+#[test]
+fn return_pending_stakes_in_batch() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    app.set_sender(&sender1);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-02", "10:00")?,
+        local_timestamp(&app, "2024-03-02", "11:00")?,
+    )?;
+    app.set_sender(&sender2);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+        local_timestamp(&app, "2024-03-01", "12:00")?,
+    )?;
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "14:00")?,
+        local_timestamp(&app, "2024-03-01", "15:00")?,
+    )?;
+
+    set_local_time(&app, "2024-03-02", "12:00")?;
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let before = local_timestamp(&app, "2024-03-02", "00:00")?;
+
+    let (first_page, next_key) = pending_resolutions(&app, before, None)?;
+    assert_eq!(vec![(day_datetime, 0), (day_datetime, 1)], first_page);
+    assert!(next_key.is_some());
+    let (second_page, next_key) = pending_resolutions(&app, before, next_key)?;
+    assert_eq!(vec![(day_datetime, 2)], second_page);
+    assert_eq!(None, next_key);
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::ReturnStakes {
+                meetings: vec![(day_datetime.into(), 0); 51],
+            }),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::BatchTooLarge { max: 50 }.to_string(),
+        error.root_cause().to_string()
+    );
+
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::ReturnStakes {
+            meetings: first_page
+                .into_iter()
+                .chain(second_page)
+                .map(|(day_datetime, meeting_index)| (day_datetime.into(), meeting_index))
+                .collect(),
+        }),
+        None,
+    )?;
+
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender1, DENOM)?
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender2, DENOM)?
+    );
+    assert!(pending_resolutions(&app, before, None)?.0.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn export_and_import_state_round_trip() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut source, _mock) = setup()?;
+    set_local_time(&source, "2024-03-01", "08:00")?;
+
+    source.set_sender(&Addr::unchecked("sender1"));
+    source.request_meeting_with_auto_funds(
+        local_timestamp(&source, "2024-03-01", "10:00")?,
+        local_timestamp(&source, "2024-03-01", "11:00")?,
+    )?;
+    source.set_sender(&Addr::unchecked("sender2"));
+    source.request_meeting_with_auto_funds(
+        local_timestamp(&source, "2024-03-01", "13:00")?,
+        local_timestamp(&source, "2024-03-01", "13:30")?,
+    )?;
+    source.request_meeting_with_auto_funds(
+        local_timestamp(&source, "2024-03-04", "09:00")?,
+        local_timestamp(&source, "2024-03-04", "12:00")?,
+    )?;
+
+    let snapshot = source.export_state()?;
+    assert_eq!(2, snapshot.days.len());
+
+    let (_account, _abstr, mut target, _mock) = setup()?;
+    // Not sure how to get this dynamically...
+    target.set_sender(&Addr::unchecked("contract2"));
+    target.import_state(&snapshot, false)?;
+
+    assert_eq!(snapshot, target.export_state()?);
+    let day_datetime = local_timestamp(&target, "2024-03-04", "00:00")?;
+    assert_eq!(
+        Uint128::from(180u128),
+        target.meetings(day_datetime)?.meetings[0].amount_staked
+    );
+
+    let error: anyhow::Error = target.import_state(&snapshot, false).unwrap_err().into();
+    assert_eq!(
+        AppError::DayNotEmpty {
+            day_datetime: snapshot.days[0].0
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Importing again with force leaves the state unchanged
+    target.import_state(&snapshot, true)?;
+    assert_eq!(snapshot, target.export_state()?);
+
+    Ok(())
+}
+
+#[test]
+fn import_checks_each_day() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut source, _mock) = setup()?;
+    set_local_time(&source, "2024-03-01", "08:00")?;
+
+    source.set_sender(&Addr::unchecked("sender1"));
+    for (date, start, end) in [
+        ("2024-03-01", "13:00", "14:00"),
+        ("2024-03-01", "10:00", "11:00"),
+        ("2024-03-04", "09:00", "12:00"),
+    ] {
+        source.request_meeting_with_auto_funds(
+            local_timestamp(&source, date, start)?,
+            local_timestamp(&source, date, end)?,
+        )?;
+    }
+    let snapshot = source.export_state()?;
+    assert_eq!(2, snapshot.days.len());
+
+    let (_account, _abstr, mut target, _mock) = setup()?;
+    set_local_time(&target, "2024-03-01", "08:00")?;
+    target.set_sender(&Addr::unchecked("sender2"));
+    target.request_meeting_with_auto_funds(
+        local_timestamp(&target, "2024-03-04", "14:00")?,
+        local_timestamp(&target, "2024-03-04", "15:00")?,
+    )?;
+
+    // Not sure how to get this dynamically...
+    target.set_sender(&Addr::unchecked("contract2"));
+    let error: anyhow::Error = target.import_state(&snapshot, false).unwrap_err().into();
+    assert_eq!(
+        AppError::DayNotEmpty {
+            day_datetime: snapshot.days[1].0
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    // Nothing was written by the failed import.
+    assert_eq!(1, target.export_state()?.days.len());
+
+    // Days without meetings can be imported next to the existing ones.
+    let mut first_day = snapshot.clone();
+    first_day.days.truncate(1);
+    target.import_state(&first_day, false)?;
+    assert_eq!(2, target.export_state()?.days.len());
+
+    let mut overlapping = snapshot.clone();
+    let mut meeting = overlapping.days[1].1[0].clone();
+    meeting.start_time += 3600;
+    meeting.end_time += 3600;
+    overlapping.days[1].1.push(meeting.clone());
+    let error: anyhow::Error = target.import_state(&overlapping, true).unwrap_err().into();
+    assert_eq!(
+        AppError::ImportedMeetingsOverlap {
+            day_datetime: overlapping.days[1].0,
+            start_time: meeting.start_time,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Forcing overwrites the occupied day, which round-trips through the export.
+    target.import_state(&snapshot, true)?;
+    assert_eq!(snapshot, target.export_state()?);
+
+    Ok(())
+}
+
+#[test]
+fn import_validates_days_meetings_and_config() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    let snapshot = app.export_state()?;
+    let (day_datetime, meetings) = snapshot.days[0].clone();
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let import_error = |snapshot: &CalendarSnapshot| -> String {
+        let error: anyhow::Error = app.import_state(snapshot, true).unwrap_err().into();
+        error.root_cause().to_string()
+    };
+
+    let mut duplicated = snapshot.clone();
+    duplicated.days.push((day_datetime, vec![]));
+    assert_eq!(
+        AppError::DuplicateImportedDay { day_datetime }.to_string(),
+        import_error(&duplicated)
+    );
+
+    let mut shifted = snapshot.clone();
+    shifted.days[0].0 = day_datetime + 3600;
+    assert_eq!(
+        AppError::DayKeyMismatch {
+            day_datetime: day_datetime + 3600
+        }
+        .to_string(),
+        import_error(&shifted)
+    );
+
+    let mut half_minute = snapshot.clone();
+    let meeting = &mut half_minute.days[0].1[0];
+    meeting.end_time = meeting.start_time + 30;
+    assert_eq!(
+        AppError::MeetingNotWholeMinutes {
+            start_time: meetings[0].start_time,
+            end_time: meetings[0].start_time + 30,
+        }
+        .to_string(),
+        import_error(&half_minute)
+    );
+
+    // Splits adding up to more than the stake would fail every later resolution.
+    let mut oversplit = snapshot.clone();
+    oversplit.days[0].1[0].refund_splits = Some(vec![
+        (Addr::unchecked("sender1"), 10_000),
+        (Addr::unchecked("sender2"), 10_000),
+    ]);
+    assert_eq!(
+        AppError::InvalidRefundSplits { total_bps: 20_000 }.to_string(),
+        import_error(&oversplit)
+    );
+
+    // A resolved meeting has no stake held for it anymore.
+    let mut resolved = snapshot.clone();
+    resolved.days[0].1[0].resolution = Some(Resolution {
+        action: ResolutionAction::Return,
+        resolver: Addr::unchecked("contract2"),
+        resolved_at: meetings[0].end_time,
+        amount_to_requester: meetings[0].amount_staked,
+        amount_to_admin: Uint128::zero(),
+    });
+    assert_eq!(
+        AppError::ImportedMeetingResolved {
+            day_datetime,
+            start_time: meetings[0].start_time,
+        }
+        .to_string(),
+        import_error(&resolved)
+    );
+
+    let config_error = |update: fn(&mut Config)| {
+        let mut snapshot = snapshot.clone();
+        update(&mut snapshot.config);
+        import_error(&snapshot)
+    };
+    assert_eq!(
+        AppError::InvalidSlashDistribution { total_bps: 5_000 }.to_string(),
+        config_error(|config| {
+            config.slash_distribution = vec![(Addr::unchecked("sender1"), 5_000)]
+        })
+    );
+    assert_eq!(
+        AppError::InvalidGranularity {
+            granularity_seconds: 90
+        }
+        .to_string(),
+        config_error(|config| config.time_granularity_seconds = 90)
+    );
+    assert_eq!(
+        AppError::InvalidSlashCurve {
+            grace_minutes: 5,
+            full_after_minutes: 5,
+        }
+        .to_string(),
+        config_error(|config| {
+            config.slash_curve = SlashCurve::Threshold {
+                grace_minutes: 5,
+                full_after_minutes: 5,
+            }
+        })
+    );
+    assert_eq!(
+        AppError::InvalidDenom {
+            denom: String::new()
+        }
+        .to_string(),
+        config_error(|config| config.denom = String::new())
+    );
+
+    // None of the rejected imports changed the calendar.
+    assert_eq!(
+        vec![(day_datetime, meetings.clone())],
+        app.export_state()?.days
+    );
+
+    // `Import` writes days without a config and only overwrites them when asked to.
+    let import = |overwrite: bool| {
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::Import {
+                days: resolved.days.clone(),
+                overwrite,
+            }),
+            None,
+        )
+    };
+    let error: anyhow::Error = import(false).unwrap_err().into();
+    assert_eq!(
+        AppError::DayNotEmpty { day_datetime }.to_string(),
+        error.root_cause().to_string()
+    );
+    let error: anyhow::Error = import(true).unwrap_err().into();
+    assert_eq!(
+        AppError::ImportedMeetingResolved {
+            day_datetime,
+            start_time: meetings[0].start_time,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::Import {
+            days: snapshot.days.clone(),
+            overwrite: true,
+        }),
+        None,
+    )?;
+    assert_eq!(vec![(day_datetime, meetings)], app.export_state()?.days);
+
+    Ok(())
+}
+
+#[test]
+fn audit_log_records_stake_movements() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    app.set_sender(&sender1);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "13:00")?,
+        local_timestamp(&app, "2024-03-01", "14:00")?,
+    )?;
+    app.set_sender(&sender2);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+        local_timestamp(&app, "2024-03-01", "12:00")?,
+    )?;
+
+    set_local_time(&app, "2024-03-01", "15:00")?;
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    app.return_stake_for("2024-03-01", "10:00")?;
+    app.slash_full_for("2024-03-01", "11:00")?;
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "13:00")?;
+    app.slash_partial_stake(day_datetime.into(), meeting_index, 15)?;
+
+    // Follow the cursor until the last page, which has none.
+    let mut events = vec![];
+    let mut start_after = None;
+    let mut pages = 0;
+    loop {
+        let response: AuditLogResponse = app.query(&QueryMsg::from(AppQueryMsg::AuditLog {
+            start_after,
+            limit: Some(2),
+        }))?;
+        assert_eq!(Some(4), response.total);
+        assert!(response.items.len() <= 2);
+        events.extend(response.items);
+        pages += 1;
+        match response.next_key {
+            Some(next_key) => start_after = Some(next_key),
+            None => break,
+        }
+    }
+    assert_eq!(2, pages);
+
+    let timestamp = Timestamp::from_seconds(local_timestamp(&app, "2024-03-01", "15:00")? as u64);
+    let event = |movement: StakeMovement, recipient: &Addr, amount: u128| StakeEvent {
+        movement,
+        actor: admin.clone(),
+        recipient: recipient.clone(),
+        amount: Uint128::from(amount),
+        denom: DENOM.to_string(),
+        timestamp,
+    };
+    assert_eq!(
+        vec![
+            (0, event(StakeMovement::Return, &sender1, 60)),
+            (1, event(StakeMovement::Slash, &admin, 60)),
+            (2, event(StakeMovement::Return, &sender1, 45)),
+            (3, event(StakeMovement::Slash, &admin, 15)),
+        ],
+        events
+    );
+
+    Ok(())
+}
+
+#[test]
+fn day_key_is_local_midnight() -> anyhow::Result<()> {
+    for utc_offset in [13 * 3600, -8 * 3600, 0] {
+        let (_account, _abstr, mut app, _mock) = setup_with(AppInstantiateMsg {
+            price_per_minute: Uint128::from(1u128),
+            denom: AssetEntry::from(DENOM),
+            utc_offset,
+            start_time: Time { hour: 0, minute: 0 },
+            end_time: Time {
+                hour: 23,
+                minute: 59,
+            },
+            self_gap_minutes: 0,
+            accepted_denoms: vec![],
+            name: None,
+            description: None,
+            contact_uri: None,
+        })?;
+        set_local_time(&app, "2024-02-29", "12:00")?;
+
+        app.set_sender(&Addr::unchecked("sender"));
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", "00:30")?,
+            local_timestamp(&app, "2024-03-01", "01:00")?,
+        )?;
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", "23:30")?,
+            local_timestamp(&app, "2024-03-01", "23:59")?,
+        )?;
+
+        let local_midnight = local_timestamp(&app, "2024-03-01", "00:00")?;
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2024, 3, 1)
+                .unwrap()
+                .and_time(NaiveTime::default())
+                .timestamp()
+                - i64::from(utc_offset),
+            local_midnight
+        );
+        assert_eq!(2, app.meetings(local_midnight)?.meetings.len());
+        assert_eq!(
+            (local_midnight, 1),
+            app.find_meeting("2024-03-01", "23:30")?
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn day_bounds_apply_negative_utc_offset() -> anyhow::Result<()> {
+    let (_account, _abstr, app, _mock) = setup_with(AppInstantiateMsg {
+        price_per_minute: Uint128::from(1u128),
+        denom: AssetEntry::from(DENOM),
+        utc_offset: -5 * 3600,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 30,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![],
+        name: None,
+        description: None,
+        contact_uri: None,
+    })?;
+
+    // Local midnight of 2024-03-01 is 05:00 UTC, so the calendar is open 14:00 to 22:30 UTC.
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let utc = |time: &str| -> anyhow::Result<i64> {
+        Ok(
+            NaiveDateTime::parse_from_str(&format!("2024-03-01 {time}"), "%Y-%m-%d %H:%M")
+                .map_err(anyhow::Error::msg)?
+                .timestamp(),
+        )
+    };
+    assert_eq!(utc("05:00")?, day_datetime);
+    let bounds: DayBoundsResponse = app.query(&QueryMsg::from(AppQueryMsg::DayBounds {
+        day_datetime: day_datetime.into(),
+    }))?;
+    assert_eq!(
+        DayBoundsResponse {
+            open_ts: utc("14:00")?,
+            close_ts: utc("22:30")?,
+        },
+        bounds
+    );
+
+    // The UTC midnight of the day is not its key.
+    let error: anyhow::Error = app
+        .query::<DayBoundsResponse>(&QueryMsg::from(AppQueryMsg::DayBounds {
+            day_datetime: utc("00:00")?.into(),
+        }))
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::DayKeyMismatch {
+            day_datetime: utc("00:00")?,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn bookings_restricted_to_allowed_weekdays() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-02-29", "08:00")?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: Some(vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ]),
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    // 2024-03-01 is a Friday
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+
+    // 2024-03-03 is a Sunday
+    let error: anyhow::Error = app
+        .request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-03", "10:00")?,
+            local_timestamp(&app, "2024-03-03", "11:00")?,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::WeekdayNotAllowed {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn due_reminders_within_window() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    let request_meeting = |date: &str, start: &str, end: &str, reminder_minutes: Option<u32>| {
+        let start_time = local_timestamp(&app, date, start)?;
+        let end_time = local_timestamp(&app, date, end)?;
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+                start_time: start_time.into(),
+                end_time: end_time.into(),
+                refund_splits: None,
+                reminder_minutes,
+                max_total_stake: None,
+            }),
+            Some(&[app.expected_stake(start_time, end_time)?]),
+        )?;
+        anyhow::Ok(())
+    };
+    request_meeting("2024-03-01", "10:00", "11:00", Some(30))?;
+    request_meeting("2024-03-01", "12:00", "13:00", Some(60))?;
+    request_meeting("2024-03-01", "14:00", "15:00", None)?;
+    request_meeting("2024-03-02", "10:00", "11:00", Some(24 * 60))?;
+
+    let due_reminders_page = |now: &str, lookahead: u64, start_after: Option<Binary>| {
+        let response: DueRemindersResponse =
+            app.query(&QueryMsg::from(AppQueryMsg::DueReminders {
+                now: local_timestamp(&app, "2024-03-01", now)?.into(),
+                lookahead,
+                start_after,
+                limit: Some(2),
+            }))?;
+        anyhow::Ok(response)
+    };
+    let due_reminders = |now: &str, lookahead: u64| {
+        let mut response = due_reminders_page(now, lookahead, None)?;
+        let mut reminders = response.items;
+        while response.next_key.is_some() {
+            response = due_reminders_page(now, lookahead, response.next_key)?;
+            reminders.extend(response.items);
+        }
+        anyhow::Ok(
+            reminders
+                .into_iter()
+                .map(|reminder| {
+                    (
+                        reminder.day_datetime,
+                        reminder.meeting_index,
+                        reminder.remind_at,
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    let first_day = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let second_day = local_timestamp(&app, "2024-03-02", "00:00")?;
+    assert_eq!(
+        vec![
+            (first_day, 0, local_timestamp(&app, "2024-03-01", "09:30")?),
+            (first_day, 1, local_timestamp(&app, "2024-03-01", "11:00")?),
+            (second_day, 0, local_timestamp(&app, "2024-03-01", "10:00")?),
+        ],
+        due_reminders("09:00", 2 * 3600)?
+    );
+    assert_eq!(
+        vec![(first_day, 1, local_timestamp(&app, "2024-03-01", "11:00")?)],
+        due_reminders("10:30", 3600)?
+    );
+    assert!(due_reminders("11:30", 3600)?.is_empty());
+    // The three reminders due from 09:00 take two pages.
+    let first_page = due_reminders_page("09:00", 2 * 3600, None)?;
+    assert_eq!(2, first_page.items.len());
+    assert!(first_page.next_key.is_some());
+    let second_page = due_reminders_page("09:00", 2 * 3600, first_page.next_key)?;
+    assert_eq!(1, second_page.items.len());
+    assert_eq!(None, second_page.next_key);
+    // The earliest `now` lists nothing rather than overflowing.
+    let response: DueRemindersResponse = app.query(&QueryMsg::from(AppQueryMsg::DueReminders {
+        now: i64::MIN.into(),
+        lookahead: 0,
+        start_after: None,
+        limit: None,
+    }))?;
+    assert!(response.items.is_empty());
+    // A lookahead beyond any timestamp is rejected rather than wrapping around.
+    let error = due_reminders("09:00", u64::MAX).unwrap_err();
+    assert_eq!(
+        AppError::ConversionOverflow {
+            value: u64::MAX.to_string(),
+            target: "i64".to_string(),
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    // A lookahead of more days than are read is rejected.
+    let error = due_reminders("09:00", u64::from(MAX_LISTED_DAYS) * 86_400 + 1).unwrap_err();
+    assert_eq!(
+        AppError::RangeTooLarge {
+            max_days: MAX_LISTED_DAYS
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn amend_meeting_before_it_starts() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let amend = |new_start: &str, new_end: &str| {
+        anyhow::Ok(AppExecuteMsg::AmendMeeting {
+            day_datetime: day_datetime.into(),
+            meeting_index: 0,
+            new_start: local_timestamp(&app, "2024-03-01", new_start)?.into(),
+            new_end: local_timestamp(&app, "2024-03-01", new_end)?.into(),
+        })
+    };
+    let shorten = amend("14:00", "14:30")?;
+    let move_back = amend("10:00", "11:00")?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(&abstract_core::base::ExecuteMsg::Module(shorten), None)?;
+
+    assert_eq!(
+        meeting_responses(
+            &app,
+            vec![Meeting {
+                start_time: local_timestamp(&app, "2024-03-01", "14:00")?,
+                end_time: local_timestamp(&app, "2024-03-01", "14:30")?,
+                requester: sender.clone(),
+                amount_staked: Uint128::from(30u128),
+                refund_splits: None,
+                denom: Some(DENOM.to_string()),
+                reminder_minutes: None,
+                free: false,
+                attended: None,
+                disputed: false,
+                checked_in: false,
+                ended_early_at: None,
+                self_booked: false,
+                resolution: None,
+                cancel_deadline: Some(local_timestamp(&app, "2024-03-01", "14:00")?),
+                encrypted_payload: None,
+            }]
+        )?,
+        app.meetings(day_datetime)?.meetings
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 30),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    set_local_time(&app, "2024-03-01", "14:15")?;
+    let error: anyhow::Error = app
+        .execute(&abstract_core::base::ExecuteMsg::Module(move_back), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::MeetingAlreadyStarted {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn amend_meeting_checks_its_keys_and_queues_refunds() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: Some(3600),
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let new_start = local_timestamp(&app, "2024-03-01", "10:00")?;
+    let new_end = local_timestamp(&app, "2024-03-01", "10:30")?;
+    let amend = |day_datetime: i64, new_start: i64, new_end: i64| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::AmendMeeting {
+            day_datetime: day_datetime.into(),
+            meeting_index: 0,
+            new_start: new_start.into(),
+            new_end: new_end.into(),
+        })
+    };
+
+    app.set_sender(&admin);
+    let error: anyhow::Error = app
+        .execute(&amend(day_datetime + 3600, new_start, new_end), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::DayKeyMismatch {
+            day_datetime: day_datetime + 3600
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    let error: anyhow::Error = app
+        .execute(&amend(day_datetime, -1, new_end), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::TimestampOutOfRange { value: -1 }.to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Shortening the meeting returns part of its stake, which waits to be claimed.
+    app.execute(&amend(day_datetime, new_start, new_end), None)?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    app.set_sender(&sender);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::ClaimRefund { refund_id: 0 }),
+        None,
+    )?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 30),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn locate_meeting_requires_unique_match() {
+    let meeting = |start_time: i64, requester: &str| MeetingResponse {
+        start_time,
+        end_time: start_time + 3600,
+        start_local: String::new(),
+        end_local: String::new(),
+        requester: Addr::unchecked(requester),
+        amount_staked: Uint128::from(60u128),
+        denom: DENOM.to_string(),
+        duration_minutes: 60,
+        status: MeetingStatus::Upcoming,
+        refund_splits: None,
+        reminder_minutes: None,
+        free: false,
+        attended: None,
+        disputed: false,
+        checked_in: false,
+        ended_early_at: None,
+        self_booked: false,
+        cancel_deadline: Some(start_time),
+        encrypted_payload: None,
+    };
+    let meetings = vec![
+        meeting(36000, "sender1"),
+        meeting(39600, "sender2"),
+        meeting(39600, "sender3"),
+    ];
+
+    assert_eq!(0, interface::locate_meeting(&meetings, 36000).unwrap());
+
+    let error = interface::locate_meeting(&meetings, 39600).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("multiple meetings start at 39600"));
+
+    let error = interface::locate_meeting(&meetings, 43200).unwrap_err();
+    assert!(error.to_string().contains("no meeting starts at 43200"));
+}
+
+#[test]
+fn free_booking() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup_with(AppInstantiateMsg {
+        price_per_minute: Uint128::zero(),
+        denom: AssetEntry::from(DENOM),
+        utc_offset: 0,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 0,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![],
+        name: None,
+        description: None,
+        contact_uri: None,
+    })?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    let start_time = local_timestamp(&app, "2024-03-01", "10:00")?;
+    let end_time = local_timestamp(&app, "2024-03-01", "11:00")?;
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+            refund_splits: None,
+            reminder_minutes: None,
+            max_total_stake: None,
+        }),
+        None,
+    )?;
+
+    let (day_datetime, _) = app.find_meeting("2024-03-01", "10:00")?;
+    assert_eq!(
+        meeting_responses(
+            &app,
+            vec![Meeting {
+                start_time,
+                end_time,
+                requester: sender.clone(),
+                amount_staked: Uint128::zero(),
+                refund_splits: None,
+                denom: Some(DENOM.to_string()),
+                reminder_minutes: None,
+                free: true,
+                attended: None,
+                disputed: false,
+                checked_in: false,
+                ended_early_at: None,
+                self_booked: false,
+                resolution: None,
+                cancel_deadline: Some(start_time),
+                encrypted_payload: None,
+            }]
+        )?,
+        app.meetings(day_datetime)?.meetings
+    );
+
+    set_local_time(&app, "2024-03-01", "12:00")?;
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    // There is nothing to resolve, so the meeting is just removed.
+    app.slash_full_for("2024-03-01", "10:00")?;
+    assert!(app.meetings(day_datetime)?.meetings.is_empty());
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender, DENOM)?
+    );
+    assert!(
+        pending_resolutions(&app, local_timestamp(&app, "2024-03-02", "00:00")?, None)?
+            .0
+            .is_empty()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cannot_pay_for_free_booking() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup_with(AppInstantiateMsg {
+        price_per_minute: Uint128::zero(),
+        denom: AssetEntry::from(DENOM),
+        utc_offset: 0,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 0,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![],
+        name: None,
+        description: None,
+        contact_uri: None,
+    })?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+                start_time: local_timestamp(&app, "2024-03-01", "10:00")?.into(),
+                end_time: local_timestamp(&app, "2024-03-01", "11:00")?.into(),
+                refund_splits: None,
+                reminder_minutes: None,
+                max_total_stake: None,
+            }),
+            Some(&[Coin::new(60, DENOM)]),
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::PaymentNotRequired {}.to_string(),
+        error.root_cause().to_string()
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn price_update_from_free_to_paid() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup_with(AppInstantiateMsg {
+        price_per_minute: Uint128::zero(),
+        denom: AssetEntry::from(DENOM),
+        utc_offset: 0,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 0,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![],
+        name: None,
+        description: None,
+        contact_uri: None,
+    })?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+            start_time: local_timestamp(&app, "2024-03-01", "10:00")?.into(),
+            end_time: local_timestamp(&app, "2024-03-01", "11:00")?.into(),
+            refund_splits: None,
+            reminder_minutes: None,
+            max_total_stake: None,
+        }),
+        None,
+    )?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: Some(Uint128::from(1u128)),
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    app.set_sender(&sender);
+    let request_afternoon = AppExecuteMsg::RequestMeeting {
+        start_time: local_timestamp(&app, "2024-03-01", "13:00")?.into(),
+        end_time: local_timestamp(&app, "2024-03-01", "14:00")?.into(),
+        refund_splits: None,
+        reminder_minutes: None,
+        max_total_stake: None,
+    };
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(request_afternoon.clone()),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::InvalidStakeAmountSent {
+            expected_amount: Uint128::from(60u128),
+            amount_sent: Uint128::zero()
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(request_afternoon),
+        Some(&[Coin::new(60, DENOM)]),
+    )?;
+
+    set_local_time(&app, "2024-03-01", "15:00")?;
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    // The meeting booked while the calendar was free still has nothing to resolve.
+    assert_eq!(
+        1,
+        pending_resolutions(&app, local_timestamp(&app, "2024-03-02", "00:00")?, None)?
+            .0
+            .len()
+    );
+    app.return_stake_for("2024-03-01", "10:00")?;
+    app.return_stake_for("2024-03-01", "13:00")?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let error: anyhow::Error = app.return_stake(day_datetime.into(), 0).unwrap_err().into();
+    assert_eq!(
+        AppError::NoMeetingsAtGivenDayDateTime { day_datetime }.to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+fn extension_quote(
+    app: &AppInterface<Mock>,
+    date: &str,
+    start: &str,
+    new_end: &str,
+) -> anyhow::Result<ExtensionQuoteResponse> {
+    let (day_datetime, meeting_index) = app.find_meeting(date, start)?;
+    Ok(app.query(&QueryMsg::from(AppQueryMsg::ExtensionQuote {
+        day_datetime: day_datetime.into(),
+        meeting_index,
+        new_end_time: local_timestamp(app, date, new_end)?.into(),
+    }))?)
+}
+
+fn extend_meeting(
+    app: &AppInterface<Mock>,
+    date: &str,
+    start: &str,
+    new_end: &str,
+    funds: Option<&[Coin]>,
+) -> anyhow::Result<()> {
+    let (day_datetime, meeting_index) = app.find_meeting(date, start)?;
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::ExtendMeeting {
+            day_datetime: day_datetime.into(),
+            meeting_index,
+            new_end_time: local_timestamp(app, date, new_end)?.into(),
+        }),
+        funds,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn extension_quote_matches_extend_meeting() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender1 = Addr::unchecked("sender1");
+    app.set_sender(&sender1);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    app.set_sender(&Addr::unchecked("sender2"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "12:00")?,
+        local_timestamp(&app, "2024-03-01", "13:00")?,
+    )?;
+
+    let quote = extension_quote(&app, "2024-03-01", "10:00", "12:30")?;
+    assert_eq!(Coin::new(90, DENOM), quote.additional_stake);
+    assert!(quote.conflicts);
+
+    // Running up to the next meeting does not conflict with it.
+    let quote = extension_quote(&app, "2024-03-01", "10:00", "12:00")?;
+    assert_eq!(Coin::new(60, DENOM), quote.additional_stake);
+    assert!(!quote.conflicts);
+
+    let error = extend_meeting(
+        &app,
+        "2024-03-01",
+        "10:00",
+        "11:30",
+        Some(&[Coin::new(30, DENOM)]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        AppError::NotMeetingRequester {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    app.set_sender(&sender1);
+    let error = extend_meeting(
+        &app,
+        "2024-03-01",
+        "10:00",
+        "12:30",
+        Some(&[Coin::new(90, DENOM)]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        conflict_error(
+            &app,
+            local_timestamp(&app, "2024-03-01", "12:00")?,
+            local_timestamp(&app, "2024-03-01", "13:00")?
+        )?
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // The meeting is already running when it is extended.
+    set_local_time(&app, "2024-03-01", "10:30")?;
+    let quote = extension_quote(&app, "2024-03-01", "10:00", "11:30")?;
+    assert!(!quote.conflicts);
+    extend_meeting(
+        &app,
+        "2024-03-01",
+        "10:00",
+        "11:30",
+        Some(std::slice::from_ref(&quote.additional_stake)),
+    )?;
+
+    let (day_datetime, _) = app.find_meeting("2024-03-01", "10:00")?;
+    let meeting = app.meetings(day_datetime)?.meetings.remove(0);
+    assert_eq!(
+        local_timestamp(&app, "2024-03-01", "11:30")?,
+        meeting.end_time
+    );
+    assert_eq!(
+        Uint128::from(60u128) + quote.additional_stake.amount,
+        meeting.amount_staked
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE) - meeting.amount_staked,
+        mock.query_balance(&sender1, DENOM)?
+    );
+
+    let error = extension_quote(&app, "2024-03-01", "10:00", "11:00").unwrap_err();
+    assert_eq!(
+        AppError::ExtensionMustEndLater {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    set_local_time(&app, "2024-03-01", "11:30")?;
+    let error = extension_quote(&app, "2024-03-01", "10:00", "11:45").unwrap_err();
+    assert_eq!(
+        AppError::MeetingAlreadyEnded {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn expected_stake_overflow_is_an_error() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup_with(AppInstantiateMsg {
+        price_per_minute: Uint128::MAX,
+        denom: AssetEntry::from(DENOM),
+        utc_offset: 0,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 0,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![],
+        name: None,
+        description: None,
+        contact_uri: None,
+    })?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let error: anyhow::Error = app
+        .query::<QuotePriceResponse>(&QueryMsg::from(AppQueryMsg::QuotePrice {
+            start_time: local_timestamp(&app, "2024-03-01", "00:00")?.into(),
+            end_time: local_timestamp(&app, "2024-03-02", "00:00")?.into(),
+        }))
+        .unwrap_err()
+        .into();
+    assert!(error.root_cause().to_string().contains(
+        &AppError::Overflow {
+            lhs: Uint128::from(24 * 60u128),
+            rhs: Uint128::MAX,
+        }
+        .to_string()
+    ));
+
+    app.set_sender(&Addr::unchecked("sender"));
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+                start_time: local_timestamp(&app, "2024-03-01", "10:00")?.into(),
+                end_time: local_timestamp(&app, "2024-03-01", "11:00")?.into(),
+                refund_splits: None,
+                reminder_minutes: None,
+                max_total_stake: None,
+            }),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::Overflow {
+            lhs: Uint128::from(60u128),
+            rhs: Uint128::MAX,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn partial_slash_skips_empty_leg() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "13:00")?,
+        local_timestamp(&app, "2024-03-01", "14:00")?,
+    )?;
+
+    set_local_time(&app, "2024-03-01", "15:00")?;
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "10:00")?;
+    app.slash_partial_stake(day_datetime.into(), meeting_index, 0)?;
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "13:00")?;
+    app.slash_partial_stake(day_datetime.into(), meeting_index, 60)?;
+
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender, DENOM)?
+    );
+    let response: AuditLogResponse = app.query(&QueryMsg::from(AppQueryMsg::AuditLog {
+        start_after: None,
+        limit: None,
+    }))?;
+    assert_eq!(
+        vec![StakeMovement::Return, StakeMovement::Slash],
+        response
+            .items
+            .into_iter()
+            .map(|(_, event)| event.movement)
+            .collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn moving_sole_meeting_removes_its_day() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::AmendMeeting {
+            day_datetime: local_timestamp(&app, "2024-03-01", "00:00")?.into(),
+            meeting_index: 0,
+            new_start: local_timestamp(&app, "2024-03-04", "10:00")?.into(),
+            new_end: local_timestamp(&app, "2024-03-04", "11:00")?.into(),
+        }),
+        None,
+    )?;
+
+    let days: Vec<i64> = app
+        .export_state()?
+        .days
+        .into_iter()
+        .map(|(day_datetime, _)| day_datetime)
+        .collect();
+    assert_eq!(vec![local_timestamp(&app, "2024-03-04", "00:00")?], days);
+
+    Ok(())
+}
+
+#[test]
+fn unique_requesters() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    for (requester, date, start, end) in [
+        ("sender1", "2024-03-01", "10:00", "11:00"),
+        ("sender1", "2024-03-01", "13:00", "14:00"),
+        ("sender2", "2024-03-01", "15:00", "16:00"),
+        ("sender1", "2024-03-04", "10:00", "11:00"),
+        ("sender", "2024-03-04", "13:00", "14:00"),
+    ] {
+        app.set_sender(&Addr::unchecked(requester));
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, date, start)?,
+            local_timestamp(&app, date, end)?,
+        )?;
+    }
+
+    let unique_requesters = |start_date: &str, end_date: &str| -> anyhow::Result<u32> {
+        let response: UniqueRequestersResponse =
+            app.query(&QueryMsg::from(AppQueryMsg::UniqueRequesters {
+                start_day: local_timestamp(&app, start_date, "00:00")?.into(),
+                end_day: local_timestamp(&app, end_date, "00:00")?.into(),
+            }))?;
+        Ok(response.count)
+    };
+    assert_eq!(2, unique_requesters("2024-03-01", "2024-03-01")?);
+    assert_eq!(0, unique_requesters("2024-03-02", "2024-03-03")?);
+    assert_eq!(2, unique_requesters("2024-03-02", "2024-03-04")?);
+    assert_eq!(3, unique_requesters("2024-03-01", "2024-03-04")?);
+    assert_eq!(3, unique_requesters("2024-03-01", "2024-03-31")?);
+    let error = unique_requesters("2024-03-01", "2024-04-01").unwrap_err();
+    assert_eq!(
+        AppError::RangeTooLarge {
+            max_days: MAX_LISTED_DAYS
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn resolve_stakes_after_admin_renounced() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    for (start, end) in [("10:00", "11:00"), ("12:00", "13:00"), ("14:00", "15:00")] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 180),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let attestor = Addr::unchecked("attestor");
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: Some(attestor.to_string()),
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    set_local_time(&app, "2024-03-01", "16:00")?;
+    clear_admin(&app)?;
+
+    // Without an admin, other senders still cannot resolve stakes.
+    app.set_sender(&Addr::unchecked("sender2"));
+    let error: anyhow::Error = app
+        .return_stake_for("2024-03-01", "10:00")
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::NoAdminSet {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    // The attestor can, and the stakes all go back to the requester.
+    app.set_sender(&attestor);
+    app.return_stake_for("2024-03-01", "10:00")?;
+    app.slash_full_for("2024-03-01", "12:00")?;
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "14:00")?;
+    app.slash_partial_stake(day_datetime.into(), meeting_index, 30)?;
+
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender, DENOM)?
+    );
+    assert!(app.meetings(day_datetime)?.meetings.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn booking_fee_is_collected_and_not_returned() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: Some(Uint128::from(5u128)),
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+    assert_eq!(Uint128::from(5u128), app.config()?.booking_fee);
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    let start_time = local_timestamp(&app, "2024-03-01", "10:00")?;
+    let end_time = local_timestamp(&app, "2024-03-01", "11:00")?;
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+                start_time: start_time.into(),
+                end_time: end_time.into(),
+                refund_splits: None,
+                reminder_minutes: None,
+                max_total_stake: None,
+            }),
+            Some(&[Coin::new(60, DENOM)]),
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::InvalidStakeAmountSent {
+            expected_amount: Uint128::from(65u128),
+            amount_sent: Uint128::from(60u128)
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    app.request_meeting_with_auto_funds(start_time, end_time)?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 65),
+        mock.query_balance(&sender, DENOM)?
+    );
+    assert_eq!(Uint128::from(5u128), mock.query_balance(&admin, DENOM)?);
+    let (day_datetime, _) = app.find_meeting("2024-03-01", "10:00")?;
+    assert_eq!(
+        Uint128::from(60u128),
+        app.meetings(day_datetime)?.meetings[0].amount_staked
+    );
+
+    set_local_time(&app, "2024-03-01", "12:00")?;
+    app.set_sender(&admin);
+    app.return_stake_for("2024-03-01", "10:00")?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 5),
+        mock.query_balance(&sender, DENOM)?
+    );
+    assert_eq!(Uint128::from(5u128), mock.query_balance(&admin, DENOM)?);
+
+    Ok(())
+}
+
+#[test]
+fn claim_and_sweep_queued_refunds() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: Some(3600),
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    for (start, end) in [("10:00", "11:00"), ("13:00", "14:00")] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+
+    set_local_time(&app, "2024-03-01", "12:00")?;
+    app.set_sender(&admin);
+    app.return_stake_for("2024-03-01", "10:00")?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 120),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    let claim = |refund_id: u64| AppExecuteMsg::ClaimRefund { refund_id };
+    let sweep = |refund_id: u64| AppExecuteMsg::SweepUnclaimed { refund_id };
+
+    app.set_sender(&Addr::unchecked("sender2"));
+    let error: anyhow::Error = app
+        .execute(&abstract_core::base::ExecuteMsg::Module(claim(0)), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::NotRefundRecipient {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Claimed before the deadline.
+    set_local_time(&app, "2024-03-01", "13:00")?;
+    app.set_sender(&sender);
+    app.execute(&abstract_core::base::ExecuteMsg::Module(claim(0)), None)?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    set_local_time(&app, "2024-03-01", "15:00")?;
+    app.set_sender(&admin);
+    app.return_stake_for("2024-03-01", "13:00")?;
+    let error: anyhow::Error = app
+        .execute(&abstract_core::base::ExecuteMsg::Module(sweep(1)), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::RefundClaimNotExpired {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Swept after the deadline.
+    set_local_time(&app, "2024-03-01", "16:01")?;
+    app.set_sender(&sender);
+    let error: anyhow::Error = app
+        .execute(&abstract_core::base::ExecuteMsg::Module(claim(1)), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::RefundClaimExpired {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    let admin_balance = mock.query_balance(&admin, DENOM)?;
+    app.set_sender(&admin);
+    app.execute(&abstract_core::base::ExecuteMsg::Module(sweep(1)), None)?;
+    assert_eq!(
+        admin_balance + Uint128::from(60u128),
+        mock.query_balance(&admin, DENOM)?
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    let error: anyhow::Error = app
+        .execute(&abstract_core::base::ExecuteMsg::Module(sweep(1)), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::RefundNotFound { refund_id: 1 }.to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Both refunds were logged when queued, then as claimed and swept.
+    let response: AuditLogResponse = app.query(&QueryMsg::from(AppQueryMsg::AuditLog {
+        start_after: None,
+        limit: None,
+    }))?;
+    assert_eq!(
+        vec![
+            (StakeMovement::Return, sender.clone()),
+            (StakeMovement::Claim, sender.clone()),
+            (StakeMovement::Return, sender),
+            (StakeMovement::Sweep, admin),
+        ],
+        response
+            .items
+            .into_iter()
+            .map(|(_, event)| (event.movement, event.recipient))
+            .collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cannot_use_out_of_range_timestamps() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    let end_time = local_timestamp(&app, "2024-03-01", "11:00")?;
+    for start_time in [i64::MIN, -1, i64::MAX] {
+        let error: anyhow::Error = app
+            .execute(
+                &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+                    start_time: start_time.into(),
+                    end_time: end_time.into(),
+                    refund_splits: None,
+                    reminder_minutes: None,
+                    max_total_stake: None,
+                }),
+                Some(&[Coin::new(60, DENOM)]),
+            )
+            .unwrap_err()
+            .into();
+        assert_eq!(
+            AppError::TimestampOutOfRange { value: start_time }.to_string(),
+            error.root_cause().to_string()
+        );
+    }
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    for day_datetime in [i64::MIN, -1, i64::MAX] {
+        let error: anyhow::Error = app.return_stake(day_datetime.into(), 0).unwrap_err().into();
+        assert_eq!(
+            AppError::TimestampOutOfRange {
+                value: day_datetime
+            }
+            .to_string(),
+            error.root_cause().to_string()
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn partial_slash_of_one_unit_stake() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "10:01")?,
+    )?;
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "10:00")?;
+    assert_eq!(
+        Uint128::one(),
+        app.meetings(day_datetime)?.meetings[0].amount_staked
+    );
+
+    set_local_time(&app, "2024-03-01", "11:00")?;
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    let admin_balance = mock.query_balance(&admin, DENOM)?;
+    app.slash_partial_stake(day_datetime.into(), meeting_index, 1)?;
+
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 1),
+        mock.query_balance(&sender, DENOM)?
+    );
+    assert_eq!(
+        admin_balance + Uint128::one(),
+        mock.query_balance(&admin, DENOM)?
+    );
+    assert!(app.meetings(day_datetime)?.meetings.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn withdraw_nothing() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.withdraw(Uint128::zero())?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn settle_expired_by_attested_attendance() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    for (start, end) in [("10:00", "11:00"), ("12:00", "13:00"), ("14:00", "15:00")] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let attest = |meeting_index: u32, attended: bool| AppExecuteMsg::Attest {
+        day_datetime: day_datetime.into(),
+        meeting_index,
+        attended,
+    };
+    let settle = |meeting_index: u32| AppExecuteMsg::SettleExpired {
+        day_datetime: day_datetime.into(),
+        meeting_index,
+    };
+
+    let attestor = Addr::unchecked("sender2");
+    app.set_sender(&attestor);
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(attest(0, true)),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::NotAttestor {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: Some(attestor.to_string()),
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(attest(1, false)),
+        None,
+    )?;
+    app.set_sender(&attestor);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(attest(0, true)),
+        None,
+    )?;
+    assert_eq!(
+        vec![Some(true), Some(false), None],
+        app.meetings(day_datetime)?
+            .meetings
+            .iter()
+            .map(|meeting| meeting.attended)
+            .collect::<Vec<_>>()
+    );
+
+    // Anyone can settle once the meeting ended.
+    app.set_sender(&Addr::unchecked("sender1"));
+    let error: anyhow::Error = app
+        .execute(&abstract_core::base::ExecuteMsg::Module(settle(0)), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::MeetingNotFinishedYet {
+            day_datetime,
+            meeting_index: 0
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    set_local_time(&app, "2024-03-01", "16:00")?;
+    let admin_balance = mock.query_balance(&admin, DENOM)?;
+    app.execute(&abstract_core::base::ExecuteMsg::Module(settle(0)), None)?;
+    // Settling removed the first meeting, so the slashed one moved to index 0.
+    app.execute(&abstract_core::base::ExecuteMsg::Module(settle(0)), None)?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 120),
+        mock.query_balance(&sender, DENOM)?
+    );
+    assert_eq!(
+        admin_balance + Uint128::from(60u128),
+        mock.query_balance(&admin, DENOM)?
+    );
+
+    let error: anyhow::Error = app
+        .execute(&abstract_core::base::ExecuteMsg::Module(settle(0)), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::MeetingNotAttested {
+            day_datetime,
+            meeting_index: 0
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn attestor_can_slash_but_not_update_config() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    for (start, end) in [("10:00", "11:00"), ("12:00", "13:00")] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+
+    let attestor = Addr::unchecked("sender2");
+    let update_price = AppExecuteMsg::UpdateConfig {
+        price_per_minute: Some(Uint128::from(2u128)),
+        denom: None,
+        self_gap_minutes: None,
+        accepted_denoms: None,
+        denom_decimals: None,
+        allowed_weekdays: None,
+        booking_fee: None,
+        return_claim_deadline: None,
+        attestor: None,
+        allow_partial_outside: None,
+        price_outside_hours: None,
+        price_source: None,
+        check_outstanding_balance: None,
+        time_granularity_seconds: None,
+        allow_admin_booking: None,
+        overbooking_allowed: None,
+        pegged_price_per_minute: None,
+        slash_curve: None,
+        cancel_cutoff_secs: None,
+        name: None,
+        description: None,
+        contact_uri: None,
+        slash_distribution: None,
+        force: false,
+    };
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: Some(attestor.to_string()),
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    set_local_time(&app, "2024-03-01", "14:00")?;
+    app.set_sender(&Addr::unchecked("sender1"));
+    let error: anyhow::Error = app
+        .slash_full_for("2024-03-01", "10:00")
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::NotAttestor {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    let admin_balance = mock.query_balance(&admin, DENOM)?;
+    app.set_sender(&attestor);
+    app.slash_full_for("2024-03-01", "10:00")?;
+    app.return_stake_for("2024-03-01", "12:00")?;
+    assert_eq!(
+        admin_balance + Uint128::from(60u128),
+        mock.query_balance(&admin, DENOM)?
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    let error: anyhow::Error = app
+        .execute(&abstract_core::base::ExecuteMsg::Module(update_price), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        cw_controllers::AdminError::NotAdmin {}.to_string(),
+        error.root_cause().to_string()
+    );
+    assert_eq!(Uint128::from(1u128), app.config()?.price_per_minute);
+
+    Ok(())
+}
+
+#[test]
+fn empty_addresses_clear_the_attestor_and_price_source() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let update = |app: &AppInterface<Mock>, address: &str| {
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+                price_per_minute: None,
+                denom: None,
+                self_gap_minutes: None,
+                accepted_denoms: None,
+                denom_decimals: None,
+                allowed_weekdays: None,
+                booking_fee: None,
+                return_claim_deadline: None,
+                attestor: Some(address.to_string()),
+                allow_partial_outside: None,
+                price_outside_hours: None,
+                price_source: Some(address.to_string()),
+                check_outstanding_balance: None,
+                time_granularity_seconds: None,
+                allow_admin_booking: None,
+                overbooking_allowed: None,
+                pegged_price_per_minute: None,
+                slash_curve: None,
+                cancel_cutoff_secs: None,
+                name: None,
+                description: None,
+                contact_uri: None,
+                slash_distribution: None,
+                force: false,
+            }),
+            None,
+        )
+    };
+    update(&app, "attestor")?;
+    let config = app.config()?;
+    assert_eq!(Some(Addr::unchecked("attestor")), config.attestor);
+    assert_eq!(Some(Addr::unchecked("attestor")), config.price_source);
+
+    update(&app, "")?;
+    let config = app.config()?;
+    assert_eq!(None, config.attestor);
+    assert_eq!(None, config.price_source);
+
+    Ok(())
+}
+
+#[test]
+fn resolved_meetings_are_removed_from_their_day() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    app.set_sender(&sender1);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    app.set_sender(&sender2);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "12:00")?,
+        local_timestamp(&app, "2024-03-01", "13:00")?,
+    )?;
+
+    set_local_time(&app, "2024-03-01", "14:00")?;
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.return_stake(day_datetime.into(), 1)?;
+    assert_eq!(
+        vec![sender1.clone()],
+        app.meetings(day_datetime)?
+            .meetings
+            .into_iter()
+            .map(|meeting| meeting.requester)
+            .collect::<Vec<_>>()
+    );
+
+    // The old index no longer exists rather than pointing at another meeting.
+    let error: anyhow::Error = app
+        .slash_full_stake(day_datetime.into(), 1)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::MeetingDoesNotExist {
+            day_datetime,
+            meeting_index: 1
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender1, DENOM)?
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender2, DENOM)?
+    );
+
+    // Resolving the last meeting of the day removes the day.
+    app.return_stake(day_datetime.into(), 0)?;
+    assert!(app.export_state()?.days.is_empty());
+    let error: anyhow::Error = app.return_stake(day_datetime.into(), 0).unwrap_err().into();
+    assert_eq!(
+        AppError::NoMeetingsAtGivenDayDateTime { day_datetime }.to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ical_feed_maps_meetings_to_events() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    app.set_sender(&sender1);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "13:00")?,
+        local_timestamp(&app, "2024-03-01", "14:30")?,
+    )?;
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-05", "10:00")?,
+        local_timestamp(&app, "2024-03-05", "11:00")?,
+    )?;
+    app.set_sender(&sender2);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-02", "09:00")?,
+        local_timestamp(&app, "2024-03-02", "09:30")?,
+    )?;
+
+    let ical_feed = |app: &AppInterface<Mock>,
+                     start_day: i64,
+                     end_day: i64|
+     -> anyhow::Result<Vec<ICalEvent>> {
+        let response: ICalFeedResponse = app.query(&QueryMsg::from(AppQueryMsg::ICalFeed {
+            start_day: start_day.into(),
+            end_day: end_day.into(),
+        }))?;
+        Ok(response.events)
+    };
+    let address = app.address()?;
+    let first_day = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let second_day = local_timestamp(&app, "2024-03-02", "00:00")?;
+    let event =
+        |day_datetime: i64, meeting_index: u32, start: i64, end: i64, requester: &Addr| ICalEvent {
+            uid: format!("{day_datetime}-{meeting_index}@{address}"),
+            dtstart: start,
+            dtend: end,
+            summary: format!("Meeting with {requester}"),
+        };
+    assert_eq!(
+        vec![
+            event(
+                first_day,
+                1,
+                local_timestamp(&app, "2024-03-01", "10:00")?,
+                local_timestamp(&app, "2024-03-01", "11:00")?,
+                &sender2
+            ),
+            event(
+                first_day,
+                0,
+                local_timestamp(&app, "2024-03-01", "13:00")?,
+                local_timestamp(&app, "2024-03-01", "14:30")?,
+                &sender1
+            ),
+            event(
+                second_day,
+                0,
+                local_timestamp(&app, "2024-03-02", "09:00")?,
+                local_timestamp(&app, "2024-03-02", "09:30")?,
+                &sender2
+            ),
+        ],
+        ical_feed(&app, first_day, second_day)?
+    );
+    assert!(ical_feed(
+        &app,
+        local_timestamp(&app, "2024-03-03", "00:00")?,
+        local_timestamp(&app, "2024-03-04", "00:00")?
+    )?
+    .is_empty());
+    let error = ical_feed(
+        &app,
+        local_timestamp(&app, "2024-03-01", "00:00")?,
+        local_timestamp(&app, "2024-04-01", "00:00")?,
+    )
+    .unwrap_err();
+    assert_eq!(
+        AppError::RangeTooLarge {
+            max_days: MAX_LISTED_DAYS
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Overbooked meetings sharing a start time still get distinct UIDs.
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: Some(true),
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+    app.set_sender(&sender1);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    assert_eq!(
+        vec![
+            event(
+                first_day,
+                1,
+                local_timestamp(&app, "2024-03-01", "10:00")?,
+                local_timestamp(&app, "2024-03-01", "11:00")?,
+                &sender2
+            ),
+            event(
+                first_day,
+                2,
+                local_timestamp(&app, "2024-03-01", "10:00")?,
+                local_timestamp(&app, "2024-03-01", "11:00")?,
+                &sender1
+            ),
+            event(
+                first_day,
+                0,
+                local_timestamp(&app, "2024-03-01", "13:00")?,
+                local_timestamp(&app, "2024-03-01", "14:30")?,
+                &sender1
+            ),
+        ],
+        ical_feed(&app, first_day, first_day)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn stake_handling_requires_the_day_key_of_the_meeting() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    let response = app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    assert_eq!(
+        day_datetime.to_string(),
+        response.event_attr_value("wasm", "day_datetime")?
+    );
+
+    set_local_time(&app, "2024-03-01", "12:00")?;
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    let error: anyhow::Error = app
+        .return_stake((day_datetime + 3600).into(), 0)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::DayKeyMismatch {
+            day_datetime: day_datetime + 3600
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // A meeting filed under another day is not resolved through that day's key. Imports
+    // refuse to misfile it, so it is written straight to storage.
+    let mut snapshot = app.export_state()?;
+    let next_day = local_timestamp(&app, "2024-03-02", "00:00")?;
+    snapshot.days[0].0 = next_day;
+    let error: anyhow::Error = app.import_state(&snapshot, true).unwrap_err().into();
+    assert_eq!(
+        AppError::DayKeyMismatch {
+            day_datetime: next_day
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    write_day(&app, next_day, &snapshot.days[0].1)?;
+    let error: anyhow::Error = app.return_stake(next_day.into(), 0).unwrap_err().into();
+    assert_eq!(
+        AppError::DayKeyMismatch {
+            day_datetime: next_day
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    snapshot.days[0].0 = day_datetime;
+    app.import_state(&snapshot, true)?;
+    app.return_stake(day_datetime.into(), 0)?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn meeting_handlers_require_the_day_key_of_the_meeting() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let new_end_time = local_timestamp(&app, "2024-03-01", "11:30")?;
+    set_local_time(&app, "2024-03-01", "10:10")?;
+
+    let handlers = |day_datetime: i64| {
+        [
+            AppExecuteMsg::ExtendMeeting {
+                day_datetime: day_datetime.into(),
+                meeting_index: 0,
+                new_end_time: new_end_time.into(),
+            },
+            AppExecuteMsg::CancelMeeting {
+                day_datetime: day_datetime.into(),
+                meeting_index: 0,
+            },
+            AppExecuteMsg::Attest {
+                day_datetime: day_datetime.into(),
+                meeting_index: 0,
+                attended: true,
+            },
+            AppExecuteMsg::DisputeAttendance {
+                day_datetime: day_datetime.into(),
+                meeting_index: 0,
+            },
+            AppExecuteMsg::CheckIn {
+                day_datetime: day_datetime.into(),
+                meeting_index: 0,
+            },
+            AppExecuteMsg::EndMeeting {
+                day_datetime: day_datetime.into(),
+                meeting_index: 0,
+            },
+        ]
+    };
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    let assert_mismatch = |app: &mut AppInterface<Mock>, day_datetime: i64| -> anyhow::Result<()> {
+        for msg in handlers(day_datetime) {
+            let caller = match msg {
+                AppExecuteMsg::Attest { .. } | AppExecuteMsg::EndMeeting { .. } => &admin,
+                _ => &sender,
+            };
+            app.set_sender(caller);
+            let error: anyhow::Error = app
+                .execute(&abstract_core::base::ExecuteMsg::Module(msg), None)
+                .unwrap_err()
+                .into();
+            assert_eq!(
+                AppError::DayKeyMismatch { day_datetime }.to_string(),
+                error.root_cause().to_string()
+            );
+        }
+        Ok(())
+    };
+
+    assert_mismatch(&mut app, day_datetime + 3600)?;
+
+    // A meeting filed under another day is not reached through that day's key either.
+    let snapshot = app.export_state()?;
+    let next_day = local_timestamp(&app, "2024-03-02", "00:00")?;
+    write_day(&app, next_day, &snapshot.days[0].1)?;
+    assert_mismatch(&mut app, next_day)?;
+
+    app.set_sender(&sender);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::CheckIn {
+            day_datetime: day_datetime.into(),
+            meeting_index: 0,
+        }),
+        None,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn outstanding_stake_out_of_step_is_an_error() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+
+    // A stake of 100 written straight to storage is missing from the outstanding stake of 60.
+    let snapshot = app.export_state()?;
+    let mut stray = snapshot.days[0].1[0].clone();
+    stray.start_time += 86_400;
+    stray.end_time += 86_400;
+    stray.amount_staked = Uint128::from(100u128);
+    let next_day = local_timestamp(&app, "2024-03-02", "00:00")?;
+    write_day(&app, next_day, &[stray])?;
+
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::CancelMeeting {
+                day_datetime: next_day.into(),
+                meeting_index: 0,
+            }),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert!(error
+        .root_cause()
+        .to_string()
+        .contains(&OverflowError::new(OverflowOperation::Sub, 60u128, 100u128).to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn confirm_hold_with_quoted_stake() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    let start_time = local_timestamp(&app, "2024-03-01", "10:00")?;
+    let end_time = local_timestamp(&app, "2024-03-01", "11:00")?;
+    app.set_sender(&sender);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::HoldSlot {
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+        }),
+        None,
+    )?;
+
+    app.set_sender(&Addr::unchecked("sender2"));
+    let error: anyhow::Error = app
+        .request_meeting_with_auto_funds(start_time, end_time)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::SlotHeld {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    app.set_sender(&sender);
+    let confirm =
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::ConfirmHold { hold_id: 0 });
+    let error: anyhow::Error = app
+        .execute(&confirm, Some(&[Coin::new(59, DENOM)]))
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::InvalidStakeAmountSent {
+            expected_amount: Uint128::from(60u128),
+            amount_sent: Uint128::from(59u128)
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    app.execute(&confirm, Some(&[Coin::new(60, DENOM)]))?;
+
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender, DENOM)?
+    );
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    assert_eq!(
+        meeting_responses(
+            &app,
+            vec![Meeting {
+                start_time,
+                end_time,
+                requester: sender,
+                amount_staked: Uint128::from(60u128),
+                refund_splits: None,
+                denom: Some(DENOM.to_string()),
+                reminder_minutes: None,
+                free: false,
+                attended: None,
+                disputed: false,
+                checked_in: false,
+                ended_early_at: None,
+                self_booked: false,
+                resolution: None,
+                cancel_deadline: Some(start_time),
+                encrypted_payload: None,
+            }]
+        )?,
+        app.meetings(day_datetime)?.meetings
+    );
+
+    let error: anyhow::Error = app
+        .execute(&confirm, Some(&[Coin::new(60, DENOM)]))
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::HoldNotFound { hold_id: 0 }.to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cannot_confirm_expired_hold() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    let start_time = local_timestamp(&app, "2024-03-01", "10:00")?;
+    let end_time = local_timestamp(&app, "2024-03-01", "11:00")?;
+    app.set_sender(&sender);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::HoldSlot {
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+        }),
+        None,
+    )?;
+
+    advance_minutes(&app, 16)?;
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::ConfirmHold { hold_id: 0 }),
+            Some(&[Coin::new(60, DENOM)]),
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::HoldExpired {}.to_string(),
+        error.root_cause().to_string()
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    // The slot is free again once the hold expired.
+    app.set_sender(&Addr::unchecked("sender2"));
+    app.request_meeting_with_auto_funds(start_time, end_time)?;
+
+    Ok(())
+}
+
+#[test]
+fn non_payable_messages_reject_funds() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    set_local_time(&app, "2024-03-01", "12:00")?;
+
+    let day_datetime: Int64 = local_timestamp(&app, "2024-03-01", "00:00")?.into();
+    let messages = vec![
+        AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        },
+        AppExecuteMsg::ReturnStake {
+            day_datetime,
+            meeting_index: 0,
+        },
+        AppExecuteMsg::SlashFullStake {
+            day_datetime,
+            meeting_index: 0,
+        },
+        AppExecuteMsg::SlashPartialStake {
+            day_datetime,
+            meeting_index: 0,
+            minutes_late: 10,
+        },
+        AppExecuteMsg::ReturnStakes {
+            meetings: vec![(day_datetime, 0)],
+        },
+        AppExecuteMsg::ReturnAllForRequester {
+            requester: sender.to_string(),
+            start_day: day_datetime,
+            end_day: day_datetime,
+        },
+        AppExecuteMsg::Withdraw {
+            amount: Uint128::zero(),
+        },
+        AppExecuteMsg::SettleExpired {
+            day_datetime,
+            meeting_index: 0,
+        },
+    ];
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    mock.set_balance(&Addr::unchecked("contract2"), coins(100, DENOM))?;
+    for msg in messages {
+        let error: anyhow::Error = app
+            .execute(
+                &abstract_core::base::ExecuteMsg::Module(msg),
+                Some(&[Coin::new(10, DENOM)]),
+            )
+            .unwrap_err()
+            .into();
+        assert_eq!(
+            cw_utils::PaymentError::NonPayable {}.to_string(),
+            error.root_cause().to_string()
+        );
+    }
+    assert_eq!(
+        Uint128::from(100u128),
+        mock.query_balance(&Addr::unchecked("contract2"), DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn recover_funds_not_owed_to_requesters() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    // 25 coins stranded next to the stake of 60.
+    mock.set_balance(&app.address()?, coins(85, DENOM))?;
+
+    let recipient = Addr::unchecked("recipient");
+    let recover = |amount: u128| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RecoverFunds {
+            denom: DENOM.to_string(),
+            amount: Uint128::from(amount),
+            recipient: recipient.to_string(),
+        })
+    };
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let error: anyhow::Error = app.execute(&recover(26), None).unwrap_err().into();
+    assert_eq!(
+        AppError::InsufficientRecoverableFunds {
+            available: Uint128::from(25u128)
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    app.execute(&recover(25), None)?;
+    assert_eq!(
+        Uint128::from(25u128),
+        mock.query_balance(&recipient, DENOM)?
+    );
+    assert_eq!(
+        Uint128::from(60u128),
+        mock.query_balance(&app.address()?, DENOM)?
+    );
+
+    set_local_time(&app, "2024-03-01", "12:00")?;
+    app.return_stake_for("2024-03-01", "10:00")?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn recover_funds_keeps_credit_queued_refunds_and_waitlist_payments() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: Some(3600),
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    for (start, end) in [("10:00", "11:00"), ("13:00", "14:00")] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::Deposit {}),
+        Some(&coins(40, DENOM)),
+    )?;
+    app.set_sender(&Addr::unchecked("sender2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::JoinWaitlist {
+            start_time: local_timestamp(&app, "2024-03-01", "13:00")?.into(),
+            end_time: local_timestamp(&app, "2024-03-01", "14:00")?.into(),
+        }),
+        Some(&coins(60, DENOM)),
+    )?;
+    // The stake of the first meeting is queued to be claimed.
+    set_local_time(&app, "2024-03-01", "12:00")?;
+    app.set_sender(&admin);
+    app.return_stake_for("2024-03-01", "10:00")?;
+
+    // 10 coins stranded next to the stake, queued refund, waitlist payment and credit.
+    mock.set_balance(&app.address()?, coins(60 + 60 + 60 + 40 + 10, DENOM))?;
+    let recover = |amount: u128| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RecoverFunds {
+            denom: DENOM.to_string(),
+            amount: Uint128::from(amount),
+            recipient: admin.to_string(),
+        })
+    };
+    let error: anyhow::Error = app.execute(&recover(11), None).unwrap_err().into();
+    assert_eq!(
+        AppError::InsufficientRecoverableFunds {
+            available: Uint128::from(10u128)
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    app.execute(&recover(10), None)?;
+
+    // Claiming the refund takes it off what is owed as it leaves the balance.
+    app.set_sender(&sender);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::ClaimRefund { refund_id: 0 }),
+        None,
+    )?;
+    app.set_sender(&admin);
+    let error: anyhow::Error = app.execute(&recover(1), None).unwrap_err().into();
+    assert_eq!(
+        AppError::InsufficientRecoverableFunds {
+            available: Uint128::zero()
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn longest_free_block_of_day() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    for (date, start, end) in [
+        ("2024-03-01", "10:00", "11:00"),
+        ("2024-03-01", "13:00", "16:30"),
+        ("2024-03-02", "09:00", "17:00"),
+    ] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, date, start)?,
+            local_timestamp(&app, date, end)?,
+        )?;
+    }
+
+    let longest_free_block = |date: &str| -> anyhow::Result<Option<Slot>> {
+        let response: LongestFreeBlockResponse =
+            app.query(&QueryMsg::from(AppQueryMsg::LongestFreeBlock {
+                day_datetime: local_timestamp(&app, date, "00:00")?,
+            }))?;
+        Ok(response.slot)
+    };
+    // Gaps of one hour, two hours and half an hour.
+    assert_eq!(
+        Some(Slot {
+            start_time: local_timestamp(&app, "2024-03-01", "11:00")?,
+            end_time: local_timestamp(&app, "2024-03-01", "13:00")?,
+        }),
+        longest_free_block("2024-03-01")?
+    );
+    assert_eq!(None, longest_free_block("2024-03-02")?);
+    assert_eq!(
+        Some(Slot {
+            start_time: local_timestamp(&app, "2024-03-03", "09:00")?,
+            end_time: local_timestamp(&app, "2024-03-03", "17:00")?,
+        }),
+        longest_free_block("2024-03-03")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn meetings_running_past_close() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let update_config = |allow_partial_outside, price_outside_hours| AppExecuteMsg::UpdateConfig {
+        price_per_minute: None,
+        denom: None,
+        self_gap_minutes: None,
+        accepted_denoms: None,
+        denom_decimals: None,
+        allowed_weekdays: None,
+        booking_fee: None,
+        return_claim_deadline: None,
+        attestor: None,
+        allow_partial_outside: Some(allow_partial_outside),
+        price_outside_hours: Some(price_outside_hours),
+        price_source: None,
+        check_outstanding_balance: None,
+        time_granularity_seconds: None,
+        allow_admin_booking: None,
+        overbooking_allowed: None,
+        pegged_price_per_minute: None,
+        slash_curve: None,
+        cancel_cutoff_secs: None,
+        name: None,
+        description: None,
+        contact_uri: None,
+        slash_distribution: None,
+        force: false,
+    };
+
+    // Meetings have to end by the close of the calendar hours by default.
+    app.set_sender(&Addr::unchecked("sender"));
+    let error: anyhow::Error = app
+        .request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", "16:30")?,
+            local_timestamp(&app, "2024-03-01", "17:10")?,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::EndTimeDoesNotFallWithinCalendarBounds {
+            time: Time {
+                hour: 17,
+                minute: 10
+            },
+            calendar_start: Time { hour: 9, minute: 0 },
+            calendar_end: Time {
+                hour: 17,
+                minute: 0
+            },
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Only the 30 minutes within the calendar hours are priced.
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(update_config(true, false)),
+        None,
+    )?;
+    app.set_sender(&Addr::unchecked("sender"));
+    let start_time = local_timestamp(&app, "2024-03-01", "16:30")?;
+    let end_time = local_timestamp(&app, "2024-03-01", "17:10")?;
+    assert_eq!(
+        Uint128::from(30u128),
+        app.expected_stake(start_time, end_time)?.amount
+    );
+    app.request_meeting_with_auto_funds(start_time, end_time)?;
+    let meetings = app.meetings(local_timestamp(&app, "2024-03-01", "00:00")?)?;
+    assert_eq!(end_time, meetings.meetings[0].end_time);
+    assert_eq!(Uint128::from(30u128), meetings.meetings[0].amount_staked);
+
+    // The whole span is priced.
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(update_config(true, true)),
+        None,
+    )?;
+    app.set_sender(&Addr::unchecked("sender"));
+    let start_time = local_timestamp(&app, "2024-03-02", "16:30")?;
+    let end_time = local_timestamp(&app, "2024-03-02", "17:10")?;
+    assert_eq!(
+        Uint128::from(40u128),
+        app.expected_stake(start_time, end_time)?.amount
+    );
+    app.request_meeting_with_auto_funds(start_time, end_time)?;
+    let meetings = app.meetings(local_timestamp(&app, "2024-03-02", "00:00")?)?;
+    assert_eq!(Uint128::from(40u128), meetings.meetings[0].amount_staked);
+
+    Ok(())
+}
+
+#[test]
+fn needs_attention_lists_unsettled_and_disputed_meetings() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    for (start, end) in [("10:00", "11:00"), ("12:00", "13:00"), ("14:00", "15:00")] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let dispute = AppExecuteMsg::DisputeAttendance {
+        day_datetime: day_datetime.into(),
+        meeting_index: 1,
+    };
+
+    // Only attested attendance can be disputed.
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(dispute.clone()),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::MeetingNotAttested {
+            day_datetime,
+            meeting_index: 1
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::Attest {
+            day_datetime: day_datetime.into(),
+            meeting_index: 1,
+            attended: false,
+        }),
+        None,
+    )?;
+
+    // Only the requester can dispute it.
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(dispute.clone()),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::NotMeetingRequester {}.to_string(),
+        error.root_cause().to_string()
+    );
+    app.set_sender(&sender);
+    app.execute(&abstract_core::base::ExecuteMsg::Module(dispute), None)?;
+
+    set_local_time(&app, "2024-03-01", "14:30")?;
+    let needs_attention: NeedsAttentionResponse =
+        app.query(&QueryMsg::from(AppQueryMsg::NeedsAttention {
+            start_after: None,
+            limit: None,
+        }))?;
+    let meetings = app.meetings(day_datetime)?.meetings;
+    assert_eq!(
+        vec![
+            AttentionItem {
+                day_datetime,
+                meeting_index: 0,
+                meeting: meetings[0].clone(),
+                reason: AttentionReason::Unsettled,
+            },
+            AttentionItem {
+                day_datetime,
+                meeting_index: 1,
+                meeting: meetings[1].clone(),
+                reason: AttentionReason::Disputed,
+            },
+        ],
+        needs_attention.items
+    );
+
+    // A disputed meeting cannot be settled until it is attested again.
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::SettleExpired {
+                day_datetime: day_datetime.into(),
+                meeting_index: 1,
+            }),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::MeetingDisputed {
+            day_datetime,
+            meeting_index: 1
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn partial_slash_of_corrupted_meeting_fails() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let meeting = |start_time: i64, end_time: i64| Meeting {
+        start_time,
+        end_time,
+        requester: Addr::unchecked("sender"),
+        amount_staked: Uint128::from(60u128),
+        refund_splits: None,
+        denom: None,
+        reminder_minutes: None,
+        free: false,
+        attended: None,
+        disputed: false,
+        checked_in: false,
+        ended_early_at: None,
+        self_booked: false,
+        resolution: None,
+        cancel_deadline: Some(start_time),
+        encrypted_payload: None,
+    };
+    // None of the meetings can be booked, so they are written straight to storage.
+    let too_long = meeting(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-02", "11:00")?,
+    );
+    let reversed = meeting(
+        local_timestamp(&app, "2024-03-01", "08:00")?,
+        local_timestamp(&app, "2024-03-01", "07:00")?,
+    );
+    let start_time = local_timestamp(&app, "2024-03-01", "12:00")?;
+    let half_minute = meeting(start_time, start_time + 30);
+    let days = vec![(
+        day_datetime,
+        vec![too_long.clone(), reversed, half_minute.clone()],
+    )];
+    write_day(&app, day_datetime, &days[0].1)?;
+
+    set_local_time(&app, "2024-03-03", "08:00")?;
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let partial_slash = |meeting_index: u32, minutes_late: u32| {
+        let error: anyhow::Error = app
+            .execute(
+                &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::SlashPartialStake {
+                    day_datetime: day_datetime.into(),
+                    meeting_index,
+                    minutes_late,
+                }),
+                None,
+            )
+            .unwrap_err()
+            .into();
+        error.root_cause().to_string()
+    };
+    assert_eq!(
+        AppError::MeetingTooLong {
+            start_time: too_long.start_time,
+            end_time: too_long.end_time,
+        }
+        .to_string(),
+        partial_slash(0, 10)
+    );
+    assert_eq!(
+        AppError::EndTimeMustBeAfterStartTime {}.to_string(),
+        partial_slash(1, 10)
+    );
+    // A meeting shorter than a minute would otherwise last zero minutes.
+    assert_eq!(
+        AppError::MeetingNotWholeMinutes {
+            start_time: half_minute.start_time,
+            end_time: half_minute.end_time,
+        }
+        .to_string(),
+        partial_slash(2, 0)
+    );
+    assert_eq!(days, app.export_state()?.days);
+
+    Ok(())
+}
+
+#[test]
+fn denom_change_guarded_while_meetings_hold_stake() -> anyhow::Result<()> {
+    const OTHER_DENOM: &str = "juno>other";
+
+    // Set up the environment and contract
+    let (_account, abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    abstr.ans_host.execute(
+        &abstract_core::ans_host::ExecuteMsg::UpdateAssetAddresses {
+            to_add: vec![(
+                OTHER_DENOM.to_owned(),
+                AssetInfo::native(OTHER_DENOM).into(),
+            )],
+            to_remove: vec![],
+        },
+        None,
+    )?;
+    let update_pricing = |price_per_minute: Option<u128>, denom: Option<&str>, force| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: price_per_minute.map(Uint128::from),
+            denom: denom.map(AssetEntry::from),
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force,
+        })
+    };
+
+    // The denom of a clean calendar can be changed freely.
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    let response = app.execute(&update_pricing(None, Some(OTHER_DENOM), false), None)?;
+    assert_eq!(
+        "0",
+        response.event_attr_value("wasm-abstract", "affected_meetings")?
+    );
+    app.execute(&update_pricing(None, Some(DENOM), false), None)?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+
+    app.set_sender(&admin);
+    let error: anyhow::Error = app
+        .execute(&update_pricing(None, Some(OTHER_DENOM), false), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::MeetingsHoldStake { count: 1 }.to_string(),
+        error.root_cause().to_string()
+    );
+    assert_eq!(DENOM, app.export_state()?.config.denom);
+
+    // Price changes are allowed but report the meetings booked at the old price.
+    let response = app.execute(&update_pricing(Some(2), None, false), None)?;
+    assert_eq!(
+        "1",
+        response.event_attr_value("wasm-abstract", "affected_meetings")?
+    );
+
+    let response = app.execute(&update_pricing(None, Some(OTHER_DENOM), true), None)?;
+    assert_eq!(
+        "1",
+        response.event_attr_value("wasm-abstract", "affected_meetings")?
+    );
+    assert_eq!(OTHER_DENOM, app.export_state()?.config.denom);
+
+    // Resolving the stake leaves the calendar clean again.
+    set_local_time(&app, "2024-03-01", "12:00")?;
+    app.return_stake_for("2024-03-01", "10:00")?;
+    let response = app.execute(&update_pricing(None, Some(DENOM), false), None)?;
+    assert_eq!(
+        "0",
+        response.event_attr_value("wasm-abstract", "affected_meetings")?
+    );
+
+    Ok(())
+}
+
+fn oracle_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn oracle_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+/// A price oracle quoting 2.5 usd per unit of `DENOM` and knowing no other denom.
+fn oracle_query(_deps: Deps, _env: Env, msg: OracleQueryMsg) -> StdResult<Binary> {
+    match msg {
+        OracleQueryMsg::Price { denom } if denom == DENOM => to_binary(&OraclePriceResponse {
+            price: Decimal::percent(250),
+            quote: "usd".to_string(),
+        }),
+        OracleQueryMsg::Price { denom } => {
+            Err(StdError::generic_err(format!("No price for {denom}")))
+        }
+    }
+}
+
+#[test]
+fn stake_value_in_quote_currency() -> anyhow::Result<()> {
+    // Set up the environment and contract
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let code_id = mock
+        .app
+        .borrow_mut()
+        .store_code(Box::new(ContractWrapper::new(
+            oracle_execute,
+            oracle_instantiate,
+            oracle_query,
+        )));
+    let oracle = mock
+        .instantiate(code_id, &Empty {}, Some("oracle"), None, &[])?
+        .instantiated_contract_address()?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let stake_value = |app: &AppInterface<Mock>| -> anyhow::Result<StakeValueResponse> {
+        Ok(app.query(&QueryMsg::from(AppQueryMsg::StakeValueInQuote {
+            day_datetime,
+            meeting_index: 0,
+        }))?)
+    };
+    let set_price_source = |price_source: &Addr| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: Some(price_source.to_string()),
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        })
+    };
+
+    // Without a price source only the stake is known.
+    assert_eq!(
+        StakeValueResponse {
+            stake: Coin::new(60, DENOM),
+            value: None,
+            quote: None,
+            error: None,
+        },
+        stake_value(&app)?
+    );
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(&set_price_source(&oracle), None)?;
+    assert_eq!(
+        StakeValueResponse {
+            stake: Coin::new(60, DENOM),
+            value: Some(Decimal::from_atomics(150u128, 0)?),
+            quote: Some("usd".to_string()),
+            error: None,
+        },
+        stake_value(&app)?
+    );
+
+    // A price source that cannot be queried leaves the value out.
+    app.execute(&set_price_source(&Addr::unchecked("sender2")), None)?;
+    let response = stake_value(&app)?;
+    assert_eq!(Coin::new(60, DENOM), response.stake);
+    assert_eq!(None, response.value);
+    assert!(response.error.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn cannot_pay_out_more_than_contract_balance() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    // The contract lost part of the stake of 60.
+    mock.set_balance(&app.address()?, coins(50, DENOM))?;
+
+    set_local_time(&app, "2024-03-01", "12:00")?;
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let error: anyhow::Error = app
+        .return_stake_for("2024-03-01", "10:00")
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::InsufficientContractBalance {
+            needed: Uint128::from(60u128),
+            available: Uint128::from(50u128),
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // The meeting is left in place to be resolved once the contract is topped up.
+    mock.set_balance(&app.address()?, coins(60, DENOM))?;
+    app.return_stake_for("2024-03-01", "10:00")?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn outstanding_stakes_checked_against_balance() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    for (start, end) in [("10:00", "11:00"), ("12:00", "13:00")] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+    let outstanding = |app: &AppInterface<Mock>| -> anyhow::Result<OutstandingResponse> {
+        Ok(app.query(&QueryMsg::from(AppQueryMsg::Outstanding {}))?)
+    };
+    assert_eq!(
+        OutstandingResponse {
+            denom: DENOM.to_string(),
+            outstanding: Uint128::from(120u128),
+            balance: Uint128::from(120u128),
+        },
+        outstanding(&app)?
+    );
+    let check_outstanding_balance = |check_outstanding_balance| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: Some(check_outstanding_balance),
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        })
+    };
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(&check_outstanding_balance(true), None)?;
+
+    // 20 coins are moved out of the contract behind its back.
+    mock.set_balance(&app.address()?, coins(100, DENOM))?;
+    assert_eq!(Uint128::from(100u128), outstanding(&app)?.balance);
+
+    set_local_time(&app, "2024-03-01", "14:00")?;
+    let error: anyhow::Error = app
+        .return_stake_for("2024-03-01", "10:00")
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::InsufficientContractBalance {
+            needed: Uint128::from(120u128),
+            available: Uint128::from(100u128),
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Without the check only the stake being paid out has to be covered.
+    app.execute(&check_outstanding_balance(false), None)?;
+    app.return_stake_for("2024-03-01", "10:00")?;
+    assert_eq!(
+        OutstandingResponse {
+            denom: DENOM.to_string(),
+            outstanding: Uint128::from(60u128),
+            balance: Uint128::from(40u128),
+        },
+        outstanding(&app)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn changing_granularity_revalidates_upcoming_meetings() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:15")?,
+        local_timestamp(&app, "2024-03-01", "10:45")?,
+    )?;
+
+    let update_granularity = |time_granularity_seconds, force| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: Some(time_granularity_seconds),
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force,
+        })
+    };
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+
+    let error: anyhow::Error = app
+        .execute(&update_granularity(90, false), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::InvalidGranularity {
+            granularity_seconds: 90
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // The 10:15 meeting does not fit half hour slots.
+    let error: anyhow::Error = app
+        .execute(&update_granularity(1800, false), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::MeetingsNotAligned {
+            count: 1,
+            granularity_seconds: 1800,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    assert_eq!(0, app.export_state()?.config.time_granularity_seconds);
+
+    // Quarter hours fit it.
+    let response = app.execute(&update_granularity(900, false), None)?;
+    assert_eq!(
+        "0",
+        response.event_attr_value("wasm-abstract", "misaligned_meetings")?
+    );
+
+    let response = app.execute(&update_granularity(1800, true), None)?;
+    assert_eq!(
+        "1",
+        response.event_attr_value("wasm-abstract", "misaligned_meetings")?
+    );
+    assert_eq!(1800, app.export_state()?.config.time_granularity_seconds);
+
+    app.set_sender(&sender);
+    let error: anyhow::Error = app
+        .request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", "11:00")?,
+            local_timestamp(&app, "2024-03-01", "11:15")?,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::TimeNotAlignedToGranularity {
+            time: Time {
+                hour: 11,
+                minute: 15,
+            },
+            granularity_seconds: 1800,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+        local_timestamp(&app, "2024-03-01", "11:30")?,
+    )?;
+
+    // Meetings that already started are not counted, only the one ending at 11:30 is.
+    set_local_time(&app, "2024-03-01", "10:30")?;
+    app.set_sender(&Addr::unchecked("contract2"));
+    let error: anyhow::Error = app
+        .execute(&update_granularity(3600, false), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::MeetingsNotAligned {
+            count: 1,
+            granularity_seconds: 3600,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn check_in_takes_precedence_over_early_end() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    for (start, end) in [("10:00", "11:00"), ("12:00", "13:00")] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let check_in = |meeting_index: u32| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::CheckIn {
+            day_datetime: day_datetime.into(),
+            meeting_index,
+        })
+    };
+    let end_meeting = |meeting_index: u32| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::EndMeeting {
+            day_datetime: day_datetime.into(),
+            meeting_index,
+        })
+    };
+    let settle = |meeting_index: u32| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::SettleExpired {
+            day_datetime: day_datetime.into(),
+            meeting_index,
+        })
+    };
+
+    // Checking in is only possible while the meeting is in progress.
+    let error: anyhow::Error = app.execute(&check_in(0), None).unwrap_err().into();
+    assert_eq!(
+        AppError::MeetingNotInProgress {
+            day_datetime,
+            meeting_index: 0
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    set_local_time(&app, "2024-03-01", "10:10")?;
+    app.execute(&check_in(0), None)?;
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    let error: anyhow::Error = app.execute(&check_in(0), None).unwrap_err().into();
+    assert_eq!(
+        AppError::NotMeetingRequester {}.to_string(),
+        error.root_cause().to_string()
+    );
+    set_local_time(&app, "2024-03-01", "10:20")?;
+    app.execute(&end_meeting(0), None)?;
+    let meeting = app.meetings(day_datetime)?.meetings[0].clone();
+    assert!(meeting.checked_in);
+    assert_eq!(
+        Some(local_timestamp(&app, "2024-03-01", "10:20")?),
+        meeting.ended_early_at
+    );
+
+    // The meeting can be settled once it was ended, and the check-in returns the full stake.
+    set_local_time(&app, "2024-03-01", "10:30")?;
+    app.set_sender(&Addr::unchecked("sender1"));
+    let response = app.execute(&settle(0), None)?;
+    assert_eq!(
+        "60",
+        response.event_attr_value("wasm-abstract", "amount_returned")?
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    // Without a check-in the early end allows slashing. Settling moved this meeting to index 0.
+    set_local_time(&app, "2024-03-01", "12:15")?;
+    app.set_sender(&admin);
+    app.execute(&end_meeting(0), None)?;
+    let error: anyhow::Error = app.execute(&end_meeting(0), None).unwrap_err().into();
+    assert_eq!(
+        AppError::MeetingNotInProgress {
+            day_datetime,
+            meeting_index: 0
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    app.set_sender(&sender);
+    let error: anyhow::Error = app.execute(&check_in(0), None).unwrap_err().into();
+    assert_eq!(
+        AppError::MeetingNotInProgress {
+            day_datetime,
+            meeting_index: 0
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    set_local_time(&app, "2024-03-01", "12:20")?;
+    let admin_balance = mock.query_balance(&admin, DENOM)?;
+    let response = app.execute(&settle(0), None)?;
+    assert_eq!(
+        "60",
+        response.event_attr_value("wasm-abstract", "amount_slashed")?
+    );
+    assert_eq!(
+        admin_balance + Uint128::from(60u128),
+        mock.query_balance(&admin, DENOM)?
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn payout_freeze_blocks_payouts_but_not_bookings() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: Some(3600),
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    for (start, end) in [("10:00", "11:00"), ("13:00", "14:00")] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+    set_local_time(&app, "2024-03-01", "12:00")?;
+    app.set_sender(&admin);
+    app.return_stake_for("2024-03-01", "10:00")?;
+
+    let set_payout_freeze =
+        |frozen| abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::SetPayoutFreeze { frozen });
+    let claim = |refund_id| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::ClaimRefund { refund_id })
+    };
+    let sweep = |refund_id| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::SweepUnclaimed { refund_id })
+    };
+    let assert_frozen = |result: Result<_, CwOrchError>| {
+        let error: anyhow::Error = result.unwrap_err().into();
+        assert_eq!(
+            AppError::PayoutsFrozen {}.to_string(),
+            error.root_cause().to_string()
+        );
+    };
+
+    app.set_sender(&sender);
+    assert!(app.execute(&set_payout_freeze(true), None).is_err());
+    app.set_sender(&admin);
+    let response = app.execute(&set_payout_freeze(true), None)?;
+    assert_eq!(
+        "true",
+        response.event_attr_value("wasm-abstract", "payouts_frozen")?
+    );
+
+    // The queued refund cannot be claimed while frozen.
+    app.set_sender(&sender);
+    assert_frozen(app.execute(&claim(0), None));
+    // Bookings and queries keep working.
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "15:00")?,
+        local_timestamp(&app, "2024-03-01", "16:00")?,
+    )?;
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    assert_eq!(2, app.meetings(day_datetime)?.meetings.len());
+
+    set_local_time(&app, "2024-03-01", "14:30")?;
+    app.set_sender(&admin);
+    assert_frozen(app.return_stake_for("2024-03-01", "13:00"));
+    assert_frozen(app.slash_full_for("2024-03-01", "13:00"));
+
+    // The claim deadline passed while frozen, the unclaimed refund cannot be swept either.
+    assert_frozen(app.execute(&sweep(0), None));
+
+    let response = app.execute(&set_payout_freeze(false), None)?;
+    assert_eq!(
+        "false",
+        response.event_attr_value("wasm-abstract", "payouts_frozen")?
+    );
+    // Nothing was lost while frozen.
+    app.return_stake_for("2024-03-01", "13:00")?;
+    let admin_balance = mock.query_balance(&admin, DENOM)?;
+    app.execute(&sweep(0), None)?;
+    assert_eq!(
+        admin_balance + Uint128::from(60u128),
+        mock.query_balance(&admin, DENOM)?
+    );
+    set_local_time(&app, "2024-03-01", "14:45")?;
+    app.set_sender(&sender);
+    app.execute(&claim(1), None)?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 120),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn admin_booking_policy() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    mock.set_balance(&admin, coins(INITIAL_BALANCE, DENOM))?;
+    app.set_sender(&admin);
+    let error: anyhow::Error = app
+        .request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", "10:00")?,
+            local_timestamp(&app, "2024-03-01", "11:00")?,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::AdminBookingNotAllowed {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Personal time can be blocked either way.
+    let block_slot = |app: &AppInterface<Mock>, start: &str, end: &str| -> anyhow::Result<()> {
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::BlockSlot {
+                start_time: local_timestamp(app, "2024-03-01", start)?.into(),
+                end_time: local_timestamp(app, "2024-03-01", end)?.into(),
+            }),
+            None,
+        )?;
+        Ok(())
+    };
+    block_slot(&app, "12:00", "13:00")?;
+
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: Some(true),
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    block_slot(&app, "16:00", "17:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "14:00")?,
+        local_timestamp(&app, "2024-03-01", "15:00")?,
+    )?;
+    let error: anyhow::Error = app
+        .request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", "12:30")?,
+            local_timestamp(&app, "2024-03-01", "13:30")?,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        conflict_error(
+            &app,
+            local_timestamp(&app, "2024-03-01", "12:00")?,
+            local_timestamp(&app, "2024-03-01", "13:00")?
+        )?
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    let error = block_slot(&app, "13:00", "14:00").unwrap_err();
+    assert_eq!(
+        cw_controllers::AdminError::NotAdmin {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    assert_eq!(
+        vec![true, true, true, false],
+        app.meetings(day_datetime)?
+            .meetings
+            .iter()
+            .map(|meeting| meeting.self_booked)
+            .collect::<Vec<_>>()
+    );
+
+    // The admin's own bookings are left out of the statistics.
+    let summary: DaySummaryResponse =
+        app.query(&QueryMsg::from(AppQueryMsg::DaySummary { day_datetime }))?;
+    assert_eq!(
+        DaySummaryResponse {
+            count: 1,
+            total_staked: Uint128::from(60u128),
+            self_booked: 3,
+            first_start: Some(local_timestamp(&app, "2024-03-01", "10:00")?),
+            last_end: Some(local_timestamp(&app, "2024-03-01", "17:00")?),
+        },
+        summary
+    );
+    let requesters: UniqueRequestersResponse =
+        app.query(&QueryMsg::from(AppQueryMsg::UniqueRequesters {
+            start_day: day_datetime.into(),
+            end_day: day_datetime.into(),
+        }))?;
+    assert_eq!(1, requesters.count);
+
+    Ok(())
+}
+
+#[test]
+fn list_queries_clamp_the_page_limit() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    let days = DEFAULT_PAGE_LIMIT as u64 + 2;
+    for day in 1..=days {
+        let date = format!("2024-03-{day:02}");
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, &date, "10:00")?,
+            local_timestamp(&app, &date, "11:00")?,
+        )?;
+    }
+
+    let before = local_timestamp(&app, "2024-04-01", "00:00")?;
+    let pending = |limit: Option<u32>| -> anyhow::Result<PendingResolutionsResponse> {
+        Ok(app.query(&QueryMsg::from(AppQueryMsg::PendingResolutions {
+            before: before.into(),
+            start_after: None,
+            limit,
+        }))?)
+    };
+    let export = |limit: Option<u32>| -> anyhow::Result<ExportResponse> {
+        Ok(app.query(&QueryMsg::from(AppQueryMsg::Export {
+            start_after: None,
+            limit,
+        }))?)
+    };
+
+    // Without a limit a page holds the default and says so.
+    let page = pending(None)?;
+    assert_eq!(DEFAULT_PAGE_LIMIT, page.limit);
+    assert_eq!(DEFAULT_PAGE_LIMIT as usize, page.items.len());
+    assert!(page.next_key.is_some());
+    let page = export(None)?;
+    assert_eq!(DEFAULT_PAGE_LIMIT, page.limit);
+    assert_eq!(DEFAULT_PAGE_LIMIT as usize, page.days.len());
+
+    // An absurd limit is capped, which here still covers every entry.
+    let page = pending(Some(u32::MAX))?;
+    assert_eq!(MAX_PAGE_LIMIT, page.limit);
+    assert_eq!(days as usize, page.items.len());
+    assert_eq!(None, page.next_key);
+    let page = export(Some(u32::MAX))?;
+    assert_eq!(MAX_PAGE_LIMIT, page.limit);
+    assert_eq!(days as usize, page.days.len());
+
+    Ok(())
+}
+
+#[test]
+fn export_pages_through_days() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    let dates = [
+        "2024-03-01",
+        "2024-03-04",
+        "2024-03-05",
+        "2024-03-06",
+        "2024-03-07",
+    ];
+    for date in dates {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, date, "10:00")?,
+            local_timestamp(&app, date, "11:00")?,
+        )?;
+    }
+    // A second meeting on a day is exported with it.
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-04", "13:00")?,
+        local_timestamp(&app, "2024-03-04", "14:00")?,
+    )?;
+
+    let export = |start_after: Option<i64>| -> anyhow::Result<ExportResponse> {
+        Ok(app.query(&QueryMsg::from(AppQueryMsg::Export {
+            start_after,
+            limit: Some(2),
+        }))?)
+    };
+    let mut pages = vec![];
+    let mut start_after = None;
+    loop {
+        let page = export(start_after)?;
+        assert_eq!(app.config()?.price_per_minute, page.config.price_per_minute);
+        if page.days.is_empty() {
+            break;
+        }
+        start_after = page.days.last().map(|(day_datetime, _)| *day_datetime);
+        pages.push(
+            page.days
+                .into_iter()
+                .map(|(day_datetime, meetings)| (day_datetime, meetings.len()))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    let day = |date: &str| local_timestamp(&app, date, "00:00");
+    assert_eq!(
+        vec![
+            vec![(day(dates[0])?, 1), (day(dates[1])?, 2)],
+            vec![(day(dates[2])?, 1), (day(dates[3])?, 1)],
+            vec![(day(dates[4])?, 1)],
+        ],
+        pages
+    );
+    // The pages add up to the full snapshot.
+    assert_eq!(5, app.export_state()?.days.len());
+
+    Ok(())
+}
+
+#[test]
+fn calendar_window_is_half_open() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: Some(true),
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    let request = |app: &AppInterface<Mock>, start: &str, end: &str| -> anyhow::Result<()> {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(app, "2024-03-01", start)?,
+            local_timestamp(app, "2024-03-01", end)?,
+        )?;
+        Ok(())
+    };
+
+    // Even meetings allowed to run past the close cannot start at it.
+    let error = request(&app, "17:00", "17:30").unwrap_err();
+    assert_eq!(
+        AppError::StartTimeAtCalendarClose {
+            time: Time {
+                hour: 17,
+                minute: 0
+            },
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    let error = request(&app, "08:30", "09:00").unwrap_err();
+    assert_eq!(
+        AppError::StartTimeDoesNotFallWithinCalendarBounds {
+            time: Time {
+                hour: 8,
+                minute: 30
+            },
+            calendar_start: Time { hour: 9, minute: 0 },
+            calendar_end: Time {
+                hour: 17,
+                minute: 0
+            },
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Meetings can start at the opening and end at the close.
+    request(&app, "09:00", "09:30")?;
+    request(&app, "16:30", "17:00")?;
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let longest_free_block: LongestFreeBlockResponse =
+        app.query(&QueryMsg::from(AppQueryMsg::LongestFreeBlock {
+            day_datetime,
+        }))?;
+    assert_eq!(
+        Some(Slot {
+            start_time: local_timestamp(&app, "2024-03-01", "09:30")?,
+            end_time: local_timestamp(&app, "2024-03-01", "16:30")?,
+        }),
+        longest_free_block.slot
+    );
+
+    Ok(())
+}
+
+#[test]
+fn overbooking_policy() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let book = |app: &AppInterface<Mock>, start: &str, end: &str| -> anyhow::Result<()> {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(app, "2024-03-01", start)?,
+            local_timestamp(app, "2024-03-01", end)?,
+        )?;
+        Ok(())
+    };
+    app.set_sender(&Addr::unchecked("sender1"));
+    book(&app, "10:00", "11:00")?;
+
+    // Overlapping bookings are rejected by default.
+    app.set_sender(&Addr::unchecked("sender2"));
+    let error = book(&app, "10:30", "11:30").unwrap_err();
+    assert_eq!(
+        conflict_error(
+            &app,
+            local_timestamp(&app, "2024-03-01", "10:00")?,
+            local_timestamp(&app, "2024-03-01", "11:00")?
+        )?
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: Some(true),
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+    app.set_sender(&Addr::unchecked("sender2"));
+    book(&app, "10:30", "11:30")?;
+    app.set_sender(&Addr::unchecked("sender"));
+    book(&app, "10:00", "11:00")?;
+    book(&app, "13:00", "14:00")?;
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    assert_eq!(4, app.meetings(day_datetime)?.meetings.len());
+    let overlaps: OverlapsResponse =
+        app.query(&QueryMsg::from(AppQueryMsg::Overlaps { day_datetime }))?;
+    assert_eq!(vec![(0, 1), (0, 2), (1, 2)], overlaps.pairs);
+
+    // The overbooked day survives a round trip through the export.
+    let snapshot = app.export_state()?;
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.import_state(&snapshot, true)?;
+    assert_eq!(snapshot, app.export_state()?);
+
+    Ok(())
+}
+
+#[test]
+fn cancelled_meeting_promotes_waitlist() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let join = |app: &AppInterface<Mock>, start: &str, end: &str| -> anyhow::Result<()> {
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::JoinWaitlist {
+                start_time: local_timestamp(app, "2024-03-01", start)?.into(),
+                end_time: local_timestamp(app, "2024-03-01", end)?.into(),
+            }),
+            Some(&coins(60, DENOM)),
+        )?;
+        Ok(())
+    };
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender1);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+
+    // Only taken slots can be waited for.
+    app.set_sender(&sender2);
+    let error = join(&app, "13:00", "14:00").unwrap_err();
+    assert_eq!(
+        AppError::SlotAvailable {}.to_string(),
+        error.root_cause().to_string()
+    );
+    join(&app, "10:00", "11:00")?;
+    app.set_sender(&sender);
+    join(&app, "10:30", "11:30")?;
+
+    // Cancelling frees the slot for the oldest entry; the overlapping one stays queued.
+    app.set_sender(&sender1);
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let response = app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::CancelMeeting {
+            day_datetime: day_datetime.into(),
+            meeting_index: 0,
+        }),
+        None,
+    )?;
+    assert_eq!(
+        "1",
+        response.event_attr_value("wasm-abstract", "promoted_entries")?
+    );
+    let meetings = app.meetings(day_datetime)?.meetings;
+    assert_eq!(1, meetings.len());
+    assert_eq!(sender2, meetings[0].requester);
+    assert_eq!(Uint128::new(60), meetings[0].amount_staked);
+    let waitlist: WaitlistResponse = app.query(&QueryMsg::from(AppQueryMsg::Waitlist {
+        start_after: None,
+        limit: None,
+    }))?;
+    assert_eq!(1, waitlist.items.len());
+    assert_eq!(sender, waitlist.items[0].1.requester);
+
+    // Leaving the waitlist refunds the payment.
+    let entry_id = waitlist.items[0].0;
+    app.set_sender(&sender1);
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::LeaveWaitlist {
+                day_datetime: day_datetime.into(),
+                entry_id,
+            }),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::NotMeetingRequester {}.to_string(),
+        error.root_cause().to_string()
+    );
+    app.set_sender(&sender);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::LeaveWaitlist {
+            day_datetime: day_datetime.into(),
+            entry_id,
+        }),
+        None,
+    )?;
+
+    assert_eq!(
+        Uint128::new(INITIAL_BALANCE),
+        mock.query_balance(&sender1, DENOM)?
+    );
+    assert_eq!(
+        Uint128::new(INITIAL_BALANCE - 60),
+        mock.query_balance(&sender2, DENOM)?
+    );
+    assert_eq!(
+        Uint128::new(INITIAL_BALANCE),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn waitlist_promotion_runs_booking_checks() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let update_config = |self_gap_minutes: Option<u32>, allow_admin_booking: Option<bool>| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        })
+    };
+    let join = |app: &AppInterface<Mock>| -> anyhow::Result<()> {
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::JoinWaitlist {
+                start_time: local_timestamp(app, "2024-03-01", "10:00")?.into(),
+                end_time: local_timestamp(app, "2024-03-01", "11:00")?.into(),
+            }),
+            Some(&coins(60, DENOM)),
+        )?;
+        Ok(())
+    };
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    mock.set_balance(&admin, coins(INITIAL_BALANCE, DENOM))?;
+    app.set_sender(&admin);
+    app.execute(&update_config(Some(30), Some(true)), None)?;
+
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    app.set_sender(&sender1);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    app.set_sender(&sender2);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+        local_timestamp(&app, "2024-03-01", "12:00")?,
+    )?;
+    // The entry of sender2 ends right before their own meeting, within the self gap.
+    join(&app)?;
+    // The admin joins while they may book, which is then disallowed.
+    app.set_sender(&admin);
+    join(&app)?;
+    app.execute(&update_config(None, Some(false)), None)?;
+
+    app.set_sender(&sender1);
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let response = app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::CancelMeeting {
+            day_datetime: day_datetime.into(),
+            meeting_index: 0,
+        }),
+        None,
+    )?;
+    assert_eq!(
+        "0",
+        response.event_attr_value("wasm-abstract", "promoted_entries")?
+    );
+    let meetings = app.meetings(day_datetime)?.meetings;
+    assert_eq!(1, meetings.len());
+    assert_eq!(sender2, meetings[0].requester);
+    let waitlist: WaitlistResponse = app.query(&QueryMsg::from(AppQueryMsg::Waitlist {
+        start_after: None,
+        limit: None,
+    }))?;
+    assert_eq!(
+        vec![sender2, admin],
+        waitlist
+            .items
+            .into_iter()
+            .map(|(_, entry)| entry.requester)
+            .collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn resolutions_record_who_resolved_and_where_the_stake_went() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    for (start, end) in [
+        ("09:00", "10:00"),
+        ("10:00", "11:00"),
+        ("11:00", "12:00"),
+        ("12:00", "13:00"),
+    ] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+    set_local_time(&app, "2024-03-01", "14:00")?;
+
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    app.return_stake_for("2024-03-01", "09:00")?;
+    app.slash_full_for("2024-03-01", "10:00")?;
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "11:00")?;
+    app.slash_partial_stake(day_datetime.into(), meeting_index, 15)?;
+
+    // A meeting listed twice in a batch is only resolved once.
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "12:00")?;
+    let error: anyhow::Error = app
+        .return_stakes(vec![
+            (day_datetime.into(), meeting_index),
+            (day_datetime.into(), meeting_index),
+        ])
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::StakeAlreadyHandled {
+            day_datetime,
+            meeting_index,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    app.return_stakes(vec![(day_datetime.into(), meeting_index)])?;
+
+    let resolved: ResolvedMeetingsResponse =
+        app.query(&QueryMsg::from(AppQueryMsg::ResolvedMeetings {
+            day_datetime: day_datetime.into(),
+            start_after: None,
+            limit: None,
+        }))?;
+    let resolved_at = local_timestamp(&app, "2024-03-01", "14:00")?;
+    let resolution =
+        |action, amount_to_requester: u128, amount_to_admin: u128| MeetingStatus::Resolved {
+            action,
+            resolver: admin.clone(),
+            resolved_at,
+            amount_to_requester: Uint128::new(amount_to_requester),
+            amount_to_admin: Uint128::new(amount_to_admin),
+        };
+    assert_eq!(
+        vec![
+            resolution(ResolutionAction::Return, 60, 0),
+            resolution(ResolutionAction::FullSlash, 0, 60),
+            resolution(ResolutionAction::PartialSlash { minutes_late: 15 }, 45, 15),
+            resolution(ResolutionAction::Return, 60, 0),
+        ],
+        resolved
+            .items
+            .into_iter()
+            .map(|(_, meeting)| meeting.status)
+            .collect::<Vec<_>>()
+    );
+    assert!(app.meetings(day_datetime)?.meetings.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn slash_preview_matches_partial_slash() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "10:50")?,
+    )?;
+    set_local_time(&app, "2024-03-01", "12:00")?;
+
+    let preview = |app: &AppInterface<Mock>,
+                   amount_staked: u128,
+                   duration_minutes: u32,
+                   minutes_late: u32| {
+        app.query::<SlashPreviewResponse>(&QueryMsg::from(AppQueryMsg::SlashPreview {
+            amount_staked: Uint128::new(amount_staked),
+            duration_minutes,
+            minutes_late,
+        }))
+    };
+    let expected = preview(&app, 50, 50, 7)?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "10:00")?;
+    let response = app.slash_partial_stake(day_datetime.into(), meeting_index, 7)?;
+    assert_eq!(
+        expected.slashed.to_string(),
+        response.event_attr_value("wasm-abstract", "amount_slashed")?
+    );
+    assert_eq!(
+        expected.returned.to_string(),
+        response.event_attr_value("wasm-abstract", "amount_returned")?
+    );
+
+    // The slashed part is rounded down.
+    assert_eq!(
+        SlashPreviewResponse {
+            slashed: Uint128::new(22),
+            returned: Uint128::new(78),
+        },
+        preview(&app, 100, 45, 10)?
+    );
+    let error: anyhow::Error = preview(&app, 100, 45, 46).unwrap_err().into();
+    assert_eq!(
+        AppError::MinutesLateCannotExceedDurationOfMeeting {}.to_string(),
+        error.root_cause().to_string()
+    );
+    let error: anyhow::Error = preview(&app, 100, 0, 0).unwrap_err().into();
+    assert_eq!(
+        AppError::ZeroMeetingDuration {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn stakes_pegged_to_quote_currency() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let code_id = mock
+        .app
+        .borrow_mut()
+        .store_code(Box::new(ContractWrapper::new(
+            oracle_execute,
+            oracle_instantiate,
+            oracle_query,
+        )));
+    let oracle = mock
+        .instantiate(code_id, &Empty {}, Some("oracle"), None, &[])?
+        .instantiated_contract_address()?;
+    let update_config = |price_source: Option<&Addr>, pegged_price_per_minute: Option<Decimal>| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: price_source.map(Addr::to_string),
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        })
+    };
+    let request =
+        |app: &AppInterface<Mock>, start: &str, end: &str, amount: u128| -> anyhow::Result<()> {
+            app.execute(
+                &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+                    start_time: local_timestamp(app, "2024-03-01", start)?.into(),
+                    end_time: local_timestamp(app, "2024-03-01", end)?.into(),
+                    refund_splits: None,
+                    reminder_minutes: None,
+                    max_total_stake: None,
+                }),
+                Some(&coins(amount, DENOM)),
+            )?;
+            Ok(())
+        };
+
+    // 0.7 usd per minute, pegged without a price source to convert it.
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(&update_config(None, Some(Decimal::permille(700))), None)?;
+    app.set_sender(&Addr::unchecked("sender"));
+    let error = request(&app, "10:00", "11:00", 60).unwrap_err();
+    assert_eq!(
+        AppError::PriceSourceNotSet {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    // 42 usd at 2.5 usd per unit is 16.8 units, rounded up.
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(&update_config(Some(&oracle), None), None)?;
+    app.set_sender(&Addr::unchecked("sender"));
+    assert_eq!(
+        Coin::new(17, DENOM),
+        app.expected_stake(
+            local_timestamp(&app, "2024-03-01", "10:00")?,
+            local_timestamp(&app, "2024-03-01", "11:00")?,
+        )?
+    );
+    let error = request(&app, "10:00", "11:00", 60).unwrap_err();
+    assert_eq!(
+        AppError::InvalidStakeAmountSent {
+            expected_amount: Uint128::new(17),
+            amount_sent: Uint128::new(60),
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    request(&app, "10:00", "11:00", 17)?;
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    assert_eq!(
+        Uint128::new(17),
+        app.meetings(day_datetime)?.meetings[0].amount_staked
+    );
+
+    // A price source that cannot be queried fails the booking.
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &update_config(Some(&Addr::unchecked("sender2")), None),
+        None,
+    )?;
+    app.set_sender(&Addr::unchecked("sender"));
+    let error = request(&app, "13:00", "14:00", 17).unwrap_err();
+    assert!(error
+        .root_cause()
+        .to_string()
+        .starts_with(&format!("Price source has no usable price for {DENOM}")));
+
+    // A zero price goes back to the price per minute.
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(&update_config(None, Some(Decimal::zero())), None)?;
+    app.set_sender(&Addr::unchecked("sender"));
+    request(&app, "13:00", "14:00", 60)?;
+
+    Ok(())
+}
+
+#[test]
+fn self_test_reports_injected_violations() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "13:00")?,
+        local_timestamp(&app, "2024-03-01", "14:00")?,
+    )?;
+
+    let self_test = |app: &AppInterface<Mock>| {
+        app.query::<SelfTestResponse>(&QueryMsg::from(AppQueryMsg::SelfTest {
+            start_after: None,
+            limit: None,
+        }))
+    };
+    assert_eq!(
+        SelfTestResponse {
+            days_checked: 1,
+            violations: vec![],
+            limit: DEFAULT_PAGE_LIMIT,
+        },
+        self_test(&app)?
+    );
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let next_day = local_timestamp(&app, "2024-03-02", "00:00")?;
+    let mut snapshot = app.export_state()?;
+    let meetings = &mut snapshot.days[0].1;
+    meetings[0].free = true;
+    meetings[1].start_time = local_timestamp(&app, "2024-03-01", "10:30")?;
+    meetings[1].end_time = local_timestamp(&app, "2024-03-01", "11:30")?;
+    let mut early = meetings[1].clone();
+    early.start_time = local_timestamp(&app, "2024-03-01", "07:00")?;
+    early.end_time = local_timestamp(&app, "2024-03-01", "08:00")?;
+    meetings.push(early);
+    let stray = meetings[0].clone();
+    snapshot.config.overbooking_allowed = true;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.import_state(&snapshot, true)?;
+    // Imports refuse meetings filed under another day, so this one is written straight to
+    // storage.
+    write_day(&app, next_day, &[stray])?;
+
+    let violation = |day_datetime: i64, meeting_index: u32, reason: ViolationReason| Violation {
+        day_datetime,
+        meeting_index,
+        reason,
+    };
+    // Overlaps are fine while overbooking is allowed.
+    assert_eq!(
+        SelfTestResponse {
+            days_checked: 2,
+            violations: vec![
+                violation(day_datetime, 0, ViolationReason::StakeMismatch),
+                violation(day_datetime, 2, ViolationReason::OutsideCalendarHours),
+                violation(next_day, 0, ViolationReason::WrongDay),
+                violation(next_day, 0, ViolationReason::OutsideCalendarHours),
+                violation(next_day, 0, ViolationReason::StakeMismatch),
+            ],
+            limit: DEFAULT_PAGE_LIMIT,
+        },
+        self_test(&app)?
+    );
+
+    // Only the config is replaced, so the overlapping day stays as it is.
+    snapshot.config.overbooking_allowed = false;
+    snapshot.days.clear();
+    app.import_state(&snapshot, true)?;
+    let response = self_test(&app)?;
+    assert_eq!(
+        vec![
+            violation(day_datetime, 0, ViolationReason::StakeMismatch),
+            violation(
+                day_datetime,
+                0,
+                ViolationReason::Overlaps { other_index: 1 }
+            ),
+            violation(day_datetime, 2, ViolationReason::OutsideCalendarHours),
+        ],
+        response.violations[..3]
+    );
+
+    // The range is bounded by days.
+    let response = app.query::<SelfTestResponse>(&QueryMsg::from(AppQueryMsg::SelfTest {
+        start_after: Some(day_datetime),
+        limit: Some(1),
+    }))?;
+    assert_eq!(1, response.days_checked);
+    assert!(response
+        .violations
+        .iter()
+        .all(|violation| violation.day_datetime == next_day));
+
+    Ok(())
+}
+
+#[test]
+fn self_test_accepts_days_in_booking_order() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    for (start, end) in [("13:00", "14:00"), ("10:00", "11:00")] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+
+    // The later meeting keeps the index it was booked with.
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let meetings = app.meetings(day_datetime)?.meetings;
+    assert_eq!(
+        vec![
+            local_timestamp(&app, "2024-03-01", "13:00")?,
+            local_timestamp(&app, "2024-03-01", "10:00")?,
+        ],
+        meetings
+            .iter()
+            .map(|meeting| meeting.start_time)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        SelfTestResponse {
+            days_checked: 1,
+            violations: vec![],
+            limit: DEFAULT_PAGE_LIMIT,
+        },
+        app.query::<SelfTestResponse>(&QueryMsg::from(AppQueryMsg::SelfTest {
+            start_after: None,
+            limit: None,
+        }))?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn booking_and_resolution_set_response_data() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "10:50")?,
+    )?;
+    let response = app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "13:00")?,
+        local_timestamp(&app, "2024-03-01", "14:00")?,
+    )?;
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    assert_eq!(
+        RequestMeetingResponse {
+            day_datetime,
+            meeting_index: 1,
+            amount_staked: Uint128::new(60),
+            meeting: app.meetings(day_datetime)?.meetings[1].clone(),
+        },
+        from_binary(&response.data.unwrap())?
+    );
+
+    set_local_time(&app, "2024-03-01", "15:00")?;
+    let booked = app.meetings(day_datetime)?.meetings;
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    app.set_sender(&admin);
+    let response = app.slash_partial_stake(day_datetime.into(), 0, 10)?;
+    let data: ResolutionResponse = from_binary(response.data.as_ref().unwrap())?;
+    let resolved_at = local_timestamp(&app, "2024-03-01", "15:00")?;
+    assert_eq!(
+        ResolutionResponse {
+            day_datetime,
+            meeting_index: 0,
+            action: ResolutionAction::PartialSlash { minutes_late: 10 },
+            amount_to_requester: Uint128::new(40),
+            amount_to_admin: Uint128::new(10),
+            meeting: MeetingResponse {
+                status: MeetingStatus::Resolved {
+                    action: ResolutionAction::PartialSlash { minutes_late: 10 },
+                    resolver: admin.clone(),
+                    resolved_at,
+                    amount_to_requester: Uint128::new(40),
+                    amount_to_admin: Uint128::new(10),
+                },
+                ..booked[0].clone()
+            },
+        },
+        data
+    );
+    // The data matches the attributes.
+    assert_eq!(
+        data.amount_to_admin.to_string(),
+        response.event_attr_value("wasm-abstract", "amount_slashed")?
+    );
+
+    // The remaining meeting moved down to index 0.
+    let response = app.return_stake(day_datetime.into(), 0)?;
+    assert_eq!(
+        ResolutionResponse {
+            day_datetime,
+            meeting_index: 0,
+            action: ResolutionAction::Return,
+            amount_to_requester: Uint128::new(60),
+            amount_to_admin: Uint128::zero(),
+            meeting: MeetingResponse {
+                status: MeetingStatus::Resolved {
+                    action: ResolutionAction::Return,
+                    resolver: admin,
+                    resolved_at,
+                    amount_to_requester: Uint128::new(60),
+                    amount_to_admin: Uint128::zero(),
+                },
+                ..booked[1].clone()
+            },
+        },
+        from_binary(&response.data.unwrap())?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn partial_slash_follows_slash_curve() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    for start in ["10:00", "11:00"] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", start)? + 50 * 60,
+        )?;
+    }
+    set_local_time(&app, "2024-03-01", "12:00")?;
+
+    let set_curve = |app: &AppInterface<Mock>, slash_curve: SlashCurve| {
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+                price_per_minute: None,
+                denom: None,
+                self_gap_minutes: None,
+                accepted_denoms: None,
+                denom_decimals: None,
+                allowed_weekdays: None,
+                booking_fee: None,
+                return_claim_deadline: None,
+                attestor: None,
+                allow_partial_outside: None,
+                price_outside_hours: None,
+                price_source: None,
+                check_outstanding_balance: None,
+                time_granularity_seconds: None,
+                allow_admin_booking: None,
+                overbooking_allowed: None,
+                pegged_price_per_minute: None,
+                slash_curve: Some(slash_curve),
+                cancel_cutoff_secs: None,
+                name: None,
+                description: None,
+                contact_uri: None,
+                slash_distribution: None,
+                force: false,
+            }),
+            None,
+        )
+    };
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let error: anyhow::Error = set_curve(
+        &app,
+        SlashCurve::Threshold {
+            grace_minutes: 30,
+            full_after_minutes: 10,
+        },
+    )
+    .unwrap_err()
+    .into();
+    assert_eq!(
+        AppError::InvalidSlashCurve {
+            grace_minutes: 30,
+            full_after_minutes: 10,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // Ten minutes late to fifty slashes a fifth linearly but only a twenty-fifth quadratically.
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "10:00")?;
+    let response = app.slash_partial_stake(day_datetime.into(), meeting_index, 10)?;
+    assert_eq!(
+        "10",
+        response.event_attr_value("wasm-abstract", "amount_slashed")?
+    );
+
+    set_curve(&app, SlashCurve::Quadratic)?;
+    assert_eq!(SlashCurve::Quadratic, app.config()?.slash_curve);
+    let preview =
+        app.query::<SlashPreviewResponse>(&QueryMsg::from(AppQueryMsg::SlashPreview {
+            amount_staked: Uint128::new(50),
+            duration_minutes: 50,
+            minutes_late: 10,
+        }))?;
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "11:00")?;
+    let response = app.slash_partial_stake(day_datetime.into(), meeting_index, 10)?;
+    assert_eq!(
+        "2",
+        response.event_attr_value("wasm-abstract", "amount_slashed")?
+    );
+    assert_eq!(
+        SlashPreviewResponse {
+            slashed: Uint128::new(2),
+            returned: Uint128::new(48),
+        },
+        preview
+    );
+
+    Ok(())
+}
+
+#[test]
+fn handlers_emit_typed_events() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let attributes = |pairs: Vec<(&str, String)>| -> Vec<(String, String)> {
+        pairs
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect()
+    };
+
+    app.set_sender(&Addr::unchecked("sender"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    let start_time = local_timestamp(&app, "2024-03-01", "13:00")?;
+    let end_time = local_timestamp(&app, "2024-03-01", "13:30")?;
+    let response = app.request_meeting_with_auto_funds(start_time, end_time)?;
+    assert_eq!(
+        vec![attributes(vec![
+            (KEY_SCHEMA_VERSION, SCHEMA_VERSION.to_string()),
+            (KEY_DAY_DATETIME, day_datetime.to_string()),
+            (KEY_MEETING_INDEX, "1".to_string()),
+            (KEY_START_TIME, start_time.to_string()),
+            (KEY_END_TIME, end_time.to_string()),
+            (KEY_REQUESTER, "sender".to_string()),
+            (KEY_AMOUNT_STAKED, "30".to_string()),
+            (KEY_DENOM, DENOM.to_string()),
+        ])],
+        typed_events(&response.events, MEETING_BOOKED)
+    );
+
+    set_local_time(&app, "2024-03-01", "15:00")?;
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let resolved = |meeting_index: &str, action: &str, to_requester: &str, to_admin: &str| {
+        attributes(vec![
+            (KEY_SCHEMA_VERSION, SCHEMA_VERSION.to_string()),
+            (KEY_DAY_DATETIME, day_datetime.to_string()),
+            (KEY_MEETING_INDEX, meeting_index.to_string()),
+            (KEY_REQUESTER, "sender".to_string()),
+            (KEY_ACTION, action.to_string()),
+            (KEY_RESOLVER, "contract2".to_string()),
+            (KEY_AMOUNT_TO_REQUESTER, to_requester.to_string()),
+            (KEY_AMOUNT_TO_ADMIN, to_admin.to_string()),
+            (KEY_DENOM, DENOM.to_string()),
+        ])
+    };
+    let response = app.slash_partial_stake(day_datetime.into(), 0, 15)?;
+    let mut partial_slash = resolved("0", "partial_slash", "45", "15");
+    partial_slash.insert(5, (KEY_MINUTES_LATE.to_string(), "15".to_string()));
+    assert_eq!(
+        vec![partial_slash],
+        typed_events(&response.events, STAKE_RESOLVED)
+    );
+    let response = app.return_stakes(vec![(day_datetime.into(), 0)])?;
+    assert_eq!(
+        vec![resolved("0", "return", "30", "0")],
+        typed_events(&response.events, STAKE_RESOLVED)
+    );
+
+    let response = app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: Some(Uint128::new(2)),
+            denom: None,
+            self_gap_minutes: Some(15),
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+    assert_eq!(
+        vec![attributes(vec![
+            (KEY_SCHEMA_VERSION, SCHEMA_VERSION.to_string()),
+            (KEY_UPDATED_BY, "contract2".to_string()),
+            (KEY_CHANGED, "price_per_minute,self_gap_minutes".to_string()),
+            (KEY_DENOM, DENOM.to_string()),
+            (KEY_PRICE_PER_MINUTE, "2".to_string()),
+            (KEY_UTC_OFFSET, "0".to_string()),
+            (KEY_START_TIME, "09:00".to_string()),
+            (KEY_END_TIME, "17:00".to_string()),
+        ])],
+        typed_events(&response.events, CONFIG_UPDATED)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cancellation_deadline_is_fixed_at_booking() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    let early_start = local_timestamp(&app, "2024-03-01", "10:00")?;
+    app.request_meeting_with_auto_funds(early_start, early_start + 3600)?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: Some(3600),
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+    app.set_sender(&sender);
+    let late_start = local_timestamp(&app, "2024-03-01", "13:00")?;
+    app.request_meeting_with_auto_funds(late_start, late_start + 3600)?;
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    assert_eq!(
+        vec![Some(early_start), Some(late_start - 3600)],
+        app.meetings(day_datetime)?
+            .meetings
+            .iter()
+            .map(|meeting| meeting.cancel_deadline)
+            .collect::<Vec<_>>()
+    );
+
+    let cancel = |app: &AppInterface<Mock>, meeting_index: u32| {
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::CancelMeeting {
+                day_datetime: day_datetime.into(),
+                meeting_index,
+            }),
+            None,
+        )
+    };
+    // The earlier meeting keeps the deadline it was booked with despite the new cutoff.
+    set_local_time(&app, "2024-03-01", "09:30")?;
+    cancel(&app, 0)?;
+
+    set_local_time(&app, "2024-03-01", "12:30")?;
+    let error: anyhow::Error = cancel(&app, 0).unwrap_err().into();
+    assert_eq!(
+        AppError::PastCancellationDeadline {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn resolution_event_breaks_down_payout() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    for start in ["10:00", "11:00", "12:00"] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", start)? + 30 * 60,
+        )?;
+    }
+    set_local_time(&app, "2024-03-01", "15:00")?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let breakdown = |events: &[Event]| -> Vec<String> {
+        let events = typed_events(events, STAKE_RESOLVED);
+        assert_eq!(1, events.len());
+        [
+            KEY_ACTION,
+            KEY_MINUTES_LATE,
+            KEY_AMOUNT_TO_REQUESTER,
+            KEY_AMOUNT_TO_ADMIN,
+        ]
+        .iter()
+        .map(|key| {
+            events[0]
+                .iter()
+                .find(|(event_key, _)| event_key == key)
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default()
+        })
+        .collect()
+    };
+
+    // Every branch emits the event, also with a zero leg, and is tagged with the name of its
+    // message.
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "10:00")?;
+    let response = app.return_stake(day_datetime.into(), meeting_index)?;
+    assert_eq!(vec!["return", "", "30", "0"], breakdown(&response.events));
+    assert_eq!(
+        "return_stake",
+        response.event_attr_value("wasm-abstract", "action")?
+    );
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "11:00")?;
+    let response = app.slash_full_stake(day_datetime.into(), meeting_index)?;
+    assert_eq!(
+        vec!["full_slash", "", "0", "30"],
+        breakdown(&response.events)
+    );
+    assert_eq!(
+        "slash_full_stake",
+        response.event_attr_value("wasm-abstract", "action")?
+    );
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "12:00")?;
+    let response = app.slash_partial_stake(day_datetime.into(), meeting_index, 0)?;
+    assert_eq!(
+        vec!["partial_slash", "0", "30", "0"],
+        breakdown(&response.events)
+    );
+    assert_eq!(
+        "slash_partial_stake",
+        response.event_attr_value("wasm-abstract", "action")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn times_are_also_emitted_in_local_time() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup_with(AppInstantiateMsg {
+        price_per_minute: Uint128::from(1u128),
+        denom: AssetEntry::from(DENOM),
+        utc_offset: -5 * 3600,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 0,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![],
+        name: None,
+        description: None,
+        contact_uri: None,
+    })?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    let start_time = local_timestamp(&app, "2024-03-01", "10:00")?;
+    let response = app.request_meeting_with_auto_funds(start_time, start_time + 3600)?;
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    for (key, value) in [
+        ("meeting_start_time", start_time.to_string()),
+        (
+            "meeting_start_local",
+            "2024-03-01T10:00:00-05:00".to_string(),
+        ),
+        ("meeting_end_local", "2024-03-01T11:00:00-05:00".to_string()),
+        ("day_datetime", day_datetime.to_string()),
+    ] {
+        assert_eq!(value, response.event_attr_value("wasm-abstract", key)?);
+    }
+
+    set_local_time(&app, "2024-03-01", "12:00")?;
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let response = app.slash_full_stake(day_datetime.into(), 0)?;
+    for (key, value) in [
+        (
+            "meeting_start_local",
+            "2024-03-01T10:00:00-05:00".to_string(),
+        ),
+        ("meeting_end_local", "2024-03-01T11:00:00-05:00".to_string()),
+        ("day_datetime", day_datetime.to_string()),
+    ] {
+        assert_eq!(value, response.event_attr_value("wasm-abstract", key)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn return_stake_by_key_survives_index_shifts() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender1 = Addr::unchecked("sender1");
+    let sender2 = Addr::unchecked("sender2");
+    app.set_sender(&sender1);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    app.set_sender(&sender2);
+    let start_time = local_timestamp(&app, "2024-03-01", "13:00")?;
+    app.request_meeting_with_auto_funds(start_time, start_time + 3600)?;
+
+    set_local_time(&app, "2024-03-01", "15:00")?;
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    // Removes the first meeting, moving the second one to index 0.
+    app.slash_full_stake(day_datetime.into(), 0)?;
+
+    let error: anyhow::Error = app
+        .return_stake_by_key(day_datetime.into(), sender1.to_string(), start_time.into())
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::MeetingNotFoundByKey {
+            day_datetime,
+            requester: sender1.to_string(),
+            start_time,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    app.return_stake_by_key(day_datetime.into(), sender2.to_string(), start_time.into())?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender2, DENOM)?
+    );
+    assert!(app.meetings(day_datetime)?.meetings.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn timestamp_and_seconds_messages_book_the_same_meeting() -> anyhow::Result<()> {
+    let (_account, _abstr, mut seconds_app, _mock) = setup()?;
+    let (_account, _abstr, mut timestamp_app, timestamp_mock) = setup()?;
+    let sender = Addr::unchecked("sender");
+    for app in [&mut seconds_app, &mut timestamp_app] {
+        set_local_time(app, "2024-03-01", "08:00")?;
+        app.set_sender(&sender);
+    }
+
+    let start_time = local_timestamp(&seconds_app, "2024-03-01", "10:00")?;
+    let end_time = local_timestamp(&seconds_app, "2024-03-01", "11:00")?;
+    seconds_app.request_meeting_with_auto_funds(start_time, end_time)?;
+    timestamp_app.request_meeting_at_with_auto_funds(
+        Timestamp::from_seconds(start_time as u64),
+        Timestamp::from_seconds(end_time as u64),
+    )?;
+
+    let day_datetime = local_timestamp(&seconds_app, "2024-03-01", "00:00")?;
+    let meetings = seconds_app.meetings(day_datetime)?.meetings;
+    assert_eq!(1, meetings.len());
+    assert_eq!(meetings, timestamp_app.meetings(day_datetime)?.meetings);
+
+    let later_start = Timestamp::from_seconds(start_time as u64 + 3 * 3600).plus_nanos(1);
+    let error: anyhow::Error = timestamp_app
+        .request_meeting_at_with_auto_funds(
+            later_start,
+            Timestamp::from_seconds(end_time as u64 + 3 * 3600),
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::TimestampNotWholeSeconds {
+            timestamp: later_start,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    set_local_time(&timestamp_app, "2024-03-01", "12:00")?;
+    // Not sure how to get this dynamically...
+    timestamp_app.set_sender(&Addr::unchecked("contract2"));
+    timestamp_app.return_stake_at(Timestamp::from_seconds(day_datetime as u64), 0)?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        timestamp_mock.query_balance(&sender, DENOM)?
+    );
+    assert!(timestamp_app.meetings(day_datetime)?.meetings.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn accepted_denoms_must_resolve_in_the_name_service() -> anyhow::Result<()> {
+    const OTHER_DENOM: &str = "juno>other";
+    const MISSPELLED_DENOM: &str = "juno>ohter";
+
+    let (_account, abstr, mut app, _mock) = setup()?;
+    abstr.ans_host.execute(
+        &abstract_core::ans_host::ExecuteMsg::UpdateAssetAddresses {
+            to_add: vec![(
+                OTHER_DENOM.to_owned(),
+                AssetInfo::native(OTHER_DENOM).into(),
+            )],
+            to_remove: vec![],
+        },
+        None,
+    )?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+                price_per_minute: None,
+                denom: None,
+                self_gap_minutes: None,
+                accepted_denoms: Some(vec![
+                    (AssetEntry::from(OTHER_DENOM), Uint128::from(2u128)),
+                    (AssetEntry::from(MISSPELLED_DENOM), Uint128::from(2u128)),
+                ]),
+                denom_decimals: None,
+                allowed_weekdays: None,
+                booking_fee: None,
+                return_claim_deadline: None,
+                attestor: None,
+                allow_partial_outside: None,
+                price_outside_hours: None,
+                price_source: None,
+                check_outstanding_balance: None,
+                time_granularity_seconds: None,
+                allow_admin_booking: None,
+                overbooking_allowed: None,
+                pegged_price_per_minute: None,
+                slash_curve: None,
+                cancel_cutoff_secs: None,
+                name: None,
+                description: None,
+                contact_uri: None,
+                slash_distribution: None,
+                force: false,
+            }),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::UnresolvableAcceptedDenom {
+            entry: MISSPELLED_DENOM.to_string(),
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    assert!(app.config()?.accepted_denoms.is_empty());
+
+    let error = setup_with(AppInstantiateMsg {
+        price_per_minute: Uint128::from(1u128),
+        denom: AssetEntry::from(DENOM),
+        utc_offset: 0,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 0,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![(AssetEntry::from(MISSPELLED_DENOM), Uint128::from(2u128))],
+        name: None,
+        description: None,
+        contact_uri: None,
+    })
+    .unwrap_err();
+    assert!(error.root_cause().to_string().contains(
+        &AppError::UnresolvableAcceptedDenom {
+            entry: MISSPELLED_DENOM.to_string(),
+        }
+        .to_string()
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn iso_and_unix_messages_book_the_same_meeting() -> anyhow::Result<()> {
+    let instantiate_msg = AppInstantiateMsg {
+        price_per_minute: Uint128::from(1u128),
+        denom: AssetEntry::from(DENOM),
+        utc_offset: 2 * 3600,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 0,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![],
+        name: None,
+        description: None,
+        contact_uri: None,
+    };
+    let (_account, _abstr, mut unix_app, _mock) = setup_with(instantiate_msg.clone())?;
+    let (_account, _abstr, mut iso_app, iso_mock) = setup_with(instantiate_msg)?;
+    let sender = Addr::unchecked("sender");
+    for app in [&mut unix_app, &mut iso_app] {
+        set_local_time(app, "2024-06-03", "08:00")?;
+        app.set_sender(&sender);
+    }
+
+    let start_time = local_timestamp(&unix_app, "2024-06-03", "14:00")?;
+    let end_time = local_timestamp(&unix_app, "2024-06-03", "15:00")?;
+    unix_app.request_meeting_with_auto_funds(start_time, end_time)?;
+    let request_iso = |app: &AppInterface<Mock>, start: &str, end: &str| {
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeetingIso {
+                start_time: start.to_string(),
+                end_time: end.to_string(),
+                refund_splits: None,
+                reminder_minutes: None,
+                max_total_stake: None,
+            }),
+            Some(&[app.expected_stake(start_time, end_time)?]),
+        )
+        .map_err(anyhow::Error::from)
+    };
+    request_iso(
+        &iso_app,
+        "2024-06-03T14:00:00+02:00",
+        "2024-06-03T15:00:00+02:00",
+    )?;
+
+    let day_datetime = local_timestamp(&unix_app, "2024-06-03", "00:00")?;
+    let meetings = unix_app.meetings(day_datetime)?.meetings;
+    assert_eq!(1, meetings.len());
+    assert_eq!(meetings, iso_app.meetings(day_datetime)?.meetings);
+
+    let error = request_iso(&iso_app, "2024-06-03T16:00", "2024-06-03T17:00:00+02:00").unwrap_err();
+    assert!(error
+        .root_cause()
+        .to_string()
+        .contains("\"2024-06-03T16:00\""));
+    let error = request_iso(&iso_app, "2024-06-03T16:00:00Z", "2024-06-03T17:00:00Z").unwrap_err();
+    assert_eq!(
+        AppError::IsoTimeOffsetMismatch {
+            input: "2024-06-03T16:00:00Z".to_string(),
+            utc_offset: 2 * 3600,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    set_local_time(&iso_app, "2024-06-03", "16:00")?;
+    // Not sure how to get this dynamically...
+    iso_app.set_sender(&Addr::unchecked("contract2"));
+    iso_app.return_stake_iso("2024-06-03T00:00:00+02:00".to_string(), 0)?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        iso_mock.query_balance(&sender, DENOM)?
+    );
+    assert!(iso_app.meetings(day_datetime)?.meetings.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn busy_blocks_merge_adjacent_meetings() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+    app.set_sender(&Addr::unchecked("sender"));
+    for (start, end) in [
+        ("10:00", "11:00"),
+        ("14:00", "15:00"),
+        ("11:00", "12:00"),
+        ("12:00", "13:00"),
+    ] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+
+    let response: BusyBlocksResponse = app.query(&QueryMsg::from(AppQueryMsg::BusyBlocks {
+        day_datetime: local_timestamp(&app, "2024-03-01", "00:00")?,
+    }))?;
+    assert_eq!(
+        vec![
+            (
+                local_timestamp(&app, "2024-03-01", "10:00")?,
+                local_timestamp(&app, "2024-03-01", "13:00")?,
+            ),
+            (
+                local_timestamp(&app, "2024-03-01", "14:00")?,
+                local_timestamp(&app, "2024-03-01", "15:00")?,
+            ),
+        ],
+        response.blocks
+    );
+
+    Ok(())
+}
+
+#[test]
+fn booking_fails_when_fees_push_the_stake_over_the_cap() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: Some(Uint128::from(5u128)),
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    let start_time = local_timestamp(&app, "2024-03-01", "10:00")?;
+    let end_time = local_timestamp(&app, "2024-03-01", "11:00")?;
+    let request_meeting = |app: &AppInterface<Mock>, max_total_stake: u128| {
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+                start_time: start_time.into(),
+                end_time: end_time.into(),
+                refund_splits: None,
+                reminder_minutes: None,
+                max_total_stake: Some(Uint128::from(max_total_stake)),
+            }),
+            Some(&[Coin::new(65, DENOM)]),
+        )
+    };
+
+    // The 60 minute stake fits under the cap, the booking fee does not.
+    let error: anyhow::Error = request_meeting(&app, 64).unwrap_err().into();
+    assert_eq!(
+        AppError::StakeAboveMax {
+            expected_amount: Uint128::from(65u128),
+            max_total_stake: Uint128::from(64u128),
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    request_meeting(&app, 65)?;
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 65),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn diagnose_reports_the_config_behind_a_failing_booking() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: Some(30),
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+
+    let diagnose = |sender: &str| -> anyhow::Result<DiagnoseResponse> {
+        Ok(app.query(&QueryMsg::from(AppQueryMsg::Diagnose {
+            start_time: local_timestamp(&app, "2024-03-01", "11:15")?.into(),
+            end_time: local_timestamp(&app, "2024-03-01", "11:45")?.into(),
+            sender: sender.to_string(),
+        }))?)
+    };
+    let error = AppError::SelfGapViolated {
+        self_gap_minutes: 30,
+    };
+    assert_eq!(
+        DiagnoseResponse {
+            failure: Some(BookingFailure {
+                code: error.code().to_string(),
+                message: error.to_string(),
+                config: vec![("self_gap_minutes".to_string(), "30".to_string())],
+            }),
+        },
+        diagnose("sender")?
+    );
+
+    // Other requesters are not affected by the gap.
+    assert_eq!(DiagnoseResponse { failure: None }, diagnose("sender2")?);
+
+    // Diagnosing does not book anything.
+    app.set_sender(&Addr::unchecked("sender2"));
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "11:15")?,
+        local_timestamp(&app, "2024-03-01", "11:45")?,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn simulating_a_booking_matches_requesting_it() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+    app.set_sender(&Addr::unchecked("sender"));
+
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    // Booked in order, so that the later ones run into the earlier ones.
+    let fixtures = [
+        ("10:00", "11:00"),
+        ("11:00", "11:30"),
+        ("10:30", "11:30"),
+        ("07:00", "07:30"),
+        ("17:00", "18:00"),
+        ("12:00", "12:00"),
+    ];
+    for (start, end) in fixtures {
+        let start_time = local_timestamp(&app, "2024-03-01", start)?;
+        let end_time = local_timestamp(&app, "2024-03-01", end)?;
+        let simulation: SimulateRequestMeetingResponse =
+            app.query(&QueryMsg::from(AppQueryMsg::SimulateRequestMeeting {
+                start_time: start_time.into(),
+                end_time: end_time.into(),
+                requester: Some("sender".to_string()),
+            }))?;
+        let funds = match &simulation {
+            SimulateRequestMeetingResponse::Accepted { price, .. } => price.clone(),
+            SimulateRequestMeetingResponse::Rejected { .. } => Coin::new(60, DENOM),
+        };
+        let result = app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+                start_time: start_time.into(),
+                end_time: end_time.into(),
+                refund_splits: None,
+                reminder_minutes: None,
+                max_total_stake: None,
+            }),
+            Some(std::slice::from_ref(&funds)),
+        );
+        match simulation {
+            SimulateRequestMeetingResponse::Accepted {
+                day_datetime: simulated_day,
+                meeting_index,
+                price,
+            } => {
+                let response: RequestMeetingResponse = from_binary(&result?.data.unwrap())?;
+                assert_eq!(day_datetime, simulated_day);
+                assert_eq!(
+                    (response.day_datetime, response.meeting_index),
+                    (simulated_day, meeting_index)
+                );
+                assert_eq!(response.amount_staked, price.amount);
+            }
+            SimulateRequestMeetingResponse::Rejected { failure } => {
+                let error: anyhow::Error = result.unwrap_err().into();
+                assert_eq!(failure.message, error.root_cause().to_string());
+                assert!(failure.message.starts_with(&failure.code));
+            }
+        }
+    }
+    // The first two fixtures were booked and the others rejected.
+    assert_eq!(2, app.meetings(day_datetime)?.meetings.len());
+
+    Ok(())
+}
+
+#[test]
+fn meetings_for_days_lists_each_requested_day_in_order() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+
+    for (day, start, end) in [
+        ("2024-03-01", "10:00", "11:00"),
+        ("2024-03-05", "09:00", "09:30"),
+        ("2024-03-05", "14:00", "15:00"),
+    ] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, day, start)?,
+            local_timestamp(&app, day, end)?,
+        )?;
+    }
+
+    let day = |date: &str| local_timestamp(&app, date, "00:00");
+    let meetings_for_days = |days: Vec<i64>| -> anyhow::Result<MeetingsForDaysResponse> {
+        Ok(app.query(&QueryMsg::from(AppQueryMsg::MeetingsForDays { days }))?)
+    };
+    // The days are listed in the order requested, including the one without meetings.
+    let response = meetings_for_days(vec![
+        day("2024-03-05")?,
+        day("2024-03-03")?,
+        day("2024-03-01")?,
+    ])?;
+    let start_times: Vec<(i64, Vec<i64>)> = response
+        .days
+        .into_iter()
+        .map(|(day, meetings)| (day, meetings.iter().map(|m| m.start_time).collect()))
+        .collect();
+    assert_eq!(
+        vec![
+            (
+                day("2024-03-05")?,
+                vec![
+                    local_timestamp(&app, "2024-03-05", "09:00")?,
+                    local_timestamp(&app, "2024-03-05", "14:00")?,
+                ],
+            ),
+            (day("2024-03-03")?, vec![]),
+            (
+                day("2024-03-01")?,
+                vec![local_timestamp(&app, "2024-03-01", "10:00")?],
+            ),
+        ],
+        start_times
+    );
+
+    let too_many = vec![day("2024-03-01")?; MAX_LISTED_DAYS as usize + 1];
+    let error: anyhow::Error = meetings_for_days(too_many).unwrap_err();
+    assert_eq!(
+        AppError::RangeTooLarge {
+            max_days: MAX_LISTED_DAYS
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn availability_heatmap_counts_free_minutes_per_day() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    // 2024-03-03 is a Sunday.
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: Some(vec![Weekday::Fri, Weekday::Sat]),
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::BlockSlot {
+            start_time: local_timestamp(&app, "2024-03-02", "09:00")?.into(),
+            end_time: local_timestamp(&app, "2024-03-02", "13:00")?.into(),
+        }),
+        None,
+    )?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    for (start, end) in [("10:00", "11:00"), ("14:00", "15:30")] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", end)?,
+        )?;
+    }
+
+    let heatmap = |start_day: &str, end_day: &str| -> anyhow::Result<AvailabilityHeatmapResponse> {
+        Ok(app.query(&QueryMsg::from(AppQueryMsg::AvailabilityHeatmap {
+            start_day: local_timestamp(&app, start_day, "00:00")?.into(),
+            end_day: local_timestamp(&app, end_day, "00:00")?.into(),
+        }))?)
+    };
+    // The calendar is open for 480 minutes a day.
+    assert_eq!(
+        AvailabilityHeatmapResponse {
+            days: vec![
+                (local_timestamp(&app, "2024-03-01", "00:00")?, 330),
+                (local_timestamp(&app, "2024-03-02", "00:00")?, 240),
+                (local_timestamp(&app, "2024-03-03", "00:00")?, 0),
+            ],
+        },
+        heatmap("2024-03-01", "2024-03-03")?
+    );
+
+    let error: anyhow::Error = heatmap("2024-03-01", "2024-05-02").unwrap_err();
+    assert_eq!(
+        AppError::RangeTooLarge {
+            max_days: MAX_HEATMAP_DAYS
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn transfer_all_meetings_moves_upcoming_meetings() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    let other = Addr::unchecked("other");
+    let new_account = Addr::unchecked("new_account");
+    let book =
+        |app: &AppInterface<Mock>, date: &str, start: &str, end: &str| -> anyhow::Result<()> {
+            app.request_meeting_with_auto_funds(
+                local_timestamp(app, date, start)?,
+                local_timestamp(app, date, end)?,
+            )?;
+            Ok(())
+        };
+    app.set_sender(&sender);
+    book(&app, "2024-03-01", "09:00", "10:00")?;
+    book(&app, "2024-03-01", "11:00", "12:00")?;
+    book(&app, "2024-03-02", "10:00", "11:00")?;
+    book(&app, "2024-03-04", "10:00", "11:00")?;
+    app.set_sender(&other);
+    book(&app, "2024-03-01", "13:00", "14:00")?;
+
+    // The first meeting has started, so it stays with the sender.
+    set_local_time(&app, "2024-03-01", "09:30")?;
+    app.set_sender(&sender);
+    let response = app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::TransferAllMeetings {
+            to: new_account.to_string(),
+            start_after: None,
+        }),
+        None,
+    )?;
+    assert_eq!(
+        "3",
+        response.event_attr_value("wasm-abstract", "meetings_transferred")?
+    );
+    assert_eq!(
+        "false",
+        response.event_attr_value("wasm-abstract", "has_more")?
+    );
+
+    let requesters = |date: &str| -> anyhow::Result<Vec<Addr>> {
+        Ok(app
+            .meetings(local_timestamp(&app, date, "00:00")?)?
+            .meetings
+            .into_iter()
+            .map(|meeting| meeting.requester)
+            .collect())
+    };
+    assert_eq!(
+        vec![sender.clone(), new_account.clone(), other],
+        requesters("2024-03-01")?
+    );
+    assert_eq!(vec![new_account.clone()], requesters("2024-03-02")?);
+    assert_eq!(vec![new_account], requesters("2024-03-04")?);
+
+    Ok(())
+}
+
+#[test]
+fn transfer_all_meetings_resumes_after_the_next_key() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    let new_account = Addr::unchecked("new_account");
+    app.set_sender(&sender);
+    // Thirty-two quarter-hour meetings fill a day, so the last of fifty-one is on the 3rd.
+    for slot in 0..51 {
+        let date = format!("2024-03-0{}", 2 + slot / 32);
+        let start = local_timestamp(&app, &date, "09:00")? + i64::from(slot % 32) * 900;
+        app.request_meeting_with_auto_funds(start, start + 900)?;
+    }
+    let transfer = |start_after: Option<i64>| {
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::TransferAllMeetings {
+                to: new_account.to_string(),
+                start_after: start_after.map(Int64::from),
+            }),
+            None,
+        )
+    };
+
+    // The batch fills up on the 3rd, so the next call starts after the 2nd.
+    let response = transfer(None)?;
+    assert_eq!(
+        "50",
+        response.event_attr_value("wasm-abstract", "meetings_transferred")?
+    );
+    assert_eq!(
+        "true",
+        response.event_attr_value("wasm-abstract", "has_more")?
+    );
+    let next_key = response.event_attr_value("wasm-abstract", "next_key")?;
+    assert_eq!(
+        local_timestamp(&app, "2024-03-02", "00:00")?.to_string(),
+        next_key
+    );
+
+    let response = transfer(Some(next_key.parse()?))?;
+    assert_eq!(
+        "1",
+        response.event_attr_value("wasm-abstract", "meetings_transferred")?
+    );
+    assert_eq!(
+        "false",
+        response.event_attr_value("wasm-abstract", "has_more")?
+    );
+    assert!(response
+        .event_attr_value("wasm-abstract", "next_key")
+        .is_err());
+    assert!(app
+        .meetings(local_timestamp(&app, "2024-03-03", "00:00")?)?
+        .meetings
+        .iter()
+        .all(|meeting| meeting.requester == new_account));
+
+    Ok(())
+}
+
+#[test]
+fn transferred_meetings_drop_refund_splits_and_follow_the_admin() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::RequestMeeting {
+            start_time: local_timestamp(&app, "2024-03-01", "10:00")?.into(),
+            end_time: local_timestamp(&app, "2024-03-01", "11:00")?.into(),
+            refund_splits: Some(vec![("alice".to_string(), 10_000)]),
+            reminder_minutes: None,
+            max_total_stake: None,
+        }),
+        Some(&[Coin::new(60, DENOM)]),
+    )?;
+
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    let transfer = |app: &AppInterface<Mock>, to: &Addr| {
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::TransferAllMeetings {
+                to: to.to_string(),
+                start_after: None,
+            }),
+            None,
+        )
+    };
+    // The admin cannot be handed meetings it could not book.
+    let error: anyhow::Error = transfer(&app, &admin).unwrap_err().into();
+    assert_eq!(
+        AppError::AdminBookingNotAllowed {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    app.set_sender(&admin);
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: Some(true),
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name: None,
+            description: None,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        }),
+        None,
+    )?;
+    app.set_sender(&sender);
+    transfer(&app, &admin)?;
+
+    let meeting = app
+        .meetings(local_timestamp(&app, "2024-03-01", "00:00")?)?
+        .meetings
+        .remove(0);
+    assert_eq!(admin, meeting.requester);
+    assert_eq!(None, meeting.refund_splits);
+    assert!(meeting.self_booked);
+
+    Ok(())
+}
+
+#[test]
+fn profile_is_set_at_install_and_updated() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup_with(AppInstantiateMsg {
+        price_per_minute: Uint128::from(1u128),
+        denom: AssetEntry::from(DENOM),
+        utc_offset: 0,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 0,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![],
+        name: Some("Office hours".to_string()),
+        description: None,
+        contact_uri: Some("mailto:owner@example.com".to_string()),
+    })?;
+    let profile: ProfileResponse = app.query(&QueryMsg::from(AppQueryMsg::Profile {}))?;
+    assert_eq!(
+        ProfileResponse {
+            name: Some("Office hours".to_string()),
+            description: None,
+            contact_uri: Some("mailto:owner@example.com".to_string()),
+        },
+        profile
+    );
+
+    let update_profile =
+        |name: Option<String>, description: Option<String>| AppExecuteMsg::UpdateConfig {
+            price_per_minute: None,
+            denom: None,
+            self_gap_minutes: None,
+            accepted_denoms: None,
+            denom_decimals: None,
+            allowed_weekdays: None,
+            booking_fee: None,
+            return_claim_deadline: None,
+            attestor: None,
+            allow_partial_outside: None,
+            price_outside_hours: None,
+            price_source: None,
+            check_outstanding_balance: None,
+            time_granularity_seconds: None,
+            allow_admin_booking: None,
+            overbooking_allowed: None,
+            pegged_price_per_minute: None,
+            slash_curve: None,
+            cancel_cutoff_secs: None,
+            name,
+            description,
+            contact_uri: None,
+            slash_distribution: None,
+            force: false,
+        };
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(update_profile(
+                Some("x".repeat(MAX_NAME_LENGTH + 1)),
+                None,
+            )),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::ProfileFieldTooLong {
+            field: "name".to_string(),
+            length: MAX_NAME_LENGTH + 1,
+            max: MAX_NAME_LENGTH,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+    let error: anyhow::Error = app
+        .execute(
+            &abstract_core::base::ExecuteMsg::Module(update_profile(
+                None,
+                Some("Book\u{0007}me".to_string()),
+            )),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::ProfileFieldHasControlCharacters {
+            field: "description".to_string(),
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    // An empty name clears it, fields left out are kept.
+    app.execute(
+        &abstract_core::base::ExecuteMsg::Module(update_profile(
+            Some(String::new()),
+            Some("Weekly syncs".to_string()),
+        )),
+        None,
+    )?;
+    let config: ConfigResponse = app.query(&QueryMsg::from(AppQueryMsg::Config {}))?;
+    assert_eq!(
+        (
+            None,
+            Some("Weekly syncs".to_string()),
+            Some("mailto:owner@example.com".to_string())
+        ),
+        (config.name, config.description, config.contact_uri)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn slashed_stake_follows_slash_distribution() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    app.set_sender(&Addr::unchecked("sender"));
+    for start in ["10:00", "11:00"] {
+        app.request_meeting_with_auto_funds(
+            local_timestamp(&app, "2024-03-01", start)?,
+            local_timestamp(&app, "2024-03-01", start)? + 50 * 60,
+        )?;
+    }
+    set_local_time(&app, "2024-03-01", "12:00")?;
+
+    // Not sure how to get this dynamically...
+    let admin = Addr::unchecked("contract2");
+    let treasury = Addr::unchecked("treasury");
+    let burn = Addr::unchecked("burn");
+    let set_distribution = |app: &AppInterface<Mock>, slash_distribution: Vec<(String, u16)>| {
+        app.execute(
+            &abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::UpdateConfig {
+                price_per_minute: None,
+                denom: None,
+                self_gap_minutes: None,
+                accepted_denoms: None,
+                denom_decimals: None,
+                allowed_weekdays: None,
+                booking_fee: None,
+                return_claim_deadline: None,
+                attestor: None,
+                allow_partial_outside: None,
+                price_outside_hours: None,
+                price_source: None,
+                check_outstanding_balance: None,
+                time_granularity_seconds: None,
+                allow_admin_booking: None,
+                overbooking_allowed: None,
+                pegged_price_per_minute: None,
+                slash_curve: None,
+                cancel_cutoff_secs: None,
+                name: None,
+                description: None,
+                contact_uri: None,
+                slash_distribution: Some(slash_distribution),
+                force: false,
+            }),
+            None,
+        )
+    };
+    app.set_sender(&admin);
+    let error: anyhow::Error = set_distribution(
+        &app,
+        vec![(admin.to_string(), 5000), (treasury.to_string(), 4000)],
+    )
+    .unwrap_err()
+    .into();
+    assert_eq!(
+        AppError::InvalidSlashDistribution { total_bps: 9000 }.to_string(),
+        error.root_cause().to_string()
+    );
+    set_distribution(
+        &app,
+        vec![
+            (admin.to_string(), 5000),
+            (treasury.to_string(), 3333),
+            (burn.to_string(), 1667),
+        ],
+    )?;
+
+    let balances = || -> anyhow::Result<Vec<Uint128>> {
+        let mut balances = vec![];
+        for recipient in [&admin, &treasury, &burn] {
+            balances.push(mock.query_balance(recipient, DENOM)?);
+        }
+        Ok(balances)
+    };
+    let received = |before: Vec<Uint128>| -> anyhow::Result<Vec<Uint128>> {
+        Ok(balances()?
+            .into_iter()
+            .zip(before)
+            .map(|(after, before)| after - before)
+            .collect())
+    };
+
+    // The rounding remainder goes to the last recipient, so the parts add up to the slash.
+    let before = balances()?;
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "10:00")?;
+    let response = app.slash_full_stake(day_datetime.into(), meeting_index)?;
+    assert_eq!(
+        "50",
+        response.event_attr_value("wasm-abstract", "amount_slashed")?
+    );
+    let parts = received(before)?;
+    assert_eq!(
+        vec![Uint128::new(25), Uint128::new(16), Uint128::new(9)],
+        parts
+    );
+    assert_eq!(Uint128::new(50), parts.into_iter().sum());
+
+    let before = balances()?;
+    let (day_datetime, meeting_index) = app.find_meeting("2024-03-01", "11:00")?;
+    let response = app.slash_partial_stake(day_datetime.into(), meeting_index, 10)?;
+    assert_eq!(
+        "10",
+        response.event_attr_value("wasm-abstract", "amount_slashed")?
+    );
+    let parts = received(before)?;
+    assert_eq!(
+        vec![Uint128::new(5), Uint128::new(3), Uint128::new(2)],
+        parts
+    );
+    assert_eq!(Uint128::new(10), parts.into_iter().sum());
+
+    Ok(())
+}
+
+#[test]
+fn resolving_a_stake_on_a_busy_day_keeps_totals() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    // Thirty back-to-back meetings of fifteen minutes from the opening of the calendar.
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    let opening = local_timestamp(&app, "2024-03-01", "09:00")?;
+    for slot in 0..30 {
+        let start_time = opening + slot * 15 * 60;
+        app.request_meeting_with_auto_funds(start_time, start_time + 15 * 60)?;
+    }
+    set_local_time(&app, "2024-03-01", "17:00")?;
+
+    // Resolving a stake reads the day and the config once. Saving the day used to read both
+    // again, which cost the gas of these reads on top.
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let (_, after) = with_metered_storage(&app, |deps, env| {
+        let return_stake = AppExecuteMsg::ReturnStake {
+            day_datetime: day_datetime.into(),
+            meeting_index: 10,
+        };
+        Ok(app::contract::execute(
+            deps,
+            env,
+            mock_info("contract2", &[]),
+            return_stake.into(),
+        )?)
+    })?;
+    assert_eq!(1, after.reads(&CALENDAR.key(day_datetime)));
+    assert_eq!(1, after.reads(CONFIG.as_slice()));
+    let (_, rereads) = with_metered_storage(&app, |deps, _| {
+        CALENDAR.load(deps.storage, deps.api, day_datetime)?;
+        CONFIG.load(deps.storage)?;
+        Ok(())
+    })?;
+    let before = after.gas + rereads.gas;
+    // The reads saved are charged for each byte of the thirty meetings of the day.
+    assert!(before > after.gas + 1_000 + 30 * 3 * 50);
+
+    // Not sure how to get this dynamically...
+    app.set_sender(&Addr::unchecked("contract2"));
+    app.return_stake(day_datetime.into(), 10)?;
+    app.slash_full_stake(day_datetime.into(), 0)?;
+
+    let meetings = app.meetings(day_datetime)?.meetings;
+    assert_eq!(28, meetings.len());
+    assert!(meetings.iter().all(
+        |meeting| meeting.start_time != opening && meeting.start_time != opening + 10 * 15 * 60
+    ));
+    assert_eq!(
+        OutstandingResponse {
+            denom: DENOM.to_string(),
+            outstanding: Uint128::from(28 * 15u128),
+            balance: Uint128::from(28 * 15u128),
+        },
+        app.query(&QueryMsg::from(AppQueryMsg::Outstanding {}))?
+    );
+    assert_eq!(
+        Uint128::from(INITIAL_BALANCE - 29 * 15),
+        mock.query_balance(&sender, DENOM)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn encrypted_payload_is_stored_and_returned() -> anyhow::Result<()> {
+    let (_account, _abstr, mut app, _mock) = setup()?;
+    set_local_time(&app, "2024-03-01", "08:00")?;
+
+    let sender = Addr::unchecked("sender");
+    app.set_sender(&sender);
+    app.request_meeting_with_auto_funds(
+        local_timestamp(&app, "2024-03-01", "10:00")?,
+        local_timestamp(&app, "2024-03-01", "11:00")?,
+    )?;
+    let day_datetime = local_timestamp(&app, "2024-03-01", "00:00")?;
+    let set_payload = |payload: Option<Binary>| {
+        abstract_core::base::ExecuteMsg::Module(AppExecuteMsg::SetEncryptedPayload {
+            day_datetime: day_datetime.into(),
+            meeting_index: 0,
+            payload,
+        })
+    };
+
+    // Any bytes are stored as they are, up to the size limit.
+    let payload = Binary::from(
+        (0..=255u8)
+            .cycle()
+            .take(MAX_ENCRYPTED_PAYLOAD_BYTES)
+            .collect::<Vec<_>>(),
+    );
+    app.execute(&set_payload(Some(payload.clone())), None)?;
+    assert_eq!(
+        Some(payload),
+        app.meetings(day_datetime)?.meetings[0].encrypted_payload
+    );
+
+    let error: anyhow::Error = app
+        .execute(
+            &set_payload(Some(Binary::from(vec![0; MAX_ENCRYPTED_PAYLOAD_BYTES + 1]))),
+            None,
+        )
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::EncryptedPayloadTooLarge {
+            size: MAX_ENCRYPTED_PAYLOAD_BYTES + 1,
+            max: MAX_ENCRYPTED_PAYLOAD_BYTES,
+        }
+        .to_string(),
+        error.root_cause().to_string()
+    );
+
+    app.set_sender(&Addr::unchecked("someone_else"));
+    let error: anyhow::Error = app
+        .execute(&set_payload(Some(Binary::from(b"agenda".to_vec()))), None)
+        .unwrap_err()
+        .into();
+    assert_eq!(
+        AppError::NotMeetingRequester {}.to_string(),
+        error.root_cause().to_string()
+    );
+
+    app.set_sender(&sender);
+    app.execute(&set_payload(None), None)?;
+    assert_eq!(
+        None,
+        app.meetings(day_datetime)?.meetings[0].encrypted_payload
+    );
+
+    Ok(())
+}
@@ -0,0 +1,219 @@
+//! Property tests driving the booking and payout helpers with random sequences of actions and
+//! checking that the stored calendar never violates its invariants.
+
+use std::collections::HashMap;
+
+use app::{
+    msg::Time,
+    state::{Config, Meeting, SlashCurve, CALENDAR},
+    time::{get_date_time, start_of_day_timestamp},
+    validation::{find_conflict, partial_slash_payout, validate_meeting_times, MeetingDuration},
+};
+use chrono::{FixedOffset, NaiveTime};
+use cosmwasm_std::{testing::mock_dependencies, Addr, Api, Order, StdResult, Storage, Uint128};
+use proptest::prelude::*;
+
+// 2023-11-15T00:00:00Z
+const NOW: i64 = 1_700_006_400;
+const DAY: i64 = 86_400;
+
+#[derive(Debug, Clone)]
+enum Action {
+    Book {
+        requester: u8,
+        day: i64,
+        start_minute: i64,
+        duration_minutes: i64,
+    },
+    Resolve {
+        day: i64,
+        meeting_index: usize,
+        resolution: Resolution,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum Resolution {
+    Return,
+    FullSlash,
+    PartialSlash { minutes_late: u32 },
+}
+
+fn config(utc_offset: i32) -> Config {
+    Config {
+        price_per_minute: Uint128::from(3u128),
+        denom: "stake".to_string(),
+        utc_offset,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 0,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![],
+        denom_decimals: 0,
+        allowed_weekdays: vec![],
+        booking_fee: Uint128::zero(),
+        return_claim_deadline: None,
+        attestor: None,
+        allow_partial_outside: false,
+        price_outside_hours: false,
+        price_source: None,
+        check_outstanding_balance: false,
+        time_granularity_seconds: 0,
+        allow_admin_booking: false,
+        overbooking_allowed: false,
+        pegged_price_per_minute: None,
+        slash_curve: SlashCurve::Linear,
+        cancel_cutoff_secs: 0,
+        name: None,
+        description: None,
+        contact_uri: None,
+        slash_distribution: vec![],
+    }
+}
+
+fn action_strategy() -> impl Strategy<Value = Action> {
+    let resolution = prop_oneof![
+        Just(Resolution::Return),
+        Just(Resolution::FullSlash),
+        (0u32..300).prop_map(|minutes_late| Resolution::PartialSlash { minutes_late }),
+    ];
+    prop_oneof![
+        (0u8..3, 1i64..4, 0i64..1440, 1i64..300).prop_map(
+            |(requester, day, start_minute, duration_minutes)| Action::Book {
+                requester,
+                day,
+                start_minute,
+                duration_minutes,
+            }
+        ),
+        (1i64..4, 0usize..6, resolution).prop_map(|(day, meeting_index, resolution)| {
+            Action::Resolve {
+                day,
+                meeting_index,
+                resolution,
+            }
+        }),
+    ]
+}
+
+fn stored_days(storage: &dyn Storage, api: &dyn Api) -> Vec<(i64, Vec<Meeting>)> {
+    CALENDAR
+        .range(storage, api, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()
+        .unwrap()
+}
+
+proptest! {
+    #[test]
+    fn calendar_invariants_hold(
+        utc_offset in prop_oneof![Just(0i32), Just(3600), Just(-5 * 3600)],
+        actions in prop::collection::vec(action_strategy(), 1..40),
+    ) {
+        let mut deps = mock_dependencies();
+        let config = config(utc_offset);
+        let timezone = FixedOffset::east_opt(utc_offset).unwrap();
+        let calendar_start_time = NaiveTime::try_from(config.start_time.clone()).unwrap();
+        let calendar_end_time = NaiveTime::try_from(config.end_time.clone()).unwrap();
+
+        // (day, start_time) -> (original stake, total paid out)
+        let mut payouts: HashMap<(i64, i64), (Uint128, Uint128)> = HashMap::new();
+
+        for action in actions {
+            match action {
+                Action::Book { requester, day, start_minute, duration_minutes } => {
+                    let start_time = NOW + day * DAY + start_minute * 60;
+                    let end_time = start_time + duration_minutes * 60;
+                    let start_datetime = get_date_time(timezone, start_time.into()).unwrap();
+                    let end_datetime = get_date_time(timezone, end_time.into()).unwrap();
+                    if validate_meeting_times(&config, NOW, &start_datetime, &end_datetime).is_err() {
+                        continue;
+                    }
+
+                    let day_datetime = start_of_day_timestamp(&start_datetime);
+                    let mut meetings = CALENDAR
+                        .may_load(&deps.storage, &deps.api, day_datetime)
+                        .unwrap()
+                        .unwrap_or_default();
+                    if find_conflict(&meetings, start_time, end_time).is_some() {
+                        continue;
+                    }
+                    let amount_staked =
+                        config.price_per_minute * Uint128::from(duration_minutes as u128);
+                    meetings.push(Meeting {
+                        start_time,
+                        end_time,
+                        requester: Addr::unchecked(format!("requester{requester}")),
+                        amount_staked,
+                        refund_splits: None,
+                        denom: None,
+                        reminder_minutes: None,
+                        free: false,
+                        attended: None,
+                        disputed: false,
+                        checked_in: false,
+                        ended_early_at: None,
+                        self_booked: false,
+                        resolution: None,
+                        cancel_deadline: None,
+                        encrypted_payload: None,
+                    });
+                    CALENDAR.save(&mut deps.storage, &deps.api, day_datetime, &meetings).unwrap();
+                    payouts.insert((day_datetime, start_time), (amount_staked, Uint128::zero()));
+                }
+                Action::Resolve { day, meeting_index, resolution } => {
+                    let days = stored_days(&deps.storage, &deps.api);
+                    let Some((day_datetime, mut meetings)) = days.into_iter().nth(day as usize - 1) else {
+                        continue;
+                    };
+                    let Some(meeting) = meetings.get_mut(meeting_index) else {
+                        continue;
+                    };
+                    if meeting.amount_staked.is_zero() {
+                        continue;
+                    }
+                    let amount_staked = meeting.amount_staked;
+                    let (to_requester, to_admin) = match resolution {
+                        Resolution::Return => (amount_staked, Uint128::zero()),
+                        Resolution::FullSlash => (Uint128::zero(), amount_staked),
+                        Resolution::PartialSlash { minutes_late } => {
+                            let duration = MeetingDuration::of(meeting).unwrap().minutes();
+                            match partial_slash_payout(amount_staked, minutes_late, duration) {
+                                Ok(payout) => payout,
+                                Err(_) => {
+                                    prop_assert!(minutes_late > duration);
+                                    continue;
+                                }
+                            }
+                        }
+                    };
+                    meeting.amount_staked = Uint128::zero();
+                    let paid = &mut payouts.get_mut(&(day_datetime, meeting.start_time)).unwrap().1;
+                    *paid += to_requester + to_admin;
+                    CALENDAR.save(&mut deps.storage, &deps.api, day_datetime, &meetings).unwrap();
+                }
+            }
+
+            for (_, meetings) in stored_days(&deps.storage, &deps.api) {
+                for (i, meeting) in meetings.iter().enumerate() {
+                    // No two meetings on the same day overlap.
+                    for other in meetings.iter().skip(i + 1) {
+                        prop_assert!(
+                            meeting.end_time <= other.start_time || other.end_time <= meeting.start_time,
+                            "{:?} overlaps {:?}", meeting, other
+                        );
+                    }
+                    // Every meeting lies within the configured daily window.
+                    let start = get_date_time(timezone, meeting.start_time.into()).unwrap().time();
+                    let end = get_date_time(timezone, meeting.end_time.into()).unwrap().time();
+                    prop_assert!(calendar_start_time <= start && end <= calendar_end_time);
+                }
+            }
+            // Payouts never exceed the original stake.
+            for (stake, paid) in payouts.values() {
+                prop_assert!(paid <= stake);
+            }
+        }
+    }
+}
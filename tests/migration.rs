@@ -0,0 +1,241 @@
+use app::{
+    error::AppError,
+    migration::{compact_meetings, rebucket_days, rekey_waitlist, Rebucketed},
+    state::{to_stored, Meeting, Resolution, ResolutionAction, WaitlistEntry, CALENDAR, WAITLIST},
+    testing::MeteredStorage,
+};
+use chrono::{FixedOffset, TimeZone};
+use cosmwasm_std::{testing::mock_dependencies, to_vec, Addr, Coin, Order, StdResult, Uint128};
+use cw_storage_plus::Map;
+
+fn meeting(start_time: i64) -> Meeting {
+    Meeting {
+        start_time,
+        end_time: start_time + 3600,
+        requester: Addr::unchecked("requester"),
+        amount_staked: Uint128::new(60),
+        refund_splits: None,
+        denom: None,
+        reminder_minutes: None,
+        free: false,
+        attended: None,
+        disputed: false,
+        checked_in: false,
+        ended_early_at: None,
+        self_booked: false,
+        resolution: None,
+        cancel_deadline: None,
+        encrypted_payload: None,
+    }
+}
+
+#[test]
+fn rebucket_days_moves_meetings_to_their_local_day() -> Result<(), AppError> {
+    let mut deps = mock_dependencies();
+    let timezone = FixedOffset::east_opt(2 * 3600).unwrap();
+    let utc = FixedOffset::east_opt(0).unwrap();
+    let local = |day: u32, hour: u32| timezone.with_ymd_and_hms(2024, 3, day, hour, 0, 0).unwrap();
+
+    // Both meetings were keyed by their UTC date, even though the second one starts on the
+    // next local day, which already has a correctly keyed meeting.
+    let skewed_day = utc
+        .with_ymd_and_hms(2024, 3, 1, 0, 0, 0)
+        .unwrap()
+        .timestamp();
+    let (morning, past_midnight, next_morning) = (
+        meeting(local(1, 9).timestamp()),
+        meeting(local(2, 1).timestamp()),
+        meeting(local(2, 10).timestamp()),
+    );
+    CALENDAR.save(
+        &mut deps.storage,
+        &deps.api,
+        skewed_day,
+        &[morning.clone(), past_midnight.clone()],
+    )?;
+    CALENDAR.save(
+        &mut deps.storage,
+        &deps.api,
+        local(2, 0).timestamp(),
+        std::slice::from_ref(&next_morning),
+    )?;
+
+    // A step of no days could not tell where to resume.
+    assert_eq!(
+        AppError::ZeroLimit {}.to_string(),
+        rebucket_days(&mut deps.storage, &deps.api, timezone, None, 0)
+            .unwrap_err()
+            .to_string()
+    );
+
+    let first = rebucket_days(&mut deps.storage, &deps.api, timezone, None, 1)?;
+    assert_eq!(
+        Rebucketed {
+            days_checked: 1,
+            meetings_moved: 2,
+            resume_after: Some(skewed_day),
+        },
+        first
+    );
+    let second = rebucket_days(
+        &mut deps.storage,
+        &deps.api,
+        timezone,
+        first.resume_after,
+        1,
+    )?;
+    assert_eq!(
+        Rebucketed {
+            days_checked: 1,
+            meetings_moved: 0,
+            resume_after: None,
+        },
+        second
+    );
+
+    let days: Vec<(i64, Vec<Meeting>)> = CALENDAR
+        .range(&deps.storage, &deps.api, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    assert_eq!(
+        vec![
+            (local(1, 0).timestamp(), vec![morning]),
+            (local(2, 0).timestamp(), vec![next_morning, past_midnight]),
+        ],
+        days
+    );
+
+    // Running the migration again finds nothing left to move.
+    assert_eq!(
+        Rebucketed {
+            days_checked: 2,
+            meetings_moved: 0,
+            resume_after: None,
+        },
+        rebucket_days(&mut deps.storage, &deps.api, timezone, None, 10)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn compact_meetings_rewrites_legacy_days() -> Result<(), AppError> {
+    let mut deps = mock_dependencies();
+    let legacy: Map<i64, Vec<Meeting>> = Map::new("calendar");
+    let plain = meeting(1_709_287_200);
+    let detailed = Meeting {
+        refund_splits: Some(vec![(Addr::unchecked("friend"), 10_000)]),
+        denom: Some("other".to_string()),
+        reminder_minutes: Some(15),
+        attended: Some(false),
+        disputed: true,
+        ended_early_at: Some(1_709_290_000),
+        resolution: Some(Resolution {
+            action: ResolutionAction::PartialSlash { minutes_late: 5 },
+            resolver: Addr::unchecked("admin"),
+            resolved_at: 1_709_291_000,
+            amount_to_requester: Uint128::new(55),
+            amount_to_admin: Uint128::new(5),
+        }),
+        cancel_deadline: Some(-1),
+        ..meeting(1_709_290_800)
+    };
+    legacy.save(&mut deps.storage, 1_709_251_200, &vec![plain.clone()])?;
+    legacy.save(
+        &mut deps.storage,
+        1_709_337_600,
+        &vec![detailed.clone(), plain.clone()],
+    )?;
+
+    assert_eq!(2, compact_meetings(&mut deps.storage, &deps.api)?);
+    assert_eq!(
+        vec![plain.clone()],
+        CALENDAR.load(&deps.storage, &deps.api, 1_709_251_200)?
+    );
+    assert_eq!(
+        vec![detailed, plain],
+        CALENDAR.load(&deps.storage, &deps.api, 1_709_337_600)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn rekey_waitlist_moves_entries_under_their_day() -> Result<(), AppError> {
+    let mut deps = mock_dependencies();
+    let legacy: Map<u64, WaitlistEntry> = Map::new("waitlist");
+    let entry = |day_datetime: i64| WaitlistEntry {
+        requester: Addr::unchecked("requester"),
+        day_datetime,
+        start_time: day_datetime + 36_000,
+        end_time: day_datetime + 39_600,
+        stake: Coin::new(60, "stake"),
+        booking_fee: Uint128::zero(),
+    };
+    legacy.save(&mut deps.storage, 0, &entry(1_709_337_600))?;
+    legacy.save(&mut deps.storage, 1, &entry(1_709_251_200))?;
+    legacy.save(&mut deps.storage, 2, &entry(1_709_337_600))?;
+
+    assert_eq!(3, rekey_waitlist(&mut deps.storage)?);
+    assert_eq!(
+        0,
+        legacy
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .count()
+    );
+    assert_eq!(
+        vec![(0, entry(1_709_337_600)), (2, entry(1_709_337_600))],
+        WAITLIST
+            .prefix(1_709_337_600)
+            .range(&deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?
+    );
+    assert_eq!(
+        entry(1_709_251_200),
+        WAITLIST.load(&deps.storage, (1_709_251_200, 1))?
+    );
+    // Running it again finds nothing left to move.
+    assert_eq!(0, rekey_waitlist(&mut deps.storage)?);
+
+    Ok(())
+}
+
+#[test]
+fn stored_meetings_are_smaller_and_cheaper_than_meetings() -> Result<(), AppError> {
+    let mut deps = mock_dependencies();
+    // A busy day of fifty meetings, half of them with a reminder.
+    let day_datetime = 1_709_251_200;
+    let meetings: Vec<Meeting> = (0..50)
+        .map(|slot| Meeting {
+            reminder_minutes: Some(15).filter(|_| slot % 2 == 0),
+            ..meeting(1_709_287_200 + slot * 900)
+        })
+        .collect();
+    let verbose = to_vec(&meetings)?.len();
+    let compact = to_vec(&to_stored(&deps.api, &meetings)?)?.len();
+    assert_eq!(16_151, verbose);
+    assert!(compact < verbose);
+
+    // Storage gas is charged per byte written and read, so it shrinks with the day. Canonical
+    // addresses of the mock API are longer than the 20 bytes of those of a chain, which saves
+    // more there.
+    let legacy: Map<i64, Vec<Meeting>> = Map::new("calendar");
+    let mut storage = MeteredStorage::new(&mut deps.storage);
+    legacy.save(&mut storage, day_datetime, &meetings)?;
+    let legacy_write = storage.take_gas();
+    legacy.load(&storage, day_datetime)?;
+    let legacy_read = storage.take_gas();
+    // The key is the length-prefixed namespace followed by the day.
+    let bytes = |value: usize| u64::try_from(2 + "calendar".len() + 8 + value).unwrap();
+    assert_eq!(2_000 + 30 * bytes(verbose), legacy_write);
+    assert_eq!(1_000 + 3 * bytes(verbose), legacy_read);
+
+    CALENDAR.save(&mut storage, &deps.api, day_datetime, &meetings)?;
+    let write = storage.take_gas();
+    assert_eq!(meetings, CALENDAR.load(&storage, &deps.api, day_datetime)?);
+    let read = storage.take_gas();
+    assert_eq!(2_000 + 30 * bytes(compact), write);
+    assert_eq!(1_000 + 3 * bytes(compact), read);
+    assert!(write < legacy_write && read < legacy_read);
+
+    Ok(())
+}
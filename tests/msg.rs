@@ -0,0 +1,146 @@
+use app::{
+    msg::{MeetingResponse, MeetingStatus, Time},
+    state::{Config, Meeting, Resolution, ResolutionAction, SlashCurve},
+};
+use cosmwasm_std::{testing::mock_env, Addr, Env, Timestamp, Uint128};
+
+// 2024-03-01T10:00:00Z
+const START_TIME: i64 = 1_709_287_200;
+
+fn config(utc_offset: i32) -> Config {
+    Config {
+        price_per_minute: Uint128::one(),
+        denom: "stake".to_string(),
+        utc_offset,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 0,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![],
+        denom_decimals: 0,
+        allowed_weekdays: vec![],
+        booking_fee: Uint128::zero(),
+        return_claim_deadline: None,
+        attestor: None,
+        allow_partial_outside: false,
+        price_outside_hours: false,
+        price_source: None,
+        check_outstanding_balance: false,
+        time_granularity_seconds: 0,
+        allow_admin_booking: false,
+        overbooking_allowed: false,
+        pegged_price_per_minute: None,
+        slash_curve: SlashCurve::Linear,
+        cancel_cutoff_secs: 0,
+        name: None,
+        description: None,
+        contact_uri: None,
+        slash_distribution: vec![],
+    }
+}
+
+fn meeting() -> Meeting {
+    Meeting {
+        start_time: START_TIME,
+        end_time: START_TIME + 90 * 60,
+        requester: Addr::unchecked("requester"),
+        amount_staked: Uint128::new(90),
+        refund_splits: None,
+        denom: None,
+        reminder_minutes: Some(15),
+        free: false,
+        attended: None,
+        disputed: false,
+        checked_in: false,
+        ended_early_at: None,
+        self_booked: false,
+        resolution: None,
+        cancel_deadline: Some(START_TIME),
+        encrypted_payload: None,
+    }
+}
+
+fn env_at(timestamp: i64) -> Env {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(timestamp as u64);
+    env
+}
+
+#[test]
+fn meeting_response_renders_local_times_and_duration() {
+    let response =
+        MeetingResponse::try_from((&meeting(), &config(2 * 3600), &env_at(START_TIME))).unwrap();
+    assert_eq!(
+        MeetingResponse {
+            start_time: START_TIME,
+            end_time: START_TIME + 90 * 60,
+            start_local: "2024-03-01T12:00:00+02:00".to_string(),
+            end_local: "2024-03-01T13:30:00+02:00".to_string(),
+            requester: Addr::unchecked("requester"),
+            amount_staked: Uint128::new(90),
+            denom: "stake".to_string(),
+            duration_minutes: 90,
+            status: MeetingStatus::InProgress,
+            refund_splits: None,
+            reminder_minutes: Some(15),
+            free: false,
+            attended: None,
+            disputed: false,
+            checked_in: false,
+            ended_early_at: None,
+            self_booked: false,
+            cancel_deadline: Some(START_TIME),
+            encrypted_payload: None,
+        },
+        response
+    );
+
+    let mut other_denom = meeting();
+    other_denom.denom = Some("other".to_string());
+    let response =
+        MeetingResponse::try_from((&other_denom, &config(0), &env_at(START_TIME))).unwrap();
+    assert_eq!("other", response.denom);
+}
+
+#[test]
+fn meeting_status_follows_block_time() {
+    let config = config(0);
+    let status = |meeting: &Meeting, now: i64| {
+        MeetingResponse::try_from((meeting, &config, &env_at(now)))
+            .unwrap()
+            .status
+    };
+    let meeting = meeting();
+    assert_eq!(MeetingStatus::Upcoming, status(&meeting, START_TIME - 1));
+    assert_eq!(MeetingStatus::InProgress, status(&meeting, START_TIME));
+    assert_eq!(
+        MeetingStatus::InProgress,
+        status(&meeting, meeting.end_time - 1)
+    );
+    assert_eq!(MeetingStatus::Ended, status(&meeting, meeting.end_time));
+
+    let mut ended_early = meeting.clone();
+    ended_early.ended_early_at = Some(START_TIME + 600);
+    assert_eq!(MeetingStatus::Ended, status(&ended_early, START_TIME + 600));
+
+    let mut resolved = meeting.clone();
+    resolved.resolution = Some(Resolution {
+        action: ResolutionAction::FullSlash,
+        resolver: Addr::unchecked("admin"),
+        resolved_at: meeting.end_time + 60,
+        amount_to_requester: Uint128::zero(),
+        amount_to_admin: Uint128::new(90),
+    });
+    assert_eq!(
+        MeetingStatus::Resolved {
+            action: ResolutionAction::FullSlash,
+            resolver: Addr::unchecked("admin"),
+            resolved_at: meeting.end_time + 60,
+            amount_to_requester: Uint128::zero(),
+            amount_to_admin: Uint128::new(90),
+        },
+        status(&resolved, meeting.end_time + 60)
+    );
+}
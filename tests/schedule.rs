@@ -0,0 +1,147 @@
+use app::{
+    msg::Slot,
+    schedule::{
+        busy_blocks, free_minutes, free_slots, in_calendar_window, longest_free_slot,
+        max_non_overlapping, overlapping_pairs,
+    },
+    state::Meeting,
+};
+use cosmwasm_std::{Addr, Uint128};
+
+fn meeting(start_time: i64, end_time: i64) -> Meeting {
+    Meeting {
+        start_time,
+        end_time,
+        requester: Addr::unchecked("requester"),
+        amount_staked: Uint128::zero(),
+        refund_splits: None,
+        denom: None,
+        reminder_minutes: None,
+        free: false,
+        attended: None,
+        disputed: false,
+        checked_in: false,
+        ended_early_at: None,
+        self_booked: false,
+        resolution: None,
+        cancel_deadline: None,
+        encrypted_payload: None,
+    }
+}
+
+#[test]
+fn max_non_overlapping_of_empty_day_is_zero() {
+    assert_eq!(0, max_non_overlapping(&[]));
+}
+
+#[test]
+fn max_non_overlapping_counts_back_to_back_meetings() {
+    let meetings = vec![meeting(0, 60), meeting(60, 120), meeting(120, 180)];
+    assert_eq!(3, max_non_overlapping(&meetings));
+}
+
+#[test]
+fn max_non_overlapping_with_overlapping_meetings() {
+    // A long meeting overlapping three short ones, plus a meeting overlapping two of them.
+    let meetings = vec![
+        meeting(0, 600),
+        meeting(60, 120),
+        meeting(100, 300),
+        meeting(120, 180),
+        meeting(240, 360),
+    ];
+    assert_eq!(3, max_non_overlapping(&meetings));
+}
+
+#[test]
+fn overlapping_pairs_skip_back_to_back_meetings() {
+    let meetings = vec![
+        meeting(0, 600),
+        meeting(60, 120),
+        meeting(120, 180),
+        meeting(600, 660),
+    ];
+    assert_eq!(vec![(0, 1), (0, 2)], overlapping_pairs(&meetings).unwrap());
+}
+
+#[test]
+fn busy_blocks_merge_back_to_back_meetings() {
+    let meetings = vec![
+        meeting(120, 180),
+        meeting(0, 60),
+        meeting(60, 120),
+        meeting(300, 360),
+    ];
+    assert_eq!(vec![(0, 180), (300, 360)], busy_blocks(&meetings));
+}
+
+#[test]
+fn busy_blocks_merge_overlapping_meetings() {
+    let meetings = vec![meeting(0, 300), meeting(60, 120), meeting(240, 360)];
+    assert_eq!(vec![(0, 360)], busy_blocks(&meetings));
+    assert!(busy_blocks(&[]).is_empty());
+}
+
+fn slot(start_time: i64, end_time: i64) -> Slot {
+    Slot {
+        start_time,
+        end_time,
+    }
+}
+
+#[test]
+fn free_slots_between_meetings() {
+    let meetings = vec![meeting(300, 360), meeting(0, 60), meeting(100, 200)];
+    assert_eq!(
+        vec![slot(60, 100), slot(200, 300), slot(360, 600)],
+        free_slots(&meetings, 0, 600)
+    );
+    assert_eq!(vec![slot(0, 600)], free_slots(&[], 0, 600));
+}
+
+#[test]
+fn free_minutes_leave_out_time_outside_the_window() {
+    // A meeting running past the close takes its free time up to the close only.
+    let meetings = vec![meeting(600, 1200), meeting(3000, 4200)];
+    assert_eq!(40, free_minutes(&meetings, 0, 3600).unwrap());
+    assert_eq!(60, free_minutes(&[], 0, 3600).unwrap());
+}
+
+#[test]
+fn longest_free_slot_picks_the_largest_gap() {
+    // Gaps of 40, 100 and 30 seconds.
+    let meetings = vec![meeting(40, 60), meeting(160, 570)];
+    assert_eq!(Some(slot(60, 160)), longest_free_slot(&meetings, 0, 600));
+
+    // Gaps of 40, 40 and 240 seconds.
+    let meetings = vec![meeting(40, 100), meeting(140, 360)];
+    assert_eq!(Some(slot(360, 600)), longest_free_slot(&meetings, 0, 600));
+}
+
+#[test]
+fn longest_free_slot_prefers_the_earliest_on_ties() {
+    let meetings = vec![meeting(60, 120)];
+    assert_eq!(Some(slot(0, 60)), longest_free_slot(&meetings, 0, 180));
+}
+
+#[test]
+fn longest_free_slot_of_full_day_is_none() {
+    let meetings = vec![meeting(0, 300), meeting(300, 600)];
+    assert_eq!(None, longest_free_slot(&meetings, 0, 600));
+}
+
+#[test]
+fn calendar_window_is_half_open() {
+    assert!(in_calendar_window(0, 600, 0));
+    assert!(in_calendar_window(0, 600, 599));
+    assert!(!in_calendar_window(0, 600, 600));
+    assert!(!in_calendar_window(0, 600, -1));
+    assert!(!in_calendar_window(600, 600, 600));
+}
+
+#[test]
+fn free_slots_never_start_at_close() {
+    assert_eq!(Vec::<Slot>::new(), free_slots(&[meeting(0, 600)], 0, 600));
+    assert_eq!(Vec::<Slot>::new(), free_slots(&[], 600, 600));
+    assert_eq!(vec![slot(0, 300)], free_slots(&[meeting(300, 700)], 0, 600));
+}
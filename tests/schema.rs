@@ -0,0 +1,16 @@
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn schema_binary_writes_api() {
+    let out_dir = std::env::temp_dir().join("calendar-app-schema");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_schema"))
+        .current_dir(&out_dir)
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+    assert!(out_dir.join("schema").join("app.json").exists());
+}
@@ -0,0 +1,178 @@
+use app::{
+    error::AppError,
+    msg::Time,
+    state::{Config, SlashCurve},
+    time::{
+        day_key, format_local, parse_local, validate_utc_offset, validate_window, MAX_UTC_OFFSET,
+    },
+};
+use chrono::{DateTime, FixedOffset, TimeZone};
+use cosmwasm_std::Uint128;
+
+fn config(utc_offset: i32) -> Config {
+    Config {
+        price_per_minute: Uint128::one(),
+        denom: "stake".to_string(),
+        utc_offset,
+        start_time: Time { hour: 9, minute: 0 },
+        end_time: Time {
+            hour: 17,
+            minute: 0,
+        },
+        self_gap_minutes: 0,
+        accepted_denoms: vec![],
+        denom_decimals: 0,
+        allowed_weekdays: vec![],
+        booking_fee: Uint128::zero(),
+        return_claim_deadline: None,
+        attestor: None,
+        allow_partial_outside: false,
+        price_outside_hours: false,
+        price_source: None,
+        check_outstanding_balance: false,
+        time_granularity_seconds: 0,
+        allow_admin_booking: false,
+        overbooking_allowed: false,
+        pegged_price_per_minute: None,
+        slash_curve: SlashCurve::Linear,
+        cancel_cutoff_secs: 0,
+        name: None,
+        description: None,
+        contact_uri: None,
+        slash_distribution: vec![],
+    }
+}
+
+fn local(
+    timezone: FixedOffset,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+) -> DateTime<FixedOffset> {
+    timezone
+        .with_ymd_and_hms(2024, 3, day, hour, minute, second)
+        .unwrap()
+}
+
+#[test]
+fn day_key_is_the_local_midnight() {
+    for utc_offset in [-5 * 3600, 0, 5 * 3600 + 1800, MAX_UTC_OFFSET] {
+        let timezone = FixedOffset::east_opt(utc_offset).unwrap();
+        let midnight = local(timezone, 1, 0, 0, 0).timestamp();
+        assert_eq!(Ok(midnight), day_key(timezone, midnight));
+        assert_eq!(
+            Ok(midnight),
+            day_key(timezone, local(timezone, 1, 23, 59, 59).timestamp())
+        );
+        assert_eq!(
+            Ok(midnight + 86_400),
+            day_key(timezone, local(timezone, 2, 0, 0, 0).timestamp())
+        );
+    }
+}
+
+#[test]
+fn day_key_follows_the_local_date_not_the_utc_date() {
+    let utc = FixedOffset::east_opt(0).unwrap();
+    let timezone = FixedOffset::east_opt(2 * 3600).unwrap();
+    // 23:00 UTC on March 1st is 01:00 on March 2nd locally.
+    let timestamp = local(utc, 1, 23, 0, 0).timestamp();
+    assert_eq!(
+        Ok(local(timezone, 2, 0, 0, 0).timestamp()),
+        day_key(timezone, timestamp)
+    );
+}
+
+#[test]
+fn format_local_shows_the_offset() {
+    let utc = FixedOffset::east_opt(0).unwrap();
+    // 10:00 UTC on March 1st.
+    let timestamp = local(utc, 1, 10, 0, 0).timestamp();
+    for (utc_offset, expected) in [
+        (0, "2024-03-01T10:00:00+00:00"),
+        (5 * 3600 + 1800, "2024-03-01T15:30:00+05:30"),
+        (-8 * 3600, "2024-03-01T02:00:00-08:00"),
+        (-11 * 3600, "2024-02-29T23:00:00-11:00"),
+    ] {
+        let timezone = FixedOffset::east_opt(utc_offset).unwrap();
+        assert_eq!(Ok(expected.to_string()), format_local(timezone, timestamp));
+    }
+}
+
+#[test]
+fn parse_local_reads_what_format_local_writes() {
+    let timezone = FixedOffset::east_opt(2 * 3600).unwrap();
+    let timestamp = local(timezone, 1, 14, 0, 0).timestamp();
+    let formatted = format_local(timezone, timestamp).unwrap();
+    assert_eq!("2024-03-01T14:00:00+02:00", formatted);
+    assert_eq!(Ok(timestamp.into()), parse_local(timezone, &formatted));
+
+    assert_eq!(
+        Err(AppError::IsoTimeOffsetMismatch {
+            input: "2024-03-01T12:00:00Z".to_string(),
+            utc_offset: 2 * 3600,
+        }),
+        parse_local(timezone, "2024-03-01T12:00:00Z")
+    );
+    for input in [
+        "2024-03-01 14:00",
+        "2024-03-01T14:00:00",
+        "2024-02-30T14:00:00+02:00",
+        "2024-03-01T14:00:00.5+02:00",
+        "",
+    ] {
+        assert!(matches!(
+            parse_local(timezone, input),
+            Err(AppError::InvalidIsoTime { input: quoted, .. }) if quoted == input
+        ));
+    }
+}
+
+#[test]
+fn utc_offsets_are_limited_to_fourteen_hours() {
+    assert!(validate_utc_offset(MAX_UTC_OFFSET).is_ok());
+    assert!(validate_utc_offset(-MAX_UTC_OFFSET).is_ok());
+    for offset in [MAX_UTC_OFFSET + 1, -MAX_UTC_OFFSET - 1, i32::MAX, i32::MIN] {
+        assert_eq!(
+            Err(AppError::InvalidUtcOffset { offset }),
+            validate_utc_offset(offset)
+        );
+    }
+}
+
+#[test]
+fn validate_window_checks_the_local_times() {
+    let config = config(3600);
+    let timezone = FixedOffset::east_opt(3600).unwrap();
+    let now = local(timezone, 1, 8, 0, 0).timestamp();
+    let window = |start: DateTime<FixedOffset>, end: DateTime<FixedOffset>| {
+        validate_window(&config, start.timestamp(), end.timestamp(), now)
+    };
+
+    assert_eq!(
+        Ok(()),
+        window(local(timezone, 1, 10, 0, 0), local(timezone, 1, 11, 0, 0))
+    );
+    assert_eq!(
+        Err(AppError::StartTimeMustBeInFuture {}),
+        window(local(timezone, 1, 7, 0, 0), local(timezone, 1, 7, 30, 0))
+    );
+    assert_eq!(
+        Err(AppError::StartTimeNotRoundedToNearestMinute {}),
+        window(local(timezone, 1, 10, 0, 30), local(timezone, 1, 11, 0, 0))
+    );
+    assert_eq!(
+        Err(AppError::StartAndEndTimeNotOnSameDay {}),
+        window(local(timezone, 1, 16, 0, 0), local(timezone, 2, 10, 0, 0))
+    );
+    assert_eq!(
+        Err(AppError::StartTimeAtCalendarClose {
+            time: Time {
+                hour: 17,
+                minute: 0
+            },
+        }),
+        window(local(timezone, 1, 17, 0, 0), local(timezone, 1, 17, 30, 0))
+    );
+}
@@ -0,0 +1,452 @@
+use app::{
+    error::AppError,
+    msg::Time,
+    state::{Meeting, SlashCurve},
+    time::{duration_in_minutes, get_date_time},
+    validation::{
+        curve_slash_payout, find_conflict, partial_slash_payout, payment_required,
+        validate_profile_field, validate_slash_curve, MeetingDuration, MAX_MEETING_SECONDS,
+        MAX_TIMESTAMP,
+    },
+};
+use chrono::{FixedOffset, NaiveTime};
+use cosmwasm_std::{coin, Addr, Int64, Uint128};
+use cw_utils::PaymentError;
+
+fn meeting(start_time: i64, end_time: i64) -> Meeting {
+    Meeting {
+        start_time,
+        end_time,
+        requester: Addr::unchecked("requester"),
+        amount_staked: Uint128::zero(),
+        refund_splits: None,
+        denom: None,
+        reminder_minutes: None,
+        free: false,
+        attended: None,
+        disputed: false,
+        checked_in: false,
+        ended_early_at: None,
+        self_booked: false,
+        resolution: None,
+        cancel_deadline: None,
+        encrypted_payload: None,
+    }
+}
+
+#[test]
+fn conflict_with_enveloped_meeting() {
+    let meetings = vec![meeting(3600, 7200)];
+    assert_eq!(Some(&meetings[0]), find_conflict(&meetings, 0, 10800));
+}
+
+#[test]
+fn conflict_with_exact_duplicate() {
+    let meetings = vec![meeting(3600, 7200)];
+    assert_eq!(Some(&meetings[0]), find_conflict(&meetings, 3600, 7200));
+}
+
+#[test]
+fn conflict_with_containing_meeting() {
+    let meetings = vec![meeting(0, 10800)];
+    assert_eq!(Some(&meetings[0]), find_conflict(&meetings, 3600, 7200));
+}
+
+#[test]
+fn conflict_with_earliest_overlapping_meeting() {
+    let meetings = vec![meeting(7200, 10800), meeting(0, 3600), meeting(3600, 7200)];
+    assert_eq!(Some(&meetings[1]), find_conflict(&meetings, 0, 10800));
+    assert_eq!(Some(&meetings[2]), find_conflict(&meetings, 5400, 9000));
+}
+
+#[test]
+fn no_conflict_with_touching_meetings() {
+    let meetings = vec![meeting(0, 3600), meeting(7200, 10800)];
+    assert_eq!(None, find_conflict(&meetings, 3600, 7200));
+}
+
+#[test]
+fn partial_slash_of_one_minute_meeting() {
+    assert_eq!(
+        (Uint128::from(60u128), Uint128::zero()),
+        partial_slash_payout(Uint128::from(60u128), 0, 1).unwrap()
+    );
+    assert_eq!(
+        (Uint128::zero(), Uint128::from(60u128)),
+        partial_slash_payout(Uint128::from(60u128), 1, 1).unwrap()
+    );
+}
+
+#[test]
+fn partial_slash_of_stake_of_one_rounds_towards_requester() {
+    for minutes_late in 0..60 {
+        assert_eq!(
+            (Uint128::one(), Uint128::zero()),
+            partial_slash_payout(Uint128::one(), minutes_late, 60).unwrap()
+        );
+    }
+    assert_eq!(
+        (Uint128::zero(), Uint128::one()),
+        partial_slash_payout(Uint128::one(), 60, 60).unwrap()
+    );
+}
+
+#[test]
+fn partial_slash_legs_add_up_to_stake() {
+    for amount_staked in [1u128, 7, 1439, 1_000_003, u128::MAX] {
+        for duration in [1u32, 7, 59, 1440] {
+            for minutes_late in [0, 1, duration / 2, duration - 1, duration] {
+                let (to_requester, to_admin) =
+                    partial_slash_payout(Uint128::from(amount_staked), minutes_late, duration)
+                        .unwrap();
+                assert_eq!(Uint128::from(amount_staked), to_requester + to_admin);
+            }
+        }
+    }
+}
+
+#[test]
+fn partial_slash_cannot_exceed_duration() {
+    assert_eq!(
+        AppError::MinutesLateCannotExceedDurationOfMeeting {},
+        partial_slash_payout(Uint128::from(60u128), 61, 60).unwrap_err()
+    );
+}
+
+#[test]
+fn slash_of_zero_minute_meeting_is_an_error() {
+    assert_eq!(
+        AppError::ZeroMeetingDuration {},
+        partial_slash_payout(Uint128::from(60u128), 0, 0).unwrap_err()
+    );
+    for curve in [
+        SlashCurve::Linear,
+        SlashCurve::Quadratic,
+        SlashCurve::Threshold {
+            grace_minutes: 5,
+            full_after_minutes: 30,
+        },
+    ] {
+        assert_eq!(
+            AppError::ZeroMeetingDuration {},
+            curve_slash_payout(&curve, Uint128::from(60u128), 0, 0).unwrap_err(),
+            "{curve:?}"
+        );
+    }
+}
+
+#[test]
+fn slash_curves_for_the_same_lateness() {
+    let threshold = SlashCurve::Threshold {
+        grace_minutes: 5,
+        full_after_minutes: 30,
+    };
+    // Slashed out of a stake of 100 for a meeting of an hour: linear, quadratic, threshold.
+    for (minutes_late, expected) in [
+        (0, [0, 0, 0]),
+        (3, [5, 0, 0]),
+        (10, [16, 2, 20]),
+        (30, [50, 25, 100]),
+        (60, [100, 100, 100]),
+    ] {
+        for (curve, expected) in [SlashCurve::Linear, SlashCurve::Quadratic, threshold.clone()]
+            .iter()
+            .zip(expected)
+        {
+            let (to_requester, to_admin) =
+                curve_slash_payout(curve, Uint128::from(100u128), minutes_late, 60).unwrap();
+            assert_eq!(Uint128::from(expected as u128), to_admin, "{curve:?}");
+            assert_eq!(Uint128::from(100u128), to_requester + to_admin);
+        }
+    }
+    assert_eq!(
+        AppError::MinutesLateCannotExceedDurationOfMeeting {},
+        curve_slash_payout(&threshold, Uint128::from(100u128), 61, 60).unwrap_err()
+    );
+}
+
+#[test]
+fn threshold_grace_must_be_shorter_than_full_slash() {
+    assert!(validate_slash_curve(&SlashCurve::Threshold {
+        grace_minutes: 5,
+        full_after_minutes: 6,
+    })
+    .is_ok());
+    assert_eq!(
+        AppError::InvalidSlashCurve {
+            grace_minutes: 5,
+            full_after_minutes: 5,
+        },
+        validate_slash_curve(&SlashCurve::Threshold {
+            grace_minutes: 5,
+            full_after_minutes: 5,
+        })
+        .unwrap_err()
+    );
+}
+
+#[test]
+fn duration_in_whole_minutes() {
+    assert_eq!(1, duration_in_minutes(0, 60).unwrap());
+    assert_eq!(1, duration_in_minutes(0, 119).unwrap());
+    assert_eq!(24 * 60, duration_in_minutes(0, 24 * 3600).unwrap());
+}
+
+#[test]
+fn duration_requires_end_after_start() {
+    assert_eq!(
+        AppError::EndTimeMustBeAfterStartTime {},
+        duration_in_minutes(60, 60).unwrap_err()
+    );
+    assert_eq!(
+        AppError::EndTimeMustBeAfterStartTime {},
+        duration_in_minutes(120, 60).unwrap_err()
+    );
+}
+
+#[test]
+fn meeting_duration_of_at_most_a_day() {
+    assert_eq!(90, MeetingDuration::between(0, 90 * 60).unwrap().minutes());
+    assert_eq!(
+        24 * 60,
+        MeetingDuration::between(0, MAX_MEETING_SECONDS)
+            .unwrap()
+            .minutes()
+    );
+    assert_eq!(
+        AppError::MeetingTooLong {
+            start_time: 0,
+            end_time: MAX_MEETING_SECONDS + 60,
+        },
+        MeetingDuration::between(0, MAX_MEETING_SECONDS + 60).unwrap_err()
+    );
+    assert_eq!(
+        AppError::MeetingTooLong {
+            start_time: i64::MIN,
+            end_time: i64::MAX,
+        },
+        MeetingDuration::between(i64::MIN, i64::MAX).unwrap_err()
+    );
+    assert_eq!(
+        AppError::EndTimeMustBeAfterStartTime {},
+        MeetingDuration::between(60, 0).unwrap_err()
+    );
+    for end_time in [30, 90] {
+        assert_eq!(
+            AppError::MeetingNotWholeMinutes {
+                start_time: 0,
+                end_time,
+            },
+            MeetingDuration::between(0, end_time).unwrap_err()
+        );
+    }
+}
+
+#[test]
+fn duration_overflow_is_an_error() {
+    assert_eq!(
+        AppError::ConversionOverflow {
+            value: format!("{} - {}", i64::MAX, i64::MIN),
+            target: "i64".to_string(),
+        },
+        duration_in_minutes(i64::MIN, i64::MAX).unwrap_err()
+    );
+    let minutes = i64::from(u32::MAX) + 1;
+    assert_eq!(
+        AppError::ConversionOverflow {
+            value: minutes.to_string(),
+            target: "u32".to_string(),
+        },
+        duration_in_minutes(0, minutes * 60).unwrap_err()
+    );
+}
+
+#[test]
+fn errors_render_their_context() {
+    assert_eq!(
+        "CAL-013: Meeting conflicts with the meeting from 1970-01-01T10:00:00+00:00 (36000) to \
+         1970-01-01T11:00:00+00:00 (39600)",
+        AppError::MeetingConflictExists {
+            existing_start: 36_000,
+            existing_end: 39_600,
+            existing_start_local: "1970-01-01T10:00:00+00:00".to_string(),
+            existing_end_local: "1970-01-01T11:00:00+00:00".to_string(),
+        }
+        .to_string()
+    );
+    assert_eq!(
+        "CAL-009: Start time 08:30 does not fall within calendar bounds 09:00-17:00",
+        AppError::StartTimeDoesNotFallWithinCalendarBounds {
+            time: Time {
+                hour: 8,
+                minute: 30
+            },
+            calendar_start: Time { hour: 9, minute: 0 },
+            calendar_end: Time {
+                hour: 17,
+                minute: 0,
+            },
+        }
+        .to_string()
+    );
+    assert_eq!(
+        "CAL-031: Invalid stake amount sent. Expected: 60, sent: 30",
+        AppError::InvalidStakeAmountSent {
+            expected_amount: Uint128::from(60u128),
+            amount_sent: Uint128::from(30u128),
+        }
+        .to_string()
+    );
+    assert_eq!(
+        "CAL-059: Stake of meeting 2 on day 86400 already handled",
+        AppError::StakeAlreadyHandled {
+            day_datetime: 86_400,
+            meeting_index: 2,
+        }
+        .to_string()
+    );
+}
+
+#[test]
+fn payment_errors_name_the_expected_denom() {
+    let required = |reason: &str| AppError::PaymentRequired {
+        denom: "ujuno".to_string(),
+        reason: reason.to_string(),
+    };
+    assert_eq!(
+        required("no funds sent"),
+        payment_required(&[], "ujuno", PaymentError::NoFunds {})
+    );
+    assert_eq!(
+        required("sent uatom"),
+        payment_required(
+            &[coin(10, "uatom")],
+            "ujuno",
+            PaymentError::MissingDenom("ujuno".to_string())
+        )
+    );
+    assert_eq!(
+        required("sent uatom"),
+        payment_required(
+            &[coin(10, "uatom")],
+            "ujuno",
+            PaymentError::ExtraDenom("uatom".to_string())
+        )
+    );
+    assert_eq!(
+        required("sent ujuno, uatom, send a single coin of ujuno instead"),
+        payment_required(
+            &[coin(10, "ujuno"), coin(10, "uatom")],
+            "ujuno",
+            PaymentError::MultipleDenoms {}
+        )
+    );
+    assert_eq!(
+        AppError::PaymentError(PaymentError::NonPayable {}),
+        payment_required(&[coin(10, "ujuno")], "ujuno", PaymentError::NonPayable {})
+    );
+    assert_eq!(
+        "CAL-033: Payment in ujuno required: no funds sent",
+        required("no funds sent").to_string()
+    );
+}
+
+#[test]
+fn time_components_must_make_up_a_time_of_day() {
+    for (hour, minute) in [(24, 0), (23, 60), (u32::MAX, u32::MAX)] {
+        let time = Time { hour, minute };
+        assert_eq!(
+            Err(AppError::InvalidTimeValue { hour, minute }),
+            time.validate()
+        );
+        assert_eq!(
+            Err(AppError::InvalidTimeValue { hour, minute }),
+            NaiveTime::try_from(time)
+        );
+    }
+    for (hour, minute) in [(0, 0), (23, 59), (12, 30)] {
+        let time = Time { hour, minute };
+        assert_eq!(Ok(()), time.validate());
+        assert_eq!(
+            NaiveTime::from_hms_opt(hour, minute, 0),
+            NaiveTime::try_from(time).ok()
+        );
+    }
+    assert_eq!(
+        "CAL-016: 23:60 is not a time of the day",
+        AppError::InvalidTimeValue {
+            hour: 23,
+            minute: 60
+        }
+        .to_string()
+    );
+}
+
+#[test]
+fn get_date_time_reports_out_of_range_timestamps() {
+    let timezone = FixedOffset::east_opt(14 * 3600).unwrap();
+    for timestamp in [i64::MAX, i64::MIN, 10_000_000_000_000, -10_000_000_000_000] {
+        assert_eq!(
+            Err(AppError::LocalTimeOutOfRange {
+                timestamp,
+                utc_offset: 14 * 3600,
+            }),
+            get_date_time(timezone, Int64::new(timestamp))
+        );
+    }
+    assert_eq!(
+        "CAL-018: Timestamp 9223372036854775807 is out of range at UTC offset -3600s",
+        get_date_time(FixedOffset::west_opt(3600).unwrap(), Int64::new(i64::MAX))
+            .unwrap_err()
+            .to_string()
+    );
+    assert_eq!(
+        "CAL-021: Invalid meeting end: CAL-018: Timestamp 9223372036854775807 is out of range at UTC offset 0s",
+        AppError::InvalidMeetingEnd {
+            reason: Box::new(AppError::LocalTimeOutOfRange {
+                timestamp: i64::MAX,
+                utc_offset: 0,
+            }),
+        }
+        .to_string()
+    );
+
+    // The extremes accepted by `validate_timestamp` are representable in every timezone.
+    for timestamp in [0, MAX_TIMESTAMP] {
+        for timezone in [
+            FixedOffset::east_opt(14 * 3600).unwrap(),
+            FixedOffset::west_opt(14 * 3600).unwrap(),
+        ] {
+            assert_eq!(
+                timestamp,
+                get_date_time(timezone, Int64::new(timestamp))
+                    .unwrap()
+                    .timestamp()
+            );
+        }
+    }
+}
+
+#[test]
+fn profile_fields_are_limited_in_characters() {
+    // Characters are counted, not bytes.
+    assert_eq!(
+        Ok(Some("Café".to_string())),
+        validate_profile_field("name", "Café".to_string(), 4)
+    );
+    assert_eq!(
+        Err(AppError::ProfileFieldTooLong {
+            field: "name".to_string(),
+            length: 5,
+            max: 4,
+        }),
+        validate_profile_field("name", "Cafés".to_string(), 4)
+    );
+    assert_eq!(
+        Err(AppError::ProfileFieldHasControlCharacters {
+            field: "description".to_string(),
+        }),
+        validate_profile_field("description", "line\nbreak".to_string(), 64)
+    );
+    assert_eq!(Ok(None), validate_profile_field("name", String::new(), 4));
+}